@@ -0,0 +1,51 @@
+#!/usr/bin/env python3
+"""Recompute the "hash" field of an event pack after editing it by hand.
+
+Usage: python scripts/pack_hash.py [event_tracks.json] [--check]
+
+The hash is SHA-256 (hex) of the whole document minus the hash field, written as compact JSON
+with sorted keys, matching pack_hash in src/json_loader.rs. With --check the file is left alone
+and the exit code says whether its hash is current.
+"""
+
+import hashlib
+import json
+import sys
+
+
+def pack_hash(root):
+    unhashed = {key: value for key, value in root.items() if key != "hash"}
+    canonical = json.dumps(unhashed, sort_keys=True, separators=(",", ":"), ensure_ascii=False)
+    return hashlib.sha256(canonical.encode("utf-8")).hexdigest()
+
+
+def main():
+    args = [arg for arg in sys.argv[1:] if arg != "--check"]
+    check = "--check" in sys.argv[1:]
+    path = args[0] if args else "event_tracks.json"
+
+    with open(path, encoding="utf-8") as file:
+        content = file.read()
+    root = json.loads(content)
+    actual = pack_hash(root)
+
+    if check:
+        if root.get("hash", "").strip().lower() != actual:
+            print(f"{path}: hash is out of date (should be {actual})")
+            return 1
+        print(f"{path}: hash is current")
+        return 0
+
+    # Replace the value in place so the file's layout and field order stay as they are
+    declared = root.get("hash")
+    if declared is None:
+        sys.exit(f"{path} has no \"hash\" field to update")
+    content = content.replace(json.dumps(declared), json.dumps(actual), 1)
+    with open(path, "w", encoding="utf-8", newline="") as file:
+        file.write(content)
+    print(f"{path}: hash set to {actual}")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
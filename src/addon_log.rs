@@ -0,0 +1,86 @@
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+
+use crate::time_utils::get_current_unix_time;
+
+/// Lines kept for the in-addon log viewer
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Level {
+    Error,
+    Warning,
+    Info,
+    Debug,
+}
+
+impl Level {
+    pub const ALL: [Level; 4] = [Level::Error, Level::Warning, Level::Info, Level::Debug];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Level::Error => "Error",
+            Level::Warning => "Warning",
+            Level::Info => "Info",
+            Level::Debug => "Debug",
+        }
+    }
+
+    pub fn color(&self) -> [f32; 4] {
+        match self {
+            Level::Error => [1.0, 0.4, 0.4, 1.0],
+            Level::Warning => [1.0, 0.8, 0.3, 1.0],
+            Level::Info => [0.9, 0.9, 0.9, 1.0],
+            Level::Debug => [0.6, 0.6, 0.6, 1.0],
+        }
+    }
+
+    fn nexus_level(&self) -> nexus::log::LogLevel {
+        match self {
+            Level::Error => nexus::log::LogLevel::Critical,
+            Level::Warning => nexus::log::LogLevel::Warning,
+            Level::Info => nexus::log::LogLevel::Info,
+            Level::Debug => nexus::log::LogLevel::Debug,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Unix seconds
+    pub timestamp: i64,
+    pub level: Level,
+    pub message: String,
+}
+
+/// Recent log lines, oldest first
+pub static LOG_BUFFER: Lazy<Mutex<VecDeque<LogEntry>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// Write to the Nexus log and keep a copy for the in-addon log viewer
+pub fn log(level: Level, message: impl Into<String>) {
+    let message = message.into();
+    nexus::log::log(level.nexus_level(), "Event Timers", &message);
+
+    let mut buffer = LOG_BUFFER.lock();
+    if buffer.len() >= MAX_ENTRIES {
+        buffer.pop_front();
+    }
+    buffer.push_back(LogEntry {
+        timestamp: get_current_unix_time(),
+        level,
+        message,
+    });
+}
+
+pub fn error(message: impl Into<String>) {
+    log(Level::Error, message);
+}
+
+pub fn warn(message: impl Into<String>) {
+    log(Level::Warning, message);
+}
+
+pub fn info(message: impl Into<String>) {
+    log(Level::Info, message);
+}
@@ -0,0 +1,216 @@
+// Timestamped copies of event_tracks.json and user_config.json in <addon dir>/backups.
+//
+// Track data is backed up before every update, settings at most once a day on load.
+// The oldest copies beyond MAX_BACKUPS_PER_KIND are pruned. Backups are ordered by the
+// stamp in their file name, which survives copying the folder around (modification times
+// don't). The legacy single event_tracks.json.backup (from older versions) is still listed
+// and restorable.
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use nexus::paths::get_addon_dir;
+use std::{fs, path::PathBuf, time::UNIX_EPOCH};
+
+use crate::config::{apply_user_overrides, extract_user_overrides, get_user_config_path, load_user_config};
+use crate::json_loader::get_json_path;
+use crate::time_utils::get_current_unix_time;
+
+const MAX_BACKUPS_PER_KIND: usize = 10;
+const AUTO_CONFIG_BACKUP_INTERVAL: i64 = 24 * 3600;
+/// Local time in backup file names, e.g. "user_config_20240131_184500.json"
+const STAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+const STAMP_LEN: usize = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackupKind {
+    TrackData,
+    UserConfig,
+}
+
+impl BackupKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BackupKind::TrackData => "Event data",
+            BackupKind::UserConfig => "Settings",
+        }
+    }
+
+    fn file_prefix(&self) -> &'static str {
+        match self {
+            BackupKind::TrackData => "event_tracks_",
+            BackupKind::UserConfig => "user_config_",
+        }
+    }
+
+    fn live_path(&self) -> Option<PathBuf> {
+        match self {
+            BackupKind::TrackData => get_json_path(),
+            BackupKind::UserConfig => get_user_config_path(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupEntry {
+    pub path: PathBuf,
+    pub kind: BackupKind,
+    /// Unix seconds
+    pub created: i64,
+    pub size: u64,
+    /// Orders backups made within the same second ("_2", "_3", ... in the file name)
+    sequence: u32,
+}
+
+impl BackupEntry {
+    pub fn file_name(&self) -> String {
+        self.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+}
+
+fn backups_dir() -> Option<PathBuf> {
+    get_addon_dir("event_timers").map(|p| p.join("backups"))
+}
+
+/// Backup file written by versions before the backup manager
+fn legacy_track_backup() -> Option<PathBuf> {
+    get_json_path().map(|p| p.with_extension("json.backup"))
+}
+
+/// Copy the current file of `kind` into the backups folder
+pub fn create_backup(kind: BackupKind) -> Result<PathBuf, String> {
+    let source = kind.live_path().ok_or("Addon directory not available")?;
+    if !source.exists() {
+        return Err(format!("{} does not exist", source.display()));
+    }
+
+    let dir = backups_dir().ok_or("Addon directory not available")?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let stamp = Local::now().format(STAMP_FORMAT).to_string();
+    let mut target = dir.join(format!("{}{}.json", kind.file_prefix(), stamp));
+    let mut sequence = 2;
+    while target.exists() {
+        target = dir.join(format!("{}{}_{}.json", kind.file_prefix(), stamp, sequence));
+        sequence += 1;
+    }
+    fs::copy(&source, &target).map_err(|e| format!("Failed to back up {}: {}", source.display(), e))?;
+
+    prune(kind);
+    Ok(target)
+}
+
+/// Back up the settings file unless that already happened within the last day
+pub fn auto_backup_user_config() {
+    let newest = list_backups()
+        .into_iter()
+        .find(|b| b.kind == BackupKind::UserConfig)
+        .map(|b| b.created);
+    if newest.is_none_or(|created| get_current_unix_time() - created >= AUTO_CONFIG_BACKUP_INTERVAL) {
+        let _ = create_backup(BackupKind::UserConfig);
+    }
+}
+
+/// All backups, newest first
+pub fn list_backups() -> Vec<BackupEntry> {
+    let mut backups = Vec::new();
+
+    if let Some(dir) = backups_dir() {
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let kind = [BackupKind::TrackData, BackupKind::UserConfig]
+                    .into_iter()
+                    .find(|k| name.starts_with(k.file_prefix()) && name.ends_with(".json"));
+                if let Some(kind) = kind {
+                    if let Some(backup) = describe(entry.path(), kind) {
+                        backups.push(backup);
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(legacy) = legacy_track_backup().filter(|p| p.exists()) {
+        if let Some(backup) = describe(legacy, BackupKind::TrackData) {
+            backups.push(backup);
+        }
+    }
+
+    backups.sort_by_key(|b| std::cmp::Reverse((b.created, b.sequence)));
+    backups
+}
+
+/// Creation time and same-second sequence from a name like "event_tracks_20240131_184500_2.json"
+fn parse_stamp(name: &str, kind: BackupKind) -> Option<(i64, u32)> {
+    let stamp = name.strip_prefix(kind.file_prefix())?.strip_suffix(".json")?;
+    let (stamp, suffix) = (stamp.get(..STAMP_LEN)?, stamp.get(STAMP_LEN..)?);
+    let sequence = match suffix {
+        "" => 1,
+        _ => suffix.strip_prefix('_')?.parse().ok()?,
+    };
+    let created = NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT)
+        .ok()?
+        .and_local_timezone(Local)
+        .earliest()?
+        .timestamp();
+    Some((created, sequence))
+}
+
+fn describe(path: PathBuf, kind: BackupKind) -> Option<BackupEntry> {
+    let metadata = fs::metadata(&path).ok()?;
+    let stamped = path.file_name().and_then(|name| parse_stamp(&name.to_string_lossy(), kind));
+    // The legacy backup (and any renamed file) has no stamp, fall back to when it was written
+    let (created, sequence) = stamped.unwrap_or_else(|| {
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        (modified, 0)
+    });
+    Some(BackupEntry { path, kind, created, size: metadata.len(), sequence })
+}
+
+/// Replace the live file with a backup and reload it. The file being replaced is backed up first,
+/// so a restore can itself be undone. Must not be called while RUNTIME_CONFIG is locked.
+pub fn restore_backup(backup: &BackupEntry) -> Result<(), String> {
+    let target = backup.kind.live_path().ok_or("Addon directory not available")?;
+    if target.exists() {
+        create_backup(backup.kind)?;
+    }
+
+    match backup.kind {
+        BackupKind::TrackData => {
+            // Capture customizations against the current defaults before they change
+            extract_user_overrides();
+            fs::copy(&backup.path, &target).map_err(|e| format!("Failed to restore: {}", e))?;
+            apply_user_overrides();
+        }
+        BackupKind::UserConfig => {
+            fs::copy(&backup.path, &target).map_err(|e| format!("Failed to restore: {}", e))?;
+            load_user_config();
+        }
+    }
+    Ok(())
+}
+
+pub fn delete_backup(backup: &BackupEntry) -> Result<(), String> {
+    fs::remove_file(&backup.path).map_err(|e| format!("Failed to delete {}: {}", backup.file_name(), e))
+}
+
+/// Local creation time for display
+pub fn format_created(backup: &BackupEntry) -> String {
+    DateTime::from_timestamp(backup.created, 0)
+        .map(|d| d.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+fn prune(kind: BackupKind) {
+    let timestamped: Vec<BackupEntry> = list_backups()
+        .into_iter()
+        .filter(|b| b.kind == kind && Some(&b.path) != legacy_track_backup().as_ref())
+        .collect();
+    for old in timestamped.iter().skip(MAX_BACKUPS_PER_KIND) {
+        let _ = fs::remove_file(&old.path);
+    }
+}
@@ -0,0 +1,55 @@
+// Per-category keybinds that show only that category on the timeline until pressed again.
+//
+// Nexus has no way to unregister a keybind while the addon is loaded, so a category's
+// binding is registered the first time it's enabled and stays until unload; its handler
+// does nothing once the category is no longer in the keybind list.
+
+use nexus::keybind::register_keybind_with_string;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashSet;
+use std::ffi::{c_char, CStr};
+
+use crate::config::RUNTIME_CONFIG;
+
+const IDENTIFIER_PREFIX: &str = "Event Timers: Only ";
+
+/// Categories with a keybind registered this session
+static REGISTERED: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+extern "C-unwind" fn solo_category_keybind(identifier: *const c_char, is_release: bool) {
+    if is_release || identifier.is_null() {
+        return;
+    }
+    let identifier = unsafe { CStr::from_ptr(identifier) }.to_string_lossy();
+    let Some(category) = identifier.strip_prefix(IDENTIFIER_PREFIX) else {
+        return;
+    };
+
+    let mut config = RUNTIME_CONFIG.lock();
+    if !config.category_keybinds.iter().any(|c| c == category) {
+        return;
+    }
+    if config.solo_category.as_deref() == Some(category) {
+        config.solo_category = None;
+    } else {
+        config.solo_category = Some(category.to_string());
+    }
+}
+
+/// Register the keybind for a category (unbound by default), once per session
+pub fn register(category: &str) {
+    if !REGISTERED.lock().insert(category.to_string()) {
+        return;
+    }
+    register_keybind_with_string(format!("{}{}", IDENTIFIER_PREFIX, category), solo_category_keybind, "")
+        .revert_on_unload();
+}
+
+/// Register the keybinds of every category in the saved list
+pub fn register_all() {
+    let categories = RUNTIME_CONFIG.snapshot().category_keybinds.clone();
+    for category in &categories {
+        register(category);
+    }
+}
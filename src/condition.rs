@@ -0,0 +1,457 @@
+// Visibility conditions for events.
+//
+// A condition is a small boolean expression evaluated against each occurrence's start time:
+//   day_of_week == "Sat" || tyria_night
+//   hour >= 18 && hour < 23
+//   !(day_of_week == "Mon") and map_id == 1206
+//
+// Operators: || (or), && (and), ! (not), == != < <= > >=, parentheses.
+// Values are numbers, "strings" (compared case-insensitively) and true/false.
+// An occurrence whose condition is false is hidden from the timeline and never notified.
+
+use chrono::{DateTime, Datelike, Local, Timelike, Utc};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::time_utils::calculate_tyria_time;
+
+/// How many cycles ahead to look for an occurrence that passes an event's condition
+pub const MAX_LOOKAHEAD_CYCLES: i64 = 200;
+
+/// Deepest nesting of parentheses and '!' accepted, keeps the recursive parser off the stack limit
+const MAX_DEPTH: usize = 64;
+
+/// Map the player is on (0 if unknown), read once per frame instead of on every evaluation
+static MAP_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Read the current map for this frame's evaluations. Call once per frame.
+pub fn refresh_map_id() {
+    MAP_ID.store(crate::mumble::current_map_id().unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Variables available in conditions, with a short description for the editor tooltip
+pub const VARIABLES: [(&str, &str); 10] = [
+    ("day_of_week", "\"Mon\" .. \"Sun\" (local)"),
+    ("hour", "0-23 (local)"),
+    ("minute", "0-59 (local)"),
+    ("day_of_month", "1-31 (local)"),
+    ("month", "1-12 (local)"),
+    ("utc_hour", "0-23 (UTC)"),
+    ("tyria_hour", "0-23 (Tyrian time)"),
+    ("tyria_day", "true from Tyrian 06:00 to 20:00"),
+    ("tyria_night", "true from Tyrian 21:00 to 05:00"),
+    ("map_id", "map the player is currently on (0 if unknown)"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Variable(&'static str),
+    Not(Box<Expr>),
+    /// Chains are flat lists, so a long "a || b || c ..." doesn't nest
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Compare(CompareOp, Box<Expr>, Box<Expr>),
+}
+
+/// A parsed condition expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    expr: Expr,
+}
+
+impl Condition {
+    /// Evaluate the condition for an occurrence starting at `time` (unix seconds)
+    pub fn evaluate(&self, time: i64) -> bool {
+        truthy(&eval(&self.expr, &Context::new(time)))
+    }
+}
+
+/// Parse a condition expression
+pub fn parse_condition(source: &str) -> Result<Condition, String> {
+    let tokens = tokenize(source)?;
+    if tokens.is_empty() {
+        return Err("Empty condition".to_string());
+    }
+
+    let mut parser = Parser { tokens, pos: 0, depth: 0 };
+    let expr = parser.parse_or()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("Unexpected {}", token.describe()));
+    }
+    Ok(Condition { expr })
+}
+
+// === Tokenizer ===
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Text(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Ident(name) => format!("'{}'", name),
+            Token::Number(n) => format!("'{}'", n),
+            Token::Text(s) => format!("\"{}\"", s),
+            Token::Op(op) => format!("'{}'", op),
+            Token::LParen => "'('".to_string(),
+            Token::RParen => "')'".to_string(),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    const OPERATORS: [&str; 10] = ["||", "&&", "==", "!=", "<=", ">=", "<", ">", "!", "="];
+
+    let mut tokens = Vec::new();
+    let mut rest = source.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        if c == '(' || c == ')' {
+            tokens.push(if c == '(' { Token::LParen } else { Token::RParen });
+            rest = &rest[1..];
+        } else if c == '"' || c == '\'' {
+            let end = rest[1..].find(c).ok_or("Unterminated string")?;
+            tokens.push(Token::Text(rest[1..=end].to_string()));
+            rest = &rest[end + 2..];
+        } else if c.is_ascii_digit() {
+            let end = rest.find(|ch: char| !ch.is_ascii_digit() && ch != '.').unwrap_or(rest.len());
+            let number = rest[..end].parse().map_err(|_| format!("Invalid number '{}'", &rest[..end]))?;
+            tokens.push(Token::Number(number));
+            rest = &rest[end..];
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest.find(|ch: char| !ch.is_alphanumeric() && ch != '_').unwrap_or(rest.len());
+            let word = rest[..end].to_lowercase();
+            tokens.push(match word.as_str() {
+                "and" => Token::Op("&&"),
+                "or" => Token::Op("||"),
+                "not" => Token::Op("!"),
+                _ => Token::Ident(word),
+            });
+            rest = &rest[end..];
+        } else if let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(*op)) {
+            // A single '=' is accepted as equality
+            tokens.push(Token::Op(if *op == "=" { "==" } else { op }));
+            rest = &rest[op.len()..];
+        } else {
+            return Err(format!("Unexpected character '{}'", c));
+        }
+        rest = rest.trim_start();
+    }
+
+    Ok(tokens)
+}
+
+// === Parser ===
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Parentheses and '!' currently open
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_op(&mut self, op: &'static str) -> bool {
+        if self.peek() == Some(&Token::Op(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Run `parse` one level deeper, failing past MAX_DEPTH
+    fn nested(&mut self, parse: impl FnOnce(&mut Self) -> Result<Expr, String>) -> Result<Expr, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err(format!("Condition is nested too deeply (max {} levels)", MAX_DEPTH));
+        }
+        self.depth += 1;
+        let expr = parse(self);
+        self.depth -= 1;
+        expr
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut operands = vec![self.parse_and()?];
+        while self.eat_op("||") {
+            operands.push(self.parse_and()?);
+        }
+        Ok(if operands.len() == 1 { operands.pop().unwrap() } else { Expr::Or(operands) })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut operands = vec![self.parse_not()?];
+        while self.eat_op("&&") {
+            operands.push(self.parse_not()?);
+        }
+        Ok(if operands.len() == 1 { operands.pop().unwrap() } else { Expr::And(operands) })
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, String> {
+        if self.eat_op("!") {
+            return self.nested(|parser| Ok(Expr::Not(Box::new(parser.parse_not()?))));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, String> {
+        let left = self.parse_primary()?;
+        let op = match self.peek() {
+            Some(Token::Op("==")) => CompareOp::Eq,
+            Some(Token::Op("!=")) => CompareOp::Ne,
+            Some(Token::Op("<")) => CompareOp::Lt,
+            Some(Token::Op("<=")) => CompareOp::Le,
+            Some(Token::Op(">")) => CompareOp::Gt,
+            Some(Token::Op(">=")) => CompareOp::Ge,
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.parse_primary()?;
+        Ok(Expr::Compare(op, Box::new(left), Box::new(right)))
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Literal(Value::Number(n))),
+            Some(Token::Text(s)) => Ok(Expr::Literal(Value::Text(s))),
+            Some(Token::Ident(name)) => match name.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                _ => VARIABLES
+                    .iter()
+                    .find(|(variable, _)| *variable == name)
+                    .map(|(variable, _)| Expr::Variable(variable))
+                    .ok_or_else(|| format!("Unknown variable '{}'", name)),
+            },
+            Some(Token::LParen) => self.nested(|parser| {
+                let expr = parser.parse_or()?;
+                match parser.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err("Missing ')'".to_string()),
+                }
+            }),
+            Some(token) => Err(format!("Unexpected {}", token.describe())),
+            None => Err("Unexpected end of condition".to_string()),
+        }
+    }
+}
+
+// === Evaluation ===
+
+/// The clock readings of one occurrence, worked out once per evaluation, and the current map
+struct Context {
+    utc: DateTime<Utc>,
+    local: DateTime<Local>,
+    tyria_hour: i32,
+    map_id: u32,
+}
+
+impl Context {
+    fn new(time: i64) -> Self {
+        let utc = DateTime::from_timestamp(time, 0).unwrap_or_default();
+        let (tyria_hour, _) = calculate_tyria_time(time);
+        Self { utc, local: utc.with_timezone(&Local), tyria_hour, map_id: MAP_ID.load(Ordering::Relaxed) }
+    }
+}
+
+fn eval(expr: &Expr, context: &Context) -> Value {
+    match expr {
+        Expr::Literal(value) => value.clone(),
+        Expr::Variable(name) => variable(name, context),
+        Expr::Not(inner) => Value::Bool(!truthy(&eval(inner, context))),
+        Expr::And(operands) => Value::Bool(operands.iter().all(|operand| truthy(&eval(operand, context)))),
+        Expr::Or(operands) => Value::Bool(operands.iter().any(|operand| truthy(&eval(operand, context)))),
+        Expr::Compare(op, left, right) => Value::Bool(compare(*op, &eval(left, context), &eval(right, context))),
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Bool(b) => *b,
+        Value::Number(n) => *n != 0.0,
+        Value::Text(s) => !s.is_empty(),
+    }
+}
+
+/// Mismatched types compare unequal (and never ordered)
+fn compare(op: CompareOp, left: &Value, right: &Value) -> bool {
+    let ordering = match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.partial_cmp(b),
+        (Value::Text(a), Value::Text(b)) => Some(a.to_lowercase().cmp(&b.to_lowercase())),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    let Some(ordering) = ordering else {
+        return op == CompareOp::Ne;
+    };
+    match op {
+        CompareOp::Eq => ordering.is_eq(),
+        CompareOp::Ne => ordering.is_ne(),
+        CompareOp::Lt => ordering.is_lt(),
+        CompareOp::Le => ordering.is_le(),
+        CompareOp::Gt => ordering.is_gt(),
+        CompareOp::Ge => ordering.is_ge(),
+    }
+}
+
+fn variable(name: &str, context: &Context) -> Value {
+    let Context { utc, local, tyria_hour, map_id } = context;
+    match name {
+        "day_of_week" => Value::Text(local.format("%a").to_string()),
+        "hour" => Value::Number(local.hour() as f64),
+        "minute" => Value::Number(local.minute() as f64),
+        "day_of_month" => Value::Number(local.day() as f64),
+        "month" => Value::Number(local.month() as f64),
+        "utc_hour" => Value::Number(utc.hour() as f64),
+        "tyria_hour" => Value::Number(*tyria_hour as f64),
+        "tyria_day" => Value::Bool((6..20).contains(tyria_hour)),
+        "tyria_night" => Value::Bool(!(5..21).contains(tyria_hour)),
+        "map_id" => Value::Number(*map_id as f64),
+        _ => Value::Bool(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2025-09-30 20:30:00 UTC, Tyrian 06:00
+    const TIME: i64 = 1759264200;
+
+    fn eval_at(source: &str, time: i64) -> bool {
+        parse_condition(source).unwrap().evaluate(time)
+    }
+
+    fn eval_str(source: &str) -> bool {
+        eval_at(source, TIME)
+    }
+
+    fn error(source: &str) -> String {
+        parse_condition(source).unwrap_err()
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(parse_condition("true || false && false"), parse_condition("true || (false && false)"));
+        assert!(eval_str("true || false && false"));
+        assert!(!eval_str("false && true || false"));
+        assert!(eval_str("false or true and true"));
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and_or() {
+        assert!(eval_str("!false"));
+        assert!(!eval_str("not true"));
+        assert!(eval_str("!true || true"));
+        assert!(!eval_str("!(true || true)"));
+        assert!(eval_str("!!true"));
+        assert!(eval_str("!utc_hour == 21"));
+    }
+
+    #[test]
+    fn parentheses_override_precedence() {
+        assert!(!eval_str("(true || false) && false"));
+        assert!(eval_str("((true))"));
+        assert!(eval_str("(false || (true && true)) && !(false)"));
+    }
+
+    #[test]
+    fn strings_and_numbers_compare_by_type() {
+        assert!(eval_str("3 < 10"));
+        assert!(!eval_str("\"3\" < \"10\""));
+        assert!(eval_str("\"Sat\" == 'sat'"));
+        assert!(!eval_str("\"5\" == 5"));
+        assert!(eval_str("\"5\" != 5"));
+        assert!(!eval_str("\"5\" < 6"));
+        assert!(!eval_str("\"5\" >= 5"));
+    }
+
+    #[test]
+    fn single_equals_is_equality() {
+        assert_eq!(parse_condition("utc_hour = 20"), parse_condition("utc_hour == 20"));
+        assert!(eval_str("utc_hour = 20"));
+        assert!(!eval_str("utc_hour = 21"));
+    }
+
+    #[test]
+    fn variables_read_the_occurrence_time() {
+        assert!(eval_str("tyria_hour == 6 && tyria_day && !tyria_night"));
+        assert!(eval_at("tyria_night", TIME - 10 * 60));
+        assert!(eval_at("utc_hour >= 21", TIME + 30 * 60));
+        assert!(eval_str("HOUR >= 0 AND hour <= 23"));
+    }
+
+    #[test]
+    fn reports_bad_input() {
+        assert_eq!(error(""), "Empty condition");
+        assert_eq!(error("   "), "Empty condition");
+        assert_eq!(error("weather == 1"), "Unknown variable 'weather'");
+        assert_eq!(error("hour >"), "Unexpected end of condition");
+        assert_eq!(error("(hour > 1"), "Missing ')'");
+        assert_eq!(error("hour > 1)"), "Unexpected ')'");
+        assert_eq!(error("true true"), "Unexpected 'true'");
+        assert_eq!(error("&& true"), "Unexpected '&&'");
+        assert_eq!(error("day_of_week == \"Sat"), "Unterminated string");
+        assert_eq!(error("hour # 3"), "Unexpected character '#'");
+        assert_eq!(error("hour > 1.2.3"), "Invalid number '1.2.3'");
+    }
+
+    #[test]
+    fn nesting_is_capped() {
+        let nested = |depth: usize| format!("{}true{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(eval_str(&nested(MAX_DEPTH)));
+        assert_eq!(error(&nested(MAX_DEPTH + 1)), "Condition is nested too deeply (max 64 levels)");
+        assert_eq!(error(&"!".repeat(100_000)), "Condition is nested too deeply (max 64 levels)");
+        assert_eq!(error(&"(".repeat(100_000)), "Condition is nested too deeply (max 64 levels)");
+    }
+
+    #[test]
+    fn long_chains_dont_nest() {
+        let chain = vec!["hour == 99"; 10_000].join(" || ") + " || true";
+        assert!(eval_str(&chain));
+        let chain = vec!["true"; 10_000].join(" && ");
+        assert!(eval_str(&chain));
+    }
+
+    #[test]
+    fn map_id_comes_from_the_frame() {
+        MAP_ID.store(1206, Ordering::Relaxed);
+        assert!(eval_str("map_id == 1206"));
+        MAP_ID.store(0, Ordering::Relaxed);
+        assert!(eval_str("map_id == 0"));
+    }
+}
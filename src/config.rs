@@ -1,10 +1,16 @@
 use nexus::paths::get_addon_dir;
 use once_cell::sync::Lazy;
-use parking_lot::Mutex;
+use parking_lot::{Mutex, MutexGuard};
 use serde::{Deserialize, Serialize};
 use std::{collections::{HashMap, HashSet}, fs, hash::Hash, path::PathBuf};
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
 
-use crate::json_loader::{load_tracks_from_json, EventTrack};
+use crate::addon_log;
+use crate::diagnostics::timed_lock;
+use crate::json_loader::{load_tracks_from_json, EventTrack, TimelineEvent};
+use crate::notifications::NOTIFICATION_STATE;
 
 // === Notification Types ===
 
@@ -35,6 +41,72 @@ impl TrackedEventId {
     }
 }
 
+/// How strongly a tracked event alerts: low only lists it in the Upcoming panel, high gets a
+/// distinct, longer toast that skips the global toast cooldown and may raise the center-screen alert
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum EventPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl EventPriority {
+    pub const ALL: [EventPriority; 3] = [EventPriority::Low, EventPriority::Normal, EventPriority::High];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventPriority::Low => "Low",
+            EventPriority::Normal => "Normal",
+            EventPriority::High => "High",
+        }
+    }
+
+    /// Next level when cycling through priorities in the UI
+    pub fn next(&self) -> Self {
+        match self {
+            EventPriority::Low => EventPriority::Normal,
+            EventPriority::Normal => EventPriority::High,
+            EventPriority::High => EventPriority::Low,
+        }
+    }
+}
+
+/// Priority assigned to one event
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EventPriorityEntry {
+    #[serde(flatten)]
+    pub event_id: TrackedEventId,
+    pub priority: EventPriority,
+}
+
+/// Travel time set for one event: its reminders fire this much earlier
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EventTravelBuffer {
+    #[serde(flatten)]
+    pub event_id: TrackedEventId,
+    pub minutes: u32,
+}
+
+/// Personal note for one event ("bring markers"), shown in its toasts and the Upcoming panel
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EventNote {
+    #[serde(flatten)]
+    pub event_id: TrackedEventId,
+    pub note: String,
+}
+
+/// Occurrence of an event the player was on the map for (see `auto_attendance`)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AttendanceRecord {
+    #[serde(flatten)]
+    pub event_id: TrackedEventId,
+    pub start_time: i64,
+}
+
+/// Attendance older than this many days is dropped
+pub const ATTENDANCE_DAYS: i64 = 30;
+
 /// Toast notification position anchor
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum ToastPosition {
@@ -51,7 +123,7 @@ impl Default for ToastPosition {
 }
 
 /// A single reminder configuration
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ReminderConfig {
     /// Display name for this reminder (e.g., "Heads up!", "Starting soon!")
     pub name: String,
@@ -62,9 +134,43 @@ pub struct ReminderConfig {
     /// For ongoing reminders (minutes_before=0): interval in minutes between notifications
     #[serde(default = "default_ongoing_interval")]
     pub ongoing_interval_minutes: u32,
+    /// Count down to the pre-event start instead of the main start
+    /// (only applies to events with a pre-event segment)
+    #[serde(default)]
+    pub before_pre_event: bool,
+    /// Count down to the end of a running event instead ("5 minutes left")
+    #[serde(default)]
+    pub before_end: bool,
+    /// Fire again this many times, `repeat_spacing_minutes` apart, before the event
+    #[serde(default)]
+    pub repeat_count: u32,
+    #[serde(default = "default_repeat_spacing")]
+    pub repeat_spacing_minutes: u32,
 }
 
 fn default_ongoing_interval() -> u32 { 5 }
+fn default_repeat_spacing() -> u32 { 5 }
+
+impl ReminderConfig {
+    /// The latest repetition due `seconds_until` seconds before the target (0 is the first firing),
+    /// None before the first one. Repetitions that would fall at or after the start are dropped.
+    pub fn due_repetition(&self, seconds_until: i64) -> Option<u32> {
+        let first = self.minutes_before as i64 * 60;
+        if seconds_until <= 0 || seconds_until > first {
+            return None;
+        }
+        let spacing = self.repeat_spacing_minutes as i64 * 60;
+        if spacing == 0 {
+            return Some(0);
+        }
+        Some(((first - seconds_until) / spacing).min(self.repeat_count as i64) as u32)
+    }
+
+    /// Seconds before the target at which a repetition comes due
+    pub fn repetition_lead(&self, repetition: u32) -> i64 {
+        (self.minutes_before as i64 - repetition as i64 * self.repeat_spacing_minutes as i64) * 60
+    }
+}
 
 impl Default for ReminderConfig {
     fn default() -> Self {
@@ -73,6 +179,10 @@ impl Default for ReminderConfig {
             minutes_before: 5,
             text_color: [0.5, 1.0, 0.5, 1.0], // Green
             ongoing_interval_minutes: 5,
+            before_pre_event: false,
+            before_end: false,
+            repeat_count: 0,
+            repeat_spacing_minutes: default_repeat_spacing(),
         }
     }
 }
@@ -84,24 +194,36 @@ fn default_reminders() -> Vec<ReminderConfig> {
             minutes_before: 10,
             text_color: [0.5, 0.8, 1.0, 1.0], // Light blue
             ongoing_interval_minutes: 5,
+            before_pre_event: false,
+            before_end: false,
+            repeat_count: 0,
+            repeat_spacing_minutes: default_repeat_spacing(),
         },
         ReminderConfig {
             name: "Starting soon!".to_string(),
             minutes_before: 5,
             text_color: [1.0, 0.8, 0.2, 1.0], // Yellow/orange
             ongoing_interval_minutes: 5,
+            before_pre_event: false,
+            before_end: false,
+            repeat_count: 0,
+            repeat_spacing_minutes: default_repeat_spacing(),
         },
         ReminderConfig {
             name: "Happening now!".to_string(),
             minutes_before: 0,
             text_color: [0.5, 1.0, 0.5, 1.0], // Green
             ongoing_interval_minutes: 5,
+            before_pre_event: false,
+            before_end: false,
+            repeat_count: 0,
+            repeat_spacing_minutes: default_repeat_spacing(),
         },
     ]
 }
 
 /// Settings for the notification system
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct NotificationConfig {
     #[serde(default = "default_true")]
     pub toast_enabled: bool,
@@ -112,6 +234,10 @@ pub struct NotificationConfig {
     #[serde(default = "default_reminders")]
     pub reminders: Vec<ReminderConfig>,
 
+    /// Tracks that define `reminder_minutes` replace the reminders' start countdowns with those lead times
+    #[serde(default = "default_true")]
+    pub use_track_reminder_defaults: bool,
+
     #[serde(default = "default_toast_duration")]
     pub toast_duration_seconds: f32,
 
@@ -152,6 +278,254 @@ pub struct NotificationConfig {
 
     #[serde(default = "default_toast_track_color")]
     pub toast_track_color: [f32; 4],
+
+    /// Attach the Upcoming panel to a side of the main window so they move together
+    #[serde(default)]
+    pub upcoming_panel_docked: bool,
+
+    #[serde(default)]
+    pub upcoming_panel_dock_side: DockSide,
+
+    /// Panel position as a fraction of the display size (see relative_window_positions)
+    #[serde(default)]
+    pub upcoming_panel_position: Option<[f32; 2]>,
+
+    #[serde(default)]
+    pub upcoming_panel_locked: bool,
+
+    #[serde(default)]
+    pub upcoming_panel_size_locked: bool,
+
+    /// Panel ignores the mouse (re-enable from the settings)
+    #[serde(default)]
+    pub upcoming_panel_click_through: bool,
+
+    #[serde(default)]
+    pub upcoming_panel_hide_background: bool,
+
+    #[serde(default = "default_true")]
+    pub upcoming_panel_show_title_bar: bool,
+
+    /// Background opacity of the panel
+    #[serde(default = "default_upcoming_panel_opacity")]
+    pub upcoming_panel_opacity: f32,
+
+    /// Column the panel is sorted by (click a column header to change it)
+    #[serde(default)]
+    pub upcoming_sort_column: UpcomingColumn,
+
+    #[serde(default)]
+    pub upcoming_sort_descending: bool,
+
+    #[serde(default)]
+    pub upcoming_show_track_column: bool,
+
+    #[serde(default)]
+    pub upcoming_show_waypoint_column: bool,
+
+    #[serde(default)]
+    pub upcoming_group_by: UpcomingGrouping,
+
+    /// Group headers collapsed in the panel
+    #[serde(default)]
+    pub upcoming_collapsed_groups: Vec<String>,
+
+    /// Final countdown: rows of events starting within this many seconds pulse (0 = off)
+    #[serde(default = "default_upcoming_countdown_seconds")]
+    pub upcoming_countdown_seconds: u32,
+
+    #[serde(default = "default_true")]
+    pub upcoming_countdown_pulse: bool,
+
+    /// Beep each second of the final countdown, and once more at the spawn
+    #[serde(default)]
+    pub upcoming_countdown_beep: bool,
+
+    /// Time text color of events that haven't started yet
+    #[serde(default = "default_upcoming_time_color")]
+    pub upcoming_time_color: [f32; 4],
+
+    /// Time text color of running events
+    #[serde(default = "default_active_time_color")]
+    pub active_time_color: [f32; 4],
+
+    /// Events starting within this many seconds use the imminent color (0 = off)
+    #[serde(default)]
+    pub imminent_seconds: u32,
+
+    #[serde(default = "default_imminent_time_color")]
+    pub imminent_time_color: [f32; 4],
+
+    /// Global mute: no reminders fire while set (the Quick Access icon shows a muted variant)
+    #[serde(default)]
+    pub muted: bool,
+
+    /// Unix time until which every reminder is snoozed ("busy for N minutes")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snoozed_until: Option<i64>,
+
+    /// Snooze length used by the snooze keybind, in minutes
+    #[serde(default = "default_snooze_keybind_minutes")]
+    pub snooze_keybind_minutes: u32,
+
+    /// Show a badge on the Quick Access icon counting tracked events starting soon
+    #[serde(default)]
+    pub qa_badge_enabled: bool,
+
+    /// "Soon" for the Quick Access badge, in minutes
+    #[serde(default = "default_qa_badge_minutes")]
+    pub qa_badge_minutes: u32,
+
+    /// Name patterns (see watchlist.rs); matching events are notified as if tracked
+    #[serde(default)]
+    pub watchlist: Vec<String>,
+
+    /// Events with a non-normal priority
+    #[serde(default)]
+    pub event_priorities: Vec<EventPriorityEntry>,
+
+    /// Events to arrive early for ("leave now" marker, earlier reminders)
+    #[serde(default)]
+    pub travel_buffers: Vec<EventTravelBuffer>,
+
+    /// Events tagged as filling early by the user, on top of those the data pack tags
+    #[serde(default)]
+    pub fills_early_events: Vec<TrackedEventId>,
+
+    /// Personal notes for events
+    #[serde(default)]
+    pub event_notes: Vec<EventNote>,
+
+    /// Check tracked events in as attended when on one of their track's maps while they run
+    #[serde(default)]
+    pub auto_attendance: bool,
+
+    /// After checking in, mute the event's remaining reminders until daily reset
+    #[serde(default)]
+    pub attendance_mutes_day: bool,
+
+    /// Attended occurrences of the last ATTENDANCE_DAYS days
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attendance: Vec<AttendanceRecord>,
+
+    /// Extra minutes of warning for events that fill early (0 = off)
+    #[serde(default = "default_fills_early_minutes")]
+    pub fills_early_minutes: u32,
+
+    #[serde(default = "default_high_priority_toast_color")]
+    pub high_priority_toast_color: [f32; 4],
+
+    /// High-priority toasts stay this many times longer than the toast duration
+    #[serde(default = "default_high_priority_duration_factor")]
+    pub high_priority_duration_factor: f32,
+
+    /// Hold toasts while the game is unfocused or loading and show them on return
+    #[serde(default = "default_true")]
+    pub hold_while_away: bool,
+
+    /// Reminders missed during a loading screen or a clock jump (sleep, suspend)
+    #[serde(default)]
+    pub catch_up_policy: CatchUpPolicy,
+
+    /// Large center-screen countdown for imminent high-priority events
+    #[serde(default)]
+    pub center_alert_enabled: bool,
+
+    /// Show the center alert this many seconds before the event starts
+    #[serde(default = "default_center_alert_lead_seconds")]
+    pub center_alert_lead_seconds: u32,
+
+    /// How long the center alert stays on screen (seconds)
+    #[serde(default = "default_center_alert_duration")]
+    pub center_alert_duration: f32,
+
+    #[serde(default = "default_center_alert_text_scale")]
+    pub center_alert_text_scale: f32,
+
+    /// Vertical position of the center alert (fraction of screen height)
+    #[serde(default = "default_center_alert_offset_y")]
+    pub center_alert_offset_y: f32,
+
+    /// At most this many center alerts per hour
+    #[serde(default = "default_center_alert_max_per_hour")]
+    pub center_alert_max_per_hour: u32,
+}
+
+impl NotificationConfig {
+    pub fn priority(&self, event_id: &TrackedEventId) -> EventPriority {
+        self.event_priorities
+            .iter()
+            .find(|entry| entry.event_id == *event_id)
+            .map(|entry| entry.priority)
+            .unwrap_or_default()
+    }
+
+    pub fn set_priority(&mut self, event_id: &TrackedEventId, priority: EventPriority) {
+        self.event_priorities.retain(|entry| entry.event_id != *event_id);
+        if priority != EventPriority::Normal {
+            self.event_priorities.push(EventPriorityEntry { event_id: event_id.clone(), priority });
+        }
+    }
+
+    /// Minutes to arrive early for an event (0 = none)
+    pub fn travel_minutes(&self, event_id: &TrackedEventId) -> u32 {
+        self.travel_buffers
+            .iter()
+            .find(|entry| entry.event_id == *event_id)
+            .map_or(0, |entry| entry.minutes)
+    }
+
+    /// Seconds of snooze left at `now` (None when not snoozed, or the snooze ran out)
+    pub fn snooze_remaining(&self, now: i64) -> Option<i64> {
+        self.snoozed_until.map(|until| until - now).filter(|&left| left > 0)
+    }
+
+    /// Whether reminders are silenced by the global mute or a running snooze
+    pub fn is_silenced(&self, now: i64) -> bool {
+        self.muted || self.snooze_remaining(now).is_some()
+    }
+
+    pub fn snooze(&mut self, now: i64, minutes: u32) {
+        self.snoozed_until = Some(now + minutes as i64 * 60);
+    }
+
+    pub fn event_note(&self, event_id: &TrackedEventId) -> Option<&str> {
+        self.event_notes
+            .iter()
+            .find(|entry| entry.event_id == *event_id)
+            .map(|entry| entry.note.as_str())
+    }
+
+    pub fn set_event_note(&mut self, event_id: &TrackedEventId, note: String) {
+        self.event_notes.retain(|entry| entry.event_id != *event_id);
+        if !note.trim().is_empty() {
+            self.event_notes.push(EventNote { event_id: event_id.clone(), note });
+        }
+    }
+
+    pub fn attended(&self, event_id: &TrackedEventId, start_time: i64) -> bool {
+        self.attendance.iter().any(|record| record.start_time == start_time && record.event_id == *event_id)
+    }
+
+    /// Check in to an occurrence, dropping records that have aged out
+    pub fn record_attendance(&mut self, event_id: &TrackedEventId, start_time: i64, now: i64) {
+        self.attendance.retain(|record| now - record.start_time < ATTENDANCE_DAYS * 24 * 3600);
+        if !self.attended(event_id, start_time) {
+            self.attendance.push(AttendanceRecord { event_id: event_id.clone(), start_time });
+        }
+    }
+
+    /// Whether an event fills early, tagged by the pack or the user
+    pub fn fills_early(&self, event_id: &TrackedEventId, event: &TimelineEvent) -> bool {
+        event.fills_early || self.fills_early_events.contains(event_id)
+    }
+
+    pub fn set_travel_minutes(&mut self, event_id: &TrackedEventId, minutes: u32) {
+        self.travel_buffers.retain(|entry| entry.event_id != *event_id);
+        if minutes > 0 {
+            self.travel_buffers.push(EventTravelBuffer { event_id: event_id.clone(), minutes });
+        }
+    }
 }
 
 fn default_toast_duration() -> f32 { 5.0 }
@@ -164,6 +538,24 @@ fn default_toast_text_scale() -> f32 { 1.2 }
 fn default_toast_title_color() -> [f32; 4] { [1.0, 0.8, 0.2, 1.0] }
 fn default_toast_time_color() -> [f32; 4] { [0.5, 1.0, 0.5, 1.0] }
 fn default_toast_track_color() -> [f32; 4] { [0.7, 0.7, 0.7, 1.0] }
+fn default_qa_badge_minutes() -> u32 { 15 }
+fn default_fills_early_minutes() -> u32 { 10 }
+fn default_snooze_keybind_minutes() -> u32 { 30 }
+
+/// Snooze lengths offered in the Quick Access menu and settings, in minutes
+pub const SNOOZE_CHOICES: [u32; 3] = [10, 30, 60];
+fn default_upcoming_panel_opacity() -> f32 { 1.0 }
+fn default_high_priority_toast_color() -> [f32; 4] { [0.35, 0.08, 0.08, 0.95] }
+fn default_high_priority_duration_factor() -> f32 { 2.0 }
+fn default_center_alert_lead_seconds() -> u32 { 30 }
+fn default_center_alert_duration() -> f32 { 4.0 }
+fn default_center_alert_text_scale() -> f32 { 3.0 }
+fn default_center_alert_offset_y() -> f32 { 0.25 }
+fn default_center_alert_max_per_hour() -> u32 { 6 }
+fn default_upcoming_countdown_seconds() -> u32 { 30 }
+fn default_upcoming_time_color() -> [f32; 4] { [0.5, 1.0, 0.5, 1.0] }
+fn default_active_time_color() -> [f32; 4] { [1.0, 0.8, 0.2, 1.0] }
+fn default_imminent_time_color() -> [f32; 4] { [1.0, 0.45, 0.3, 1.0] }
 
 impl Default for NotificationConfig {
     fn default() -> Self {
@@ -171,6 +563,7 @@ impl Default for NotificationConfig {
             toast_enabled: true,
             upcoming_panel_enabled: false,
             reminders: default_reminders(),
+            use_track_reminder_defaults: true,
             toast_duration_seconds: 5.0,
             max_visible_toasts: 3,
             upcoming_panel_size: [300.0, 200.0],
@@ -184,6 +577,52 @@ impl Default for NotificationConfig {
             toast_title_color: default_toast_title_color(),
             toast_time_color: default_toast_time_color(),
             toast_track_color: default_toast_track_color(),
+            upcoming_panel_docked: false,
+            upcoming_panel_dock_side: DockSide::default(),
+            upcoming_panel_position: None,
+            upcoming_panel_locked: false,
+            upcoming_panel_size_locked: false,
+            upcoming_panel_click_through: false,
+            upcoming_panel_hide_background: false,
+            upcoming_panel_show_title_bar: true,
+            upcoming_panel_opacity: default_upcoming_panel_opacity(),
+            upcoming_sort_column: UpcomingColumn::default(),
+            upcoming_sort_descending: false,
+            upcoming_show_track_column: false,
+            upcoming_show_waypoint_column: false,
+            upcoming_group_by: UpcomingGrouping::default(),
+            upcoming_collapsed_groups: Vec::new(),
+            upcoming_countdown_seconds: default_upcoming_countdown_seconds(),
+            upcoming_countdown_pulse: true,
+            upcoming_countdown_beep: false,
+            upcoming_time_color: default_upcoming_time_color(),
+            active_time_color: default_active_time_color(),
+            imminent_seconds: 0,
+            imminent_time_color: default_imminent_time_color(),
+            muted: false,
+            snoozed_until: None,
+            snooze_keybind_minutes: default_snooze_keybind_minutes(),
+            qa_badge_enabled: false,
+            qa_badge_minutes: default_qa_badge_minutes(),
+            watchlist: Vec::new(),
+            event_priorities: Vec::new(),
+            travel_buffers: Vec::new(),
+            fills_early_events: Vec::new(),
+            event_notes: Vec::new(),
+            auto_attendance: false,
+            attendance_mutes_day: false,
+            attendance: Vec::new(),
+            fills_early_minutes: default_fills_early_minutes(),
+            high_priority_toast_color: default_high_priority_toast_color(),
+            high_priority_duration_factor: default_high_priority_duration_factor(),
+            hold_while_away: true,
+            catch_up_policy: CatchUpPolicy::default(),
+            center_alert_enabled: false,
+            center_alert_lead_seconds: default_center_alert_lead_seconds(),
+            center_alert_duration: default_center_alert_duration(),
+            center_alert_text_scale: default_center_alert_text_scale(),
+            center_alert_offset_y: default_center_alert_offset_y(),
+            center_alert_max_per_hour: default_center_alert_max_per_hour(),
         }
     }
 }
@@ -218,6 +657,167 @@ impl Default for LabelColumnPosition {
     }
 }
 
+/// Side of the main window a docked panel attaches to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum DockSide {
+    Left,
+    #[default]
+    Right,
+    Below,
+}
+
+/// Column of the Upcoming panel table
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum UpcomingColumn {
+    #[default]
+    Time,
+    Event,
+    Track,
+    Waypoint,
+}
+
+impl UpcomingColumn {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Time => "Time",
+            Self::Event => "Event",
+            Self::Track => "Track",
+            Self::Waypoint => "Waypoint",
+        }
+    }
+}
+
+/// How the Upcoming panel groups its rows
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum UpcomingGrouping {
+    #[default]
+    None,
+    Track,
+    Category,
+}
+
+impl UpcomingGrouping {
+    pub const ALL: [UpcomingGrouping; 3] = [UpcomingGrouping::None, UpcomingGrouping::Track, UpcomingGrouping::Category];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Track => "Track",
+            Self::Category => "Category",
+        }
+    }
+}
+
+/// What happens to reminders that came due while the addon wasn't running its checks
+/// (loading screens, sleep or suspend, any long stall)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum CatchUpPolicy {
+    #[default]
+    Discard,
+    Summary,
+    FireAll,
+}
+
+impl CatchUpPolicy {
+    pub const ALL: [CatchUpPolicy; 3] = [CatchUpPolicy::Discard, CatchUpPolicy::Summary, CatchUpPolicy::FireAll];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Discard => "Discard",
+            Self::Summary => "One summary toast",
+            Self::FireAll => "Show them all",
+        }
+    }
+}
+
+/// What to do with events that need an expansion the player doesn't own
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum UnownedContentMode {
+    #[default]
+    Dim,
+    Hide,
+}
+
+/// Artwork for the Quick Access button
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum QaIconStyle {
+    #[default]
+    Classic,
+    Monochrome,
+    /// User-supplied PNGs (qa_icon_path / qa_icon_hover_path)
+    Custom,
+}
+
+/// How much detail event tooltips show
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum TooltipMode {
+    #[default]
+    Compact,
+    Detailed,
+}
+
+/// A whole cycle shown at once: while ALT is held, or as the minimap strip
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum OverviewCycle {
+    #[default]
+    Off,
+    Tyria,
+    Day,
+}
+
+impl OverviewCycle {
+    /// Cycle length in seconds and the base time calculator it is aligned to
+    pub fn cycle(self) -> Option<(i64, &'static str)> {
+        match self {
+            Self::Off => None,
+            Self::Tyria => Some((2 * 3600, "tyria_cycle")),
+            Self::Day => Some((24 * 3600, "local_day_start")),
+        }
+    }
+}
+
+/// What to do with daily events that are over for the (local) day
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum FinishedTodayMode {
+    #[default]
+    Show,
+    Dim,
+    Hide,
+}
+
+/// Language of event and track names, for data packs that carry `name_localized`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum EventLanguage {
+    #[default]
+    English,
+    German,
+    French,
+    Spanish,
+}
+
+impl EventLanguage {
+    pub const ALL: [EventLanguage; 4] = [EventLanguage::English, EventLanguage::German, EventLanguage::French, EventLanguage::Spanish];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+            Self::French => "Français",
+            Self::Spanish => "Español",
+        }
+    }
+
+    /// Key in `name_localized` maps (None for English, which is the plain `name`)
+    pub fn code(self) -> Option<&'static str> {
+        match self {
+            Self::English => None,
+            Self::German => Some("de"),
+            Self::French => Some("fr"),
+            Self::Spanish => Some("es"),
+        }
+    }
+}
+
 // === Visual Configuration ===
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -250,8 +850,34 @@ pub struct TrackOverride {
     pub height: Option<f32>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub disabled_events: Vec<String>,
+    /// Events left off the timeline (still notified)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub hidden_events: Vec<String>,
+    /// Events that never notify (still drawn)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub silent_events: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub visual: Option<TrackVisualConfig>,
+    /// Label column icon replacing the pack's
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub auto_track_new: bool,
+    /// Event names the track had when last saved, to spot events added by data updates
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub known_events: Vec<String>,
+}
+
+/// Names a pack track and its id'd events had when settings were last saved
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SavedNames {
+    pub name: String,
+    /// The track's category, to follow category renames
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub category: String,
+    /// Event names by event id
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub events: HashMap<String, String>,
 }
 
 // === User Configuration ===
@@ -260,15 +886,117 @@ pub struct TrackOverride {
 pub struct UserConfig {
     #[serde(default)]
     pub track_overrides: HashMap<String, TrackOverride>,
+    /// Names of pack tracks and events with a stable id when last saved, by track key
+    /// (see EventTrack::stable_key), to follow renames
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub saved_names: HashMap<String, SavedNames>,
     #[serde(default)]
     pub custom_tracks: Vec<EventTrack>,
     #[serde(default)]
     pub category_visibility: HashMap<String, bool>,
+    #[serde(default)]
+    pub collapsed_categories: HashSet<String>,
+    #[serde(default)]
+    pub category_keybinds: Vec<String>,
     #[serde(default = "default_true")]
     pub show_main_window: bool,
     #[serde(default)]
     pub is_window_locked: bool,
     #[serde(default)]
+    pub is_window_size_locked: bool,
+    /// Height the main window is held at while its width stays resizable (0 = not held)
+    #[serde(default)]
+    pub locked_window_height: f32,
+    #[serde(default)]
+    pub disable_window_interaction: bool,
+    #[serde(default)]
+    pub show_title_bar: bool,
+    /// Drop the main window to `fade_opacity` unless the cursor is within `fade_distance` pixels
+    /// or a tracked event starts within `fade_event_minutes`
+    #[serde(default)]
+    pub fade_when_inactive: bool,
+    #[serde(default = "default_fade_opacity")]
+    pub fade_opacity: f32,
+    #[serde(default = "default_fade_distance")]
+    pub fade_distance: f32,
+    #[serde(default = "default_fade_event_minutes")]
+    pub fade_event_minutes: u32,
+    /// Skip notification and window work during loading screens and character select
+    #[serde(default = "default_true")]
+    pub pause_while_loading: bool,
+    /// Give up on an event data download attempt after this many seconds
+    #[serde(default = "default_update_timeout_seconds")]
+    pub update_timeout_seconds: u32,
+    /// Further attempts after a failed download, waiting twice as long before each
+    #[serde(default = "default_update_retries")]
+    pub update_retries: u32,
+    /// Proxy URL for downloads; empty uses the HTTP(S)_PROXY environment variables
+    #[serde(default)]
+    pub update_proxy: String,
+    /// Accent the last occurrence of an event before daily reset, and say so in tooltips and toasts
+    #[serde(default)]
+    pub last_chance_highlight: bool,
+    #[serde(default = "default_last_chance_color")]
+    pub last_chance_color: [f32; 4],
+    /// Write the next tracked events to stream/upcoming.json and .txt for OBS sources
+    #[serde(default)]
+    pub stream_export_enabled: bool,
+    /// Seconds between stream export writes
+    #[serde(default = "default_stream_export_interval")]
+    pub stream_export_interval: u32,
+    /// Events in the stream export
+    #[serde(default = "default_stream_export_count")]
+    pub stream_export_count: u32,
+    /// Serve the upcoming events as JSON on http://127.0.0.1:port/
+    #[serde(default)]
+    pub http_server_enabled: bool,
+    #[serde(default = "default_http_server_port")]
+    pub http_server_port: u32,
+    /// POST to `webhook_url` whenever a reminder fires
+    #[serde(default)]
+    pub webhook_enabled: bool,
+    #[serde(default)]
+    pub webhook_url: String,
+    /// Extra request headers, one "Name: value" per line
+    #[serde(default)]
+    pub webhook_headers: String,
+    /// Request body with {event}, {track}, {category}, {reminder}, {minutes}, {start_time},
+    /// {map} and {copy_text} filled in (JSON-escaped)
+    #[serde(default = "default_webhook_body")]
+    pub webhook_body: String,
+    #[serde(default)]
+    pub show_agenda_window: bool,
+    #[serde(default)]
+    pub agenda_tracked_only: bool,
+    #[serde(default)]
+    pub show_diagnostics: bool,
+    #[serde(default)]
+    pub qa_icon_style: QaIconStyle,
+    #[serde(default)]
+    pub qa_icon_path: String,
+    #[serde(default)]
+    pub qa_icon_hover_path: String,
+    /// Addon version the "What's New" window was last shown for
+    #[serde(default)]
+    pub last_seen_version: String,
+    #[serde(default)]
+    pub show_log_window: bool,
+    #[serde(default)]
+    pub tooltip_delay: f32,
+    #[serde(default = "default_true")]
+    pub tooltips_when_locked: bool,
+    #[serde(default)]
+    pub tooltip_mode: TooltipMode,
+    #[serde(default)]
+    pub event_language: EventLanguage,
+    /// Expansion ids the player doesn't own (stored inverted so new content defaults to owned)
+    #[serde(default)]
+    pub unowned_expansions: HashSet<String>,
+    #[serde(default)]
+    pub unowned_content_mode: UnownedContentMode,
+    #[serde(default)]
+    pub auto_solo_map: bool,
+    #[serde(default)]
     pub hide_background: bool,
     #[serde(default = "default_true")]
     pub show_time_ruler: bool,
@@ -280,6 +1008,10 @@ pub struct UserConfig {
     pub view_range_seconds: f32,
     #[serde(default = "default_time_position")]
     pub current_time_position: f32,
+    #[serde(default = "default_alt_zoom_cycle")]
+    pub alt_zoom_cycle: OverviewCycle,
+    #[serde(default)]
+    pub minimap_cycle: OverviewCycle,
     #[serde(default)]
     pub show_category_headers: bool,
     #[serde(default = "default_spacing_same_category")]
@@ -287,6 +1019,14 @@ pub struct UserConfig {
     #[serde(default = "default_spacing_between_categories")]
     pub spacing_between_categories: f32,
     #[serde(default)]
+    pub category_panels_enabled: bool,
+    #[serde(default = "default_category_panel_color")]
+    pub category_panel_color: [f32; 4],
+    #[serde(default = "default_category_panel_rounding")]
+    pub category_panel_rounding: f32,
+    #[serde(default)]
+    pub category_panel_colors: HashMap<String, [f32; 4]>,
+    #[serde(default)]
     pub category_order: Vec<String>,
     #[serde(default = "default_global_track_bg")]
     pub global_track_background: [f32; 4],
@@ -297,15 +1037,46 @@ pub struct UserConfig {
     #[serde(default = "default_height")]
     pub global_track_height: f32,
     #[serde(default)]
+    pub auto_fit_track_heights: bool,
+    #[serde(default = "default_auto_fit_min_height")]
+    pub auto_fit_min_height: f32,
+    #[serde(default = "default_auto_fit_max_height")]
+    pub auto_fit_max_height: f32,
+    #[serde(default)]
     pub draw_event_borders: bool,
     #[serde(default = "default_border_color")]
     pub event_border_color: [f32; 4],
     #[serde(default = "default_border_thickness")]
     pub event_border_thickness: f32,
+    /// Color multiplier and opacity of occurrences that have ended; the running one is drawn as is
+    #[serde(default = "default_occurrence_brightness")]
+    pub past_event_brightness: f32,
+    #[serde(default = "default_occurrence_alpha")]
+    pub past_event_alpha: f32,
+    /// Same for occurrences that haven't started yet
+    #[serde(default = "default_occurrence_brightness")]
+    pub future_event_brightness: f32,
+    #[serde(default = "default_occurrence_alpha")]
+    pub future_event_alpha: f32,
+    #[serde(default)]
+    pub hide_past_events: bool,
+    #[serde(default)]
+    pub finished_today_mode: FinishedTodayMode,
     #[serde(default)]
     pub category_header_alignment: TextAlignment,
+    /// Append running and upcoming event counts to category headers
+    #[serde(default)]
+    pub category_header_counts: bool,
     #[serde(default)]
     pub category_header_padding: f32,
+    #[serde(default = "default_category_header_bg_color")]
+    pub category_header_bg_color: [f32; 4],
+    #[serde(default = "default_category_header_text_color")]
+    pub category_header_text_color: [f32; 4],
+    #[serde(default = "default_category_header_text_scale")]
+    pub category_header_text_scale: f32,
+    #[serde(default)]
+    pub category_header_height: f32,
     #[serde(default)]
     pub label_column_position: LabelColumnPosition,
     #[serde(default = "default_label_column_width")]
@@ -326,12 +1097,24 @@ pub struct UserConfig {
     pub close_on_escape: bool,
     #[serde(default)]
     pub copy_with_event_name: bool,
+    #[serde(default)]
+    pub snap_windows: bool,
+    #[serde(default = "default_snap_threshold")]
+    pub snap_threshold: f32,
+    /// Remember window positions as fractions of the display size
+    #[serde(default = "default_true")]
+    pub relative_window_positions: bool,
+    #[serde(default)]
+    pub main_window_position: Option<[f32; 2]>,
 
     // === Time Ruler Settings ===
     #[serde(default)]
     pub time_ruler_interval: TimeRulerInterval,
     #[serde(default)]
     pub time_ruler_show_current_time: bool,
+    /// Vertical lines across the timeline at resets and chosen times
+    #[serde(default)]
+    pub time_markers: Vec<TimeMarker>,
 
     // === Notification Settings ===
     #[serde(default)]
@@ -357,8 +1140,32 @@ fn default_true() -> bool { true }
 fn default_timeline_width() -> f32 { 800.0 }
 fn default_view_range() -> f32 { 3600.0 }
 fn default_time_position() -> f32 { 0.5 }
+fn default_alt_zoom_cycle() -> OverviewCycle { OverviewCycle::Tyria }
 fn default_spacing_same_category() -> f32 { 0.0 }
 fn default_spacing_between_categories() -> f32 { 0.0 }
+fn default_auto_fit_min_height() -> f32 { 20.0 }
+fn default_auto_fit_max_height() -> f32 { 80.0 }
+fn default_snap_threshold() -> f32 { 10.0 }
+fn default_category_panel_color() -> [f32; 4] { [0.2, 0.2, 0.2, 0.35] }
+fn default_category_panel_rounding() -> f32 { 4.0 }
+fn default_category_header_bg_color() -> [f32; 4] { [0.15, 0.15, 0.15, 0.8] }
+fn default_category_header_text_color() -> [f32; 4] { [0.8, 0.8, 0.2, 1.0] }
+fn default_category_header_text_scale() -> f32 { 1.0 }
+fn default_occurrence_brightness() -> f32 { 0.5 }
+fn default_occurrence_alpha() -> f32 { 1.0 }
+fn default_fade_opacity() -> f32 { 0.3 }
+fn default_fade_distance() -> f32 { 80.0 }
+fn default_fade_event_minutes() -> u32 { 5 }
+fn default_update_timeout_seconds() -> u32 { 20 }
+fn default_update_retries() -> u32 { 3 }
+fn default_last_chance_color() -> [f32; 4] { [1.0, 0.78, 0.2, 1.0] }
+fn default_stream_export_interval() -> u32 { 5 }
+fn default_stream_export_count() -> u32 { 5 }
+fn default_http_server_port() -> u32 { 8765 }
+/// Also what "Reset Body" in the settings goes back to
+pub(crate) fn default_webhook_body() -> String {
+    String::from(r#"{"content": "{event} ({track}): {reminder}, starts in {minutes} min"}"#)
+}
 
 /// Time ruler marker spacing options (in minutes)
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
@@ -405,33 +1212,157 @@ impl TimeRulerInterval {
     }
 }
 
+/// When a vertical time marker repeats
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeMarkerKind {
+    /// Daily reset, 00:00 UTC
+    DailyReset,
+    /// Weekly reset, Monday 07:30 UTC
+    WeeklyReset,
+    /// Every day at `minute_of_day` local time
+    LocalDaily,
+}
+
+impl TimeMarkerKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::DailyReset => "Daily Reset",
+            Self::WeeklyReset => "Weekly Reset",
+            Self::LocalDaily => "Daily (local time)",
+        }
+    }
+
+    pub fn all() -> &'static [TimeMarkerKind] {
+        &[Self::DailyReset, Self::WeeklyReset, Self::LocalDaily]
+    }
+}
+
+/// Labelled vertical line drawn across every track at a repeating time
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TimeMarker {
+    pub label: String,
+    pub kind: TimeMarkerKind,
+    /// Local time of day in minutes, for `LocalDaily`
+    #[serde(default)]
+    pub minute_of_day: u32,
+    pub color: [f32; 4],
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+impl TimeMarker {
+    pub fn new(kind: TimeMarkerKind) -> Self {
+        let label = match kind {
+            TimeMarkerKind::LocalDaily => "Guild Missions",
+            _ => kind.label(),
+        };
+        Self { label: label.to_string(), kind, minute_of_day: 20 * 60, color: [1.0, 0.85, 0.3, 0.8], enabled: true }
+    }
+
+    /// Marker times in [from, until)
+    pub fn times_between(&self, from: i64, until: i64) -> Vec<i64> {
+        const DAY: i64 = 24 * 3600;
+        // The Unix epoch was a Thursday; the first Monday is 4 days later
+        let (period, anchor) = match self.kind {
+            TimeMarkerKind::DailyReset => (DAY, 0),
+            TimeMarkerKind::WeeklyReset => (7 * DAY, 4 * DAY + 7 * 3600 + 30 * 60),
+            TimeMarkerKind::LocalDaily => {
+                (DAY, self.minute_of_day as i64 * 60 - crate::time_utils::local_utc_offset_seconds())
+            }
+        };
+        let first = from + (anchor - from).rem_euclid(period);
+        (0..).map(|i| first + i * period).take_while(|&time| time < until).collect()
+    }
+}
+
 impl Default for UserConfig {
     fn default() -> Self {
         Self {
             track_overrides: HashMap::new(),
+            saved_names: HashMap::new(),
             custom_tracks: Vec::new(),
             category_visibility: HashMap::new(),
+            collapsed_categories: HashSet::new(),
+            category_keybinds: Vec::new(),
             show_main_window: false,
             is_window_locked: false,
+            is_window_size_locked: false,
+            locked_window_height: 0.0,
+            disable_window_interaction: false,
+            show_title_bar: false,
+            fade_when_inactive: false,
+            fade_opacity: default_fade_opacity(),
+            fade_distance: default_fade_distance(),
+            fade_event_minutes: default_fade_event_minutes(),
+            pause_while_loading: true,
+            update_timeout_seconds: default_update_timeout_seconds(),
+            update_retries: default_update_retries(),
+            update_proxy: String::new(),
+            last_chance_highlight: false,
+            last_chance_color: default_last_chance_color(),
+            stream_export_enabled: false,
+            stream_export_interval: default_stream_export_interval(),
+            stream_export_count: default_stream_export_count(),
+            http_server_enabled: false,
+            http_server_port: default_http_server_port(),
+            webhook_enabled: false,
+            webhook_url: String::new(),
+            webhook_headers: String::new(),
+            webhook_body: default_webhook_body(),
+            show_agenda_window: false,
+            agenda_tracked_only: false,
+            show_diagnostics: false,
+            qa_icon_style: QaIconStyle::default(),
+            qa_icon_path: String::new(),
+            qa_icon_hover_path: String::new(),
+            last_seen_version: String::new(),
+            show_log_window: false,
+            tooltip_delay: 0.0,
+            tooltips_when_locked: true,
+            tooltip_mode: TooltipMode::default(),
+            event_language: EventLanguage::default(),
+            unowned_expansions: HashSet::new(),
+            unowned_content_mode: UnownedContentMode::default(),
+            auto_solo_map: false,
             hide_background: false,
             show_time_ruler: true,
             show_scrollbar: true,
             timeline_width: 800.0,
             view_range_seconds: 3600.0,
             current_time_position: 0.5,
+            alt_zoom_cycle: OverviewCycle::Tyria,
+            minimap_cycle: OverviewCycle::default(),
             show_category_headers: false,
             spacing_same_category: 0.0,
             spacing_between_categories: 0.0,
+            category_panels_enabled: false,
+            category_panel_color: default_category_panel_color(),
+            category_panel_rounding: default_category_panel_rounding(),
+            category_panel_colors: HashMap::new(),
             category_order: Vec::new(),
             global_track_background: [0.2, 0.2, 0.2, 0.2],
             global_track_padding: 0.0,
             override_all_track_heights: false,
             global_track_height: default_height(),
+            auto_fit_track_heights: false,
+            auto_fit_min_height: default_auto_fit_min_height(),
+            auto_fit_max_height: default_auto_fit_max_height(),
             draw_event_borders: true,
             event_border_color: [0.0, 0.0, 0.0, 1.0],
             event_border_thickness: 1.0,
+            past_event_brightness: default_occurrence_brightness(),
+            past_event_alpha: default_occurrence_alpha(),
+            future_event_brightness: default_occurrence_brightness(),
+            future_event_alpha: default_occurrence_alpha(),
+            hide_past_events: false,
+            finished_today_mode: FinishedTodayMode::default(),
             category_header_alignment: TextAlignment::Center,
+            category_header_counts: false,
             category_header_padding: 0.0,
+            category_header_bg_color: default_category_header_bg_color(),
+            category_header_text_color: default_category_header_text_color(),
+            category_header_text_scale: default_category_header_text_scale(),
+            category_header_height: 0.0,
             label_column_position: LabelColumnPosition::None,
             label_column_width: 150.0,
             label_column_show_category: false,
@@ -442,8 +1373,13 @@ impl Default for UserConfig {
             label_column_category_color: [0.8, 0.8, 0.2, 1.0],
             close_on_escape: true,
             copy_with_event_name: false,
+            snap_windows: false,
+            snap_threshold: default_snap_threshold(),
+            relative_window_positions: true,
+            main_window_position: None,
             time_ruler_interval: TimeRulerInterval::default(),
             time_ruler_show_current_time: false,
+            time_markers: Vec::new(),
             tracked_events: HashSet::new(),
             oneshot_events: HashSet::new(),
             notification_config: NotificationConfig::default(),
@@ -453,32 +1389,111 @@ impl Default for UserConfig {
 
 // === Runtime Configuration ===
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RuntimeConfig {
     pub tracks: Vec<EventTrack>,
+    /// Names of the tracks that come from the pack; the others are custom
+    pub default_track_names: HashSet<String>,
     pub categories: Vec<String>,
     pub category_visibility: HashMap<String, bool>,
+    pub collapsed_categories: HashSet<String>,
+    pub category_keybinds: Vec<String>,
+    /// Track soloed from the label column (session only, not saved)
+    pub solo_track: Option<String>,
+    /// Category shown alone through its keybind (session only, not saved)
+    pub solo_category: Option<String>,
+    /// Current map id while auto-solo has matching tracks (session only, updated every frame)
+    pub map_solo: Option<u32>,
+    /// Default tracks are editable like custom ones, for building packs (session only, not saved)
+    pub authoring_mode: bool,
     pub show_main_window: bool,
     pub is_window_locked: bool,
+    pub is_window_size_locked: bool,
+    /// Height the main window is held at while its width stays resizable (0 = not held)
+    pub locked_window_height: f32,
+    pub disable_window_interaction: bool,
+    pub show_title_bar: bool,
+    /// Fade the main window while the cursor is away and no tracked event is close
+    pub fade_when_inactive: bool,
+    pub fade_opacity: f32,
+    pub fade_distance: f32,
+    pub fade_event_minutes: u32,
+    /// Skip notification and window work while the game is loading
+    pub pause_while_loading: bool,
+    /// Event data download: per-attempt timeout, retries and proxy (empty: environment)
+    pub update_timeout_seconds: u32,
+    pub update_retries: u32,
+    pub update_proxy: String,
+    /// Mark an event's last occurrence before daily reset (bar accent, tooltip, toast)
+    pub last_chance_highlight: bool,
+    pub last_chance_color: [f32; 4],
+    /// Stream overlay files: on/off, seconds between writes, events listed
+    pub stream_export_enabled: bool,
+    pub stream_export_interval: u32,
+    pub stream_export_count: u32,
+    /// Local HTTP endpoint for the upcoming events
+    pub http_server_enabled: bool,
+    pub http_server_port: u32,
+    /// Generic webhook for fired reminders: on/off, URL, header lines, body template
+    pub webhook_enabled: bool,
+    pub webhook_url: String,
+    pub webhook_headers: String,
+    pub webhook_body: String,
+    pub show_agenda_window: bool,
+    pub agenda_tracked_only: bool,
+    pub show_diagnostics: bool,
+    pub qa_icon_style: QaIconStyle,
+    pub qa_icon_path: String,
+    pub qa_icon_hover_path: String,
+    pub last_seen_version: String,
+    pub show_log_window: bool,
+    pub tooltip_delay: f32,
+    pub tooltips_when_locked: bool,
+    pub tooltip_mode: TooltipMode,
+    pub event_language: EventLanguage,
+    pub unowned_expansions: HashSet<String>,
+    pub unowned_content_mode: UnownedContentMode,
+    pub auto_solo_map: bool,
     pub hide_background: bool,
     pub show_time_ruler: bool,
     pub show_scrollbar: bool,
     pub timeline_width: f32,
     pub view_range_seconds: f32,
     pub current_time_position: f32,
+    pub alt_zoom_cycle: OverviewCycle,
+    pub minimap_cycle: OverviewCycle,
     pub show_category_headers: bool,
     pub spacing_same_category: f32,
     pub spacing_between_categories: f32,
+    pub category_panels_enabled: bool,
+    pub category_panel_color: [f32; 4],
+    pub category_panel_rounding: f32,
+    pub category_panel_colors: HashMap<String, [f32; 4]>,
     pub category_order: Vec<String>,
     pub global_track_background: [f32; 4],
     pub global_track_padding: f32,
     pub override_all_track_heights: bool,
     pub global_track_height: f32,
+    pub auto_fit_track_heights: bool,
+    pub auto_fit_min_height: f32,
+    pub auto_fit_max_height: f32,
     pub draw_event_borders: bool,
     pub event_border_color: [f32; 4],
     pub event_border_thickness: f32,
+    pub past_event_brightness: f32,
+    pub past_event_alpha: f32,
+    pub future_event_brightness: f32,
+    pub future_event_alpha: f32,
+    pub hide_past_events: bool,
+    pub finished_today_mode: FinishedTodayMode,
     pub category_header_alignment: TextAlignment,
+    /// Append running and upcoming event counts to category headers
+    pub category_header_counts: bool,
     pub category_header_padding: f32,
+    pub category_header_bg_color: [f32; 4],
+    pub category_header_text_color: [f32; 4],
+    pub category_header_text_scale: f32,
+    pub category_header_height: f32,
     pub label_column_position: LabelColumnPosition,
     pub label_column_width: f32,
     pub label_column_show_category: bool,
@@ -489,10 +1504,16 @@ pub struct RuntimeConfig {
     pub label_column_category_color: [f32; 4],
     pub close_on_escape: bool,
     pub copy_with_event_name: bool,
+    pub snap_windows: bool,
+    pub snap_threshold: f32,
+    pub relative_window_positions: bool,
+    pub main_window_position: Option<[f32; 2]>,
 
     // === Time Ruler Settings ===
     pub time_ruler_interval: TimeRulerInterval,
     pub time_ruler_show_current_time: bool,
+    /// Vertical lines across the timeline at resets and chosen times
+    pub time_markers: Vec<TimeMarker>,
 
     // === Notification Settings ===
     pub tracked_events: HashSet<TrackedEventId>,
@@ -500,34 +1521,126 @@ pub struct RuntimeConfig {
     pub notification_config: NotificationConfig,
 }
 
+impl RuntimeConfig {
+    /// Whether a track is drawn on the timeline (visible, not hidden by a soloed track or
+    /// category or the current-map filter, and not hidden for requiring an unowned expansion)
+    pub fn is_track_shown(&self, track: &EventTrack) -> bool {
+        track.visible
+            && self.solo_track.as_ref().is_none_or(|solo| *solo == track.name)
+            && self.solo_category.as_ref().is_none_or(|solo| *solo == track.category)
+            && self.map_solo.is_none_or(|map_id| track.map_ids.contains(&map_id))
+            && (self.unowned_content_mode != UnownedContentMode::Hide || self.owns_expansion(&track.expansion))
+    }
+
+    /// Whether a category is collapsed to its header on the timeline (only possible with headers shown)
+    pub fn is_category_collapsed(&self, category: &str) -> bool {
+        self.show_category_headers && !category.is_empty() && self.collapsed_categories.contains(category)
+    }
+
+    /// Whether a track's events can be edited (custom tracks, or any track in authoring mode)
+    pub fn is_editable_track(&self, track_name: &str) -> bool {
+        self.authoring_mode || !self.default_track_names.contains(track_name)
+    }
+
+    /// Whether content for an expansion id is available (empty = core game)
+    pub fn owns_expansion(&self, expansion: &str) -> bool {
+        expansion.is_empty() || !self.unowned_expansions.contains(expansion)
+    }
+}
+
 impl Default for RuntimeConfig {
     fn default() -> Self {
         let (tracks, categories) = load_tracks_from_json();
         Self {
+            default_track_names: tracks.iter().map(|t| t.name.clone()).collect(),
             tracks,
             categories,
             category_visibility: HashMap::new(),
+            collapsed_categories: HashSet::new(),
+            category_keybinds: Vec::new(),
+            solo_track: None,
+            solo_category: None,
+            map_solo: None,
+            authoring_mode: false,
             show_main_window: false,
             is_window_locked: false,
+            is_window_size_locked: false,
+            locked_window_height: 0.0,
+            disable_window_interaction: false,
+            show_title_bar: false,
+            fade_when_inactive: false,
+            fade_opacity: default_fade_opacity(),
+            fade_distance: default_fade_distance(),
+            fade_event_minutes: default_fade_event_minutes(),
+            pause_while_loading: true,
+            update_timeout_seconds: default_update_timeout_seconds(),
+            update_retries: default_update_retries(),
+            update_proxy: String::new(),
+            last_chance_highlight: false,
+            last_chance_color: default_last_chance_color(),
+            stream_export_enabled: false,
+            stream_export_interval: default_stream_export_interval(),
+            stream_export_count: default_stream_export_count(),
+            http_server_enabled: false,
+            http_server_port: default_http_server_port(),
+            webhook_enabled: false,
+            webhook_url: String::new(),
+            webhook_headers: String::new(),
+            webhook_body: default_webhook_body(),
+            show_agenda_window: false,
+            agenda_tracked_only: false,
+            show_diagnostics: false,
+            qa_icon_style: QaIconStyle::default(),
+            qa_icon_path: String::new(),
+            qa_icon_hover_path: String::new(),
+            last_seen_version: String::new(),
+            show_log_window: false,
+            tooltip_delay: 0.0,
+            tooltips_when_locked: true,
+            tooltip_mode: TooltipMode::default(),
+            event_language: EventLanguage::default(),
+            unowned_expansions: HashSet::new(),
+            unowned_content_mode: UnownedContentMode::default(),
+            auto_solo_map: false,
             hide_background: false,
             show_time_ruler: false,
             show_scrollbar: true,
             timeline_width: 800.0,
             view_range_seconds: 3600.0,
             current_time_position: 0.5,
+            alt_zoom_cycle: OverviewCycle::Tyria,
+            minimap_cycle: OverviewCycle::default(),
             show_category_headers: false,
             spacing_same_category: 0.0,
             spacing_between_categories: 0.0,
+            category_panels_enabled: false,
+            category_panel_color: default_category_panel_color(),
+            category_panel_rounding: default_category_panel_rounding(),
+            category_panel_colors: HashMap::new(),
             category_order: Vec::new(),
             global_track_background: [0.2, 0.2, 0.2, 0.2],
             global_track_padding: 0.0,
             override_all_track_heights: false,
             global_track_height: default_height(),
+            auto_fit_track_heights: false,
+            auto_fit_min_height: default_auto_fit_min_height(),
+            auto_fit_max_height: default_auto_fit_max_height(),
             draw_event_borders: true,
             event_border_color: [0.0, 0.0, 0.0, 1.0],
             event_border_thickness: 1.0,
+            past_event_brightness: default_occurrence_brightness(),
+            past_event_alpha: default_occurrence_alpha(),
+            future_event_brightness: default_occurrence_brightness(),
+            future_event_alpha: default_occurrence_alpha(),
+            hide_past_events: false,
+            finished_today_mode: FinishedTodayMode::default(),
             category_header_alignment: TextAlignment::Center,
+            category_header_counts: false,
             category_header_padding: 0.0,
+            category_header_bg_color: default_category_header_bg_color(),
+            category_header_text_color: default_category_header_text_color(),
+            category_header_text_scale: default_category_header_text_scale(),
+            category_header_height: 0.0,
             label_column_position: LabelColumnPosition::None,
             label_column_width: 150.0,
             label_column_show_category: false,
@@ -538,8 +1651,13 @@ impl Default for RuntimeConfig {
             label_column_category_color: [0.8, 0.8, 0.2, 1.0],
             close_on_escape: true,
             copy_with_event_name: false,
+            snap_windows: false,
+            snap_threshold: default_snap_threshold(),
+            relative_window_positions: true,
+            main_window_position: None,
             time_ruler_interval: TimeRulerInterval::default(),
             time_ruler_show_current_time: false,
+            time_markers: Vec::new(),
             tracked_events: HashSet::new(),
             oneshot_events: HashSet::new(),
             notification_config: NotificationConfig::default(),
@@ -547,21 +1665,279 @@ impl Default for RuntimeConfig {
     }
 }
 
+// === Shared Runtime Config ===
+
+/// The runtime config behind a lock, plus an immutable snapshot for code that only reads it.
+///
+/// `lock()` is the write path (and still fine for short reads). When a guard that was borrowed
+/// mutably is dropped, the config is compared with the published snapshot and a new copy is
+/// published only if a value changed. `snapshot()` just hands out the latest copy, so per-frame
+/// readers neither clone the config nor wait on a writer holding the lock.
+pub struct SharedConfig {
+    inner: Mutex<RuntimeConfig>,
+    snapshot: Mutex<Arc<RuntimeConfig>>,
+}
+
+impl SharedConfig {
+    fn new(config: RuntimeConfig) -> Self {
+        Self {
+            snapshot: Mutex::new(Arc::new(config.clone())),
+            inner: Mutex::new(config),
+        }
+    }
+
+    /// Lock for reading or writing (lock waits are recorded in the diagnostics)
+    pub fn lock(&self) -> ConfigGuard<'_> {
+        ConfigGuard { guard: timed_lock(&self.inner), snapshot: &self.snapshot, borrowed_mut: false }
+    }
+
+    /// Latest published copy of the config
+    pub fn snapshot(&self) -> Arc<RuntimeConfig> {
+        self.snapshot.lock().clone()
+    }
+}
+
+/// Lock guard of the runtime config; publishes a new snapshot on drop if a value was changed
+pub struct ConfigGuard<'a> {
+    guard: MutexGuard<'a, RuntimeConfig>,
+    snapshot: &'a Mutex<Arc<RuntimeConfig>>,
+    borrowed_mut: bool,
+}
+
+impl Deref for ConfigGuard<'_> {
+    type Target = RuntimeConfig;
+
+    fn deref(&self) -> &RuntimeConfig {
+        &self.guard
+    }
+}
+
+impl DerefMut for ConfigGuard<'_> {
+    fn deref_mut(&mut self) -> &mut RuntimeConfig {
+        self.borrowed_mut = true;
+        &mut self.guard
+    }
+}
+
+impl Drop for ConfigGuard<'_> {
+    fn drop(&mut self) {
+        if !self.borrowed_mut {
+            return;
+        }
+        // Comparing is much cheaper than cloning, and the settings borrow mutably every frame
+        let mut snapshot = self.snapshot.lock();
+        if **snapshot != *self.guard {
+            *snapshot = Arc::new(self.guard.clone());
+        }
+    }
+}
+
 // === Global State ===
 
-pub static RUNTIME_CONFIG: Lazy<Mutex<RuntimeConfig>> = Lazy::new(|| Mutex::new(RuntimeConfig::default()));
+pub static RUNTIME_CONFIG: Lazy<SharedConfig> = Lazy::new(|| SharedConfig::new(RuntimeConfig::default()));
 pub static USER_CONFIG: Lazy<Mutex<UserConfig>> = Lazy::new(|| Mutex::new(UserConfig::default()));
 pub static SELECTED_TRACK: Lazy<Mutex<Option<usize>>> = Lazy::new(|| Mutex::new(None));
 pub static SELECTED_EVENT: Lazy<Mutex<Option<usize>>> = Lazy::new(|| Mutex::new(None));
 
 // === Configuration Management ===
 
+/// Former names of pack tracks, events and categories mapped to their current ones
+#[derive(Debug, Default)]
+struct Renames {
+    tracks: HashMap<String, String>,
+    /// By (current track name, former event name)
+    events: HashMap<(String, String), String>,
+    categories: HashMap<String, String>,
+}
+
+impl Renames {
+    /// Renames known from the names last saved for a stable id, or from an event's `aliases`.
+    /// A category counts as renamed when an id'd track moved out of one that's gone from the pack.
+    fn find(user_cfg: &UserConfig, default_tracks: &[EventTrack]) -> Self {
+        let mut renames = Self::default();
+        let current_categories: HashSet<&str> = default_tracks.iter().map(|t| t.category.as_str()).collect();
+        let mut ambiguous_categories: HashSet<String> = HashSet::new();
+
+        for track in default_tracks {
+            let saved = user_cfg.saved_names.get(track.stable_key());
+            if let Some(saved) = saved.filter(|_| !track.id.is_empty()) {
+                if saved.name != track.name {
+                    renames.tracks.insert(saved.name.clone(), track.name.clone());
+                }
+                // Tracks of one former category ending up in different ones: leave it alone
+                let former = &saved.category;
+                if !former.is_empty() && *former != track.category && !current_categories.contains(former.as_str()) {
+                    match renames.categories.get(former) {
+                        Some(new_name) if *new_name != track.category => {
+                            ambiguous_categories.insert(former.clone());
+                        }
+                        _ => {
+                            renames.categories.insert(former.clone(), track.category.clone());
+                        }
+                    }
+                }
+            }
+
+            for event in &track.events {
+                let saved_name = saved.and_then(|saved| saved.events.get(&event.id)).filter(|_| !event.id.is_empty());
+                // A former name that is still some event's name on the track would be ambiguous
+                for former in saved_name.into_iter().chain(&event.aliases) {
+                    if !track.events.iter().any(|e| e.name == *former) {
+                        renames.events.insert((track.name.clone(), former.clone()), event.name.clone());
+                    }
+                }
+            }
+        }
+        renames.categories.retain(|former, _| !ambiguous_categories.contains(former));
+        renames
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tracks.is_empty() && self.events.is_empty() && self.categories.is_empty()
+    }
+
+    fn rename(map: &HashMap<String, String>, name: &mut String) -> usize {
+        match map.get(name.as_str()) {
+            Some(new_name) => {
+                *name = new_name.clone();
+                1
+            }
+            None => 0,
+        }
+    }
+
+    fn track(&self, track_name: &mut String) -> usize {
+        Self::rename(&self.tracks, track_name)
+    }
+
+    fn category(&self, category: &mut String) -> usize {
+        Self::rename(&self.categories, category)
+    }
+
+    fn event(&self, track_name: &str, event_name: &mut String) -> usize {
+        match self.events.get(&(track_name.to_string(), event_name.clone())) {
+            Some(new_name) => {
+                *event_name = new_name.clone();
+                1
+            }
+            None => 0,
+        }
+    }
+
+    fn event_id(&self, id: &mut TrackedEventId) -> usize {
+        self.track(&mut id.track_name) + self.event(&id.track_name, &mut id.event_name)
+    }
+}
+
+/// Rename the keys of a map, keeping the entry already under the new name if there is one
+fn rename_keys<V>(map: &mut HashMap<String, V>, rename: impl Fn(&mut String) -> usize) -> usize {
+    let mut migrated = 0;
+    for (mut key, value) in std::mem::take(map) {
+        migrated += rename(&mut key);
+        map.entry(key).or_insert(value);
+    }
+    migrated
+}
+
+/// Rename the entries of a list, dropping the ones that become duplicates
+fn rename_entries(entries: &mut Vec<String>, rename: impl Fn(&mut String) -> usize) -> usize {
+    let mut migrated = 0;
+    let mut seen = HashSet::new();
+    for mut entry in std::mem::take(entries) {
+        migrated += rename(&mut entry);
+        if seen.insert(entry.clone()) {
+            entries.push(entry);
+        }
+    }
+    migrated
+}
+
+/// Rewrite settings saved under a former track, event or category name to the current one, so
+/// disabling, tracking, priorities and category settings survive pack renames. Returns the
+/// renames, for state kept outside the user config.
+fn migrate_renamed_events(user_cfg: &mut UserConfig, default_tracks: &[EventTrack]) -> Option<Renames> {
+    let renames = Renames::find(user_cfg, default_tracks);
+    if renames.is_empty() {
+        return None;
+    }
+
+    let mut migrated = 0;
+    for (mut track_name, mut override_data) in std::mem::take(&mut user_cfg.track_overrides) {
+        migrated += renames.track(&mut track_name);
+        for event_names in [
+            &mut override_data.disabled_events,
+            &mut override_data.hidden_events,
+            &mut override_data.silent_events,
+            &mut override_data.known_events,
+        ] {
+            for event_name in event_names.iter_mut() {
+                migrated += renames.event(&track_name, event_name);
+            }
+            event_names.sort();
+            event_names.dedup();
+        }
+        user_cfg.track_overrides.insert(track_name, override_data);
+    }
+
+    for events in [&mut user_cfg.tracked_events, &mut user_cfg.oneshot_events] {
+        *events = events
+            .drain()
+            .map(|mut id| {
+                migrated += renames.event_id(&mut id);
+                id
+            })
+            .collect();
+    }
+
+    for entry in &mut user_cfg.notification_config.event_priorities {
+        migrated += renames.event_id(&mut entry.event_id);
+    }
+    for entry in &mut user_cfg.notification_config.travel_buffers {
+        migrated += renames.event_id(&mut entry.event_id);
+    }
+    for event_id in &mut user_cfg.notification_config.fills_early_events {
+        migrated += renames.event_id(event_id);
+    }
+    for entry in &mut user_cfg.notification_config.event_notes {
+        migrated += renames.event_id(&mut entry.event_id);
+    }
+    for record in &mut user_cfg.notification_config.attendance {
+        migrated += renames.event_id(&mut record.event_id);
+    }
+
+    // Category settings, and custom tracks filed under a renamed category
+    let category = |name: &mut String| renames.category(name);
+    migrated += rename_keys(&mut user_cfg.category_visibility, category);
+    migrated += rename_keys(&mut user_cfg.category_panel_colors, category);
+    migrated += rename_entries(&mut user_cfg.category_order, category);
+    migrated += rename_entries(&mut user_cfg.category_keybinds, category);
+    user_cfg.collapsed_categories = std::mem::take(&mut user_cfg.collapsed_categories)
+        .into_iter()
+        .map(|mut name| {
+            migrated += renames.category(&mut name);
+            name
+        })
+        .collect();
+    for track in &mut user_cfg.custom_tracks {
+        migrated += renames.category(&mut track.category);
+    }
+    // Upcoming groups are named after a category or a track
+    migrated += rename_entries(&mut user_cfg.notification_config.upcoming_collapsed_groups, |name| {
+        renames.category(name) + renames.track(name)
+    });
+
+    if migrated > 0 {
+        addon_log::info(format!("Migrated {} settings from renamed tracks, events and categories", migrated));
+    }
+    Some(renames)
+}
+
 pub fn apply_user_overrides() {
     // Load fresh tracks from JSON (outside locks)
     let (default_tracks, categories) = load_tracks_from_json();
     
     // Scope 1: Clean up user config
-    let (cleaned_custom_tracks, user_settings) = {
+    let (cleaned_custom_tracks, user_settings, renames) = {
         let mut user_cfg = USER_CONFIG.lock();
         
         // Deduplicate custom tracks by name (keep first occurrence)
@@ -584,6 +1960,8 @@ pub fn apply_user_overrides() {
             !default_track_names.contains(&track.name)
         });
         
+        let renames = migrate_renamed_events(&mut user_cfg, &default_tracks);
+
         // Clone data we need (releases lock early)
         (
             user_cfg.custom_tracks.clone(),
@@ -626,15 +2004,90 @@ pub fn apply_user_overrides() {
                 user_cfg.tracked_events.clone(),
                 user_cfg.oneshot_events.clone(),
                 user_cfg.notification_config.clone(),
-            )
+                user_cfg.auto_fit_track_heights,
+                user_cfg.auto_fit_min_height,
+                user_cfg.auto_fit_max_height,
+                user_cfg.snap_windows,
+                user_cfg.snap_threshold,
+                user_cfg.relative_window_positions,
+                user_cfg.main_window_position,
+                user_cfg.is_window_size_locked,
+                user_cfg.disable_window_interaction,
+                user_cfg.show_title_bar,
+                user_cfg.category_panels_enabled,
+                user_cfg.category_panel_color,
+                user_cfg.category_panel_rounding,
+                user_cfg.category_panel_colors.clone(),
+                user_cfg.category_header_bg_color,
+                user_cfg.category_header_text_color,
+                user_cfg.category_header_text_scale,
+                user_cfg.category_header_height,
+                user_cfg.tooltip_delay,
+                user_cfg.tooltips_when_locked,
+                user_cfg.tooltip_mode,
+                user_cfg.unowned_expansions.clone(),
+                user_cfg.unowned_content_mode,
+                user_cfg.auto_solo_map,
+                user_cfg.stream_export_enabled,
+                user_cfg.stream_export_interval,
+                user_cfg.stream_export_count,
+                user_cfg.http_server_enabled,
+                user_cfg.http_server_port,
+                user_cfg.webhook_enabled,
+                user_cfg.webhook_url.clone(),
+                user_cfg.webhook_headers.clone(),
+                user_cfg.webhook_body.clone(),
+                user_cfg.show_agenda_window,
+                user_cfg.agenda_tracked_only,
+                user_cfg.show_diagnostics,
+                user_cfg.show_log_window,
+                user_cfg.qa_icon_style,
+                user_cfg.qa_icon_path.clone(),
+                user_cfg.qa_icon_hover_path.clone(),
+                user_cfg.last_seen_version.clone(),
+                user_cfg.past_event_brightness,
+                user_cfg.past_event_alpha,
+                user_cfg.future_event_brightness,
+                user_cfg.future_event_alpha,
+                user_cfg.hide_past_events,
+                user_cfg.event_language,
+                user_cfg.finished_today_mode,
+                user_cfg.alt_zoom_cycle,
+                user_cfg.minimap_cycle,
+                user_cfg.collapsed_categories.clone(),
+                user_cfg.category_keybinds.clone(),
+                user_cfg.time_markers.clone(),
+                user_cfg.category_header_counts,
+                user_cfg.locked_window_height,
+                user_cfg.fade_when_inactive,
+                user_cfg.fade_opacity,
+                user_cfg.fade_distance,
+                user_cfg.fade_event_minutes,
+                user_cfg.pause_while_loading,
+                user_cfg.update_timeout_seconds,
+                user_cfg.update_retries,
+                user_cfg.update_proxy.clone(),
+                user_cfg.last_chance_highlight,
+                user_cfg.last_chance_color,
+            ),
+            renames,
         )
     }; // user_cfg lock dropped here
+
+    // Reminders already shown and muted occurrences follow renamed events too
+    if let Some(renames) = renames {
+        let moved = NOTIFICATION_STATE.lock().migrate_event_ids(|id| renames.event_id(id));
+        if moved > 0 {
+            addon_log::info(format!("Moved {} notification records to renamed events", moved));
+        }
+    }
     
     // Scope 2: Update runtime config
     {
         let mut runtime = RUNTIME_CONFIG.lock();
         
         // Set runtime tracks to defaults
+        runtime.default_track_names = default_tracks.iter().map(|t| t.name.clone()).collect();
         runtime.tracks = default_tracks;
         runtime.categories = categories;
         
@@ -647,16 +2100,39 @@ pub fn apply_user_overrides() {
                 if let Some(height) = override_data.height {
                     track.height = height;
                 }
+                if let Some(icon) = &override_data.icon {
+                    track.icon = icon.clone();
+                }
                 
                 for event in &mut track.events {
                     if override_data.disabled_events.contains(&event.name) {
                         event.enabled = false;
                     }
+                    if override_data.hidden_events.contains(&event.name) {
+                        event.show_on_timeline = false;
+                    }
+                    if override_data.silent_events.contains(&event.name) {
+                        event.notify = false;
+                    }
                 }
+                track.auto_track_new = override_data.auto_track_new;
             }
         }
         
-        // Add cleaned custom tracks
+        // Add cleaned custom tracks, compiling any recurrence expressions
+        let mut cleaned_custom_tracks = cleaned_custom_tracks;
+        for track in &mut cleaned_custom_tracks {
+            track.refresh_base_time();
+            let base_time = track.base_time;
+            for event in &mut track.events {
+                if let Err(e) = event.compile_recurrence(base_time) {
+                    addon_log::warn(format!("Invalid recurrence for event '{}': {}", event.name, e));
+                }
+                if let Err(e) = event.compile_condition() {
+                    addon_log::warn(format!("Invalid condition for event '{}': {}", event.name, e));
+                }
+            }
+        }
         runtime.tracks.extend(cleaned_custom_tracks);
         
         // Apply all user settings
@@ -695,16 +2171,98 @@ pub fn apply_user_overrides() {
         runtime.time_ruler_interval = user_settings.33;
         runtime.time_ruler_show_current_time = user_settings.34;
         runtime.tracked_events = user_settings.35;
+        auto_track_new_events(&mut runtime, &user_settings.0);
         runtime.oneshot_events = user_settings.36;
         runtime.notification_config = user_settings.37;
+        runtime.auto_fit_track_heights = user_settings.38;
+        runtime.auto_fit_min_height = user_settings.39;
+        runtime.auto_fit_max_height = user_settings.40;
+        runtime.snap_windows = user_settings.41;
+        runtime.snap_threshold = user_settings.42;
+        runtime.relative_window_positions = user_settings.43;
+        runtime.main_window_position = user_settings.44;
+        runtime.is_window_size_locked = user_settings.45;
+        runtime.disable_window_interaction = user_settings.46;
+        runtime.show_title_bar = user_settings.47;
+        runtime.category_panels_enabled = user_settings.48;
+        runtime.category_panel_color = user_settings.49;
+        runtime.category_panel_rounding = user_settings.50;
+        runtime.category_panel_colors = user_settings.51;
+        runtime.category_header_bg_color = user_settings.52;
+        runtime.category_header_text_color = user_settings.53;
+        runtime.category_header_text_scale = user_settings.54;
+        runtime.category_header_height = user_settings.55;
+        runtime.tooltip_delay = user_settings.56;
+        runtime.tooltips_when_locked = user_settings.57;
+        runtime.tooltip_mode = user_settings.58;
+        runtime.unowned_expansions = user_settings.59;
+        runtime.unowned_content_mode = user_settings.60;
+        runtime.auto_solo_map = user_settings.61;
+        runtime.stream_export_enabled = user_settings.62;
+        runtime.stream_export_interval = user_settings.63;
+        runtime.stream_export_count = user_settings.64;
+        runtime.http_server_enabled = user_settings.65;
+        runtime.http_server_port = user_settings.66;
+        runtime.webhook_enabled = user_settings.67;
+        runtime.webhook_url = user_settings.68;
+        runtime.webhook_headers = user_settings.69;
+        runtime.webhook_body = user_settings.70;
+        runtime.show_agenda_window = user_settings.71;
+        runtime.agenda_tracked_only = user_settings.72;
+        runtime.show_diagnostics = user_settings.73;
+        runtime.show_log_window = user_settings.74;
+        runtime.qa_icon_style = user_settings.75;
+        runtime.qa_icon_path = user_settings.76;
+        runtime.qa_icon_hover_path = user_settings.77;
+        runtime.last_seen_version = user_settings.78;
+        runtime.past_event_brightness = user_settings.79;
+        runtime.past_event_alpha = user_settings.80;
+        runtime.future_event_brightness = user_settings.81;
+        runtime.future_event_alpha = user_settings.82;
+        runtime.hide_past_events = user_settings.83;
+        runtime.event_language = user_settings.84;
+        runtime.finished_today_mode = user_settings.85;
+        runtime.alt_zoom_cycle = user_settings.86;
+        runtime.minimap_cycle = user_settings.87;
+        runtime.collapsed_categories = user_settings.88;
+        runtime.category_keybinds = user_settings.89;
+        runtime.time_markers = user_settings.90;
+        runtime.category_header_counts = user_settings.91;
+        runtime.locked_window_height = user_settings.92;
+        runtime.fade_when_inactive = user_settings.93;
+        runtime.fade_opacity = user_settings.94;
+        runtime.fade_distance = user_settings.95;
+        runtime.fade_event_minutes = user_settings.96;
+        runtime.pause_while_loading = user_settings.97;
+        runtime.update_timeout_seconds = user_settings.98;
+        runtime.update_retries = user_settings.99;
+        runtime.update_proxy = user_settings.100;
+        runtime.last_chance_highlight = user_settings.101;
+        runtime.last_chance_color = user_settings.102;
     } // runtime lock dropped here
 }
 
+/// Track events that appeared on auto-tracking tracks since the overrides were saved
+fn auto_track_new_events(runtime: &mut RuntimeConfig, overrides: &HashMap<String, TrackOverride>) {
+    for track in runtime.tracks.iter().filter(|t| t.auto_track_new) {
+        // Nothing to compare against until the track's events have been saved once
+        let Some(known) = overrides.get(&track.name).map(|o| &o.known_events).filter(|k| !k.is_empty()) else {
+            continue;
+        };
+        for event in track.events.iter().filter(|e| e.enabled && !known.contains(&e.name)) {
+            if runtime.tracked_events.insert(TrackedEventId::new(&track.name, &event.name)) {
+                addon_log::info(format!("Now tracking new event '{}' on {}", event.name, track.name));
+            }
+        }
+    }
+}
+
 pub fn extract_user_overrides() {
     let runtime = RUNTIME_CONFIG.lock();
     let mut user_cfg = USER_CONFIG.lock();
     
     user_cfg.track_overrides.clear();
+    user_cfg.saved_names.clear();
     user_cfg.custom_tracks.clear();
     
     let (default_tracks, _) = load_tracks_from_json();
@@ -727,17 +2285,49 @@ pub fn extract_user_overrides() {
                 override_data.height = Some(track.height);
                 has_changes = true;
             }
+
+            if track.icon != default_track.icon {
+                override_data.icon = Some(track.icon.clone());
+                has_changes = true;
+            }
             
             for event in &track.events {
                 if !event.enabled {
                     override_data.disabled_events.push(event.name.clone());
                     has_changes = true;
                 }
+                if !event.show_on_timeline {
+                    override_data.hidden_events.push(event.name.clone());
+                    has_changes = true;
+                }
+                if !event.notify {
+                    override_data.silent_events.push(event.name.clone());
+                    has_changes = true;
+                }
+            }
+
+            if track.auto_track_new {
+                override_data.auto_track_new = true;
+                override_data.known_events = track.events.iter().map(|e| e.name.clone()).collect();
+                override_data.known_events.sort();
+                override_data.known_events.dedup();
+                has_changes = true;
             }
             
             if has_changes {
                 user_cfg.track_overrides.insert(track.name.clone(), override_data);
             }
+
+            let event_names: HashMap<String, String> = track.events.iter()
+                .filter(|e| !e.id.is_empty())
+                .map(|e| (e.id.clone(), e.name.clone()))
+                .collect();
+            if !track.id.is_empty() || !event_names.is_empty() {
+                user_cfg.saved_names.insert(
+                    track.stable_key().to_string(),
+                    SavedNames { name: track.name.clone(), category: track.category.clone(), events: event_names },
+                );
+            }
         } else {
             user_cfg.custom_tracks.push(track.clone());
         }
@@ -780,6 +2370,71 @@ pub fn extract_user_overrides() {
     user_cfg.tracked_events = runtime.tracked_events.clone();
     user_cfg.oneshot_events = runtime.oneshot_events.clone();
     user_cfg.notification_config = runtime.notification_config.clone();
+    user_cfg.auto_fit_track_heights = runtime.auto_fit_track_heights;
+    user_cfg.auto_fit_min_height = runtime.auto_fit_min_height;
+    user_cfg.auto_fit_max_height = runtime.auto_fit_max_height;
+    user_cfg.snap_windows = runtime.snap_windows;
+    user_cfg.snap_threshold = runtime.snap_threshold;
+    user_cfg.relative_window_positions = runtime.relative_window_positions;
+    user_cfg.main_window_position = runtime.main_window_position;
+    user_cfg.is_window_size_locked = runtime.is_window_size_locked;
+    user_cfg.disable_window_interaction = runtime.disable_window_interaction;
+    user_cfg.show_title_bar = runtime.show_title_bar;
+    user_cfg.category_panels_enabled = runtime.category_panels_enabled;
+    user_cfg.category_panel_color = runtime.category_panel_color;
+    user_cfg.category_panel_rounding = runtime.category_panel_rounding;
+    user_cfg.category_panel_colors = runtime.category_panel_colors.clone();
+    user_cfg.category_header_bg_color = runtime.category_header_bg_color;
+    user_cfg.category_header_text_color = runtime.category_header_text_color;
+    user_cfg.category_header_text_scale = runtime.category_header_text_scale;
+    user_cfg.category_header_height = runtime.category_header_height;
+    user_cfg.tooltip_delay = runtime.tooltip_delay;
+    user_cfg.tooltips_when_locked = runtime.tooltips_when_locked;
+    user_cfg.tooltip_mode = runtime.tooltip_mode;
+    user_cfg.unowned_expansions = runtime.unowned_expansions.clone();
+    user_cfg.unowned_content_mode = runtime.unowned_content_mode;
+    user_cfg.auto_solo_map = runtime.auto_solo_map;
+    user_cfg.stream_export_enabled = runtime.stream_export_enabled;
+    user_cfg.stream_export_interval = runtime.stream_export_interval;
+    user_cfg.stream_export_count = runtime.stream_export_count;
+    user_cfg.http_server_enabled = runtime.http_server_enabled;
+    user_cfg.http_server_port = runtime.http_server_port;
+    user_cfg.webhook_enabled = runtime.webhook_enabled;
+    user_cfg.webhook_url = runtime.webhook_url.clone();
+    user_cfg.webhook_headers = runtime.webhook_headers.clone();
+    user_cfg.webhook_body = runtime.webhook_body.clone();
+    user_cfg.show_agenda_window = runtime.show_agenda_window;
+    user_cfg.agenda_tracked_only = runtime.agenda_tracked_only;
+    user_cfg.show_diagnostics = runtime.show_diagnostics;
+    user_cfg.show_log_window = runtime.show_log_window;
+    user_cfg.qa_icon_style = runtime.qa_icon_style;
+    user_cfg.qa_icon_path = runtime.qa_icon_path.clone();
+    user_cfg.qa_icon_hover_path = runtime.qa_icon_hover_path.clone();
+    user_cfg.last_seen_version = runtime.last_seen_version.clone();
+    user_cfg.past_event_brightness = runtime.past_event_brightness;
+    user_cfg.past_event_alpha = runtime.past_event_alpha;
+    user_cfg.future_event_brightness = runtime.future_event_brightness;
+    user_cfg.future_event_alpha = runtime.future_event_alpha;
+    user_cfg.hide_past_events = runtime.hide_past_events;
+    user_cfg.event_language = runtime.event_language;
+    user_cfg.finished_today_mode = runtime.finished_today_mode;
+    user_cfg.alt_zoom_cycle = runtime.alt_zoom_cycle;
+    user_cfg.minimap_cycle = runtime.minimap_cycle;
+    user_cfg.collapsed_categories = runtime.collapsed_categories.clone();
+    user_cfg.category_keybinds = runtime.category_keybinds.clone();
+    user_cfg.time_markers = runtime.time_markers.clone();
+    user_cfg.category_header_counts = runtime.category_header_counts;
+    user_cfg.locked_window_height = runtime.locked_window_height;
+    user_cfg.fade_when_inactive = runtime.fade_when_inactive;
+    user_cfg.fade_opacity = runtime.fade_opacity;
+    user_cfg.fade_distance = runtime.fade_distance;
+    user_cfg.fade_event_minutes = runtime.fade_event_minutes;
+    user_cfg.pause_while_loading = runtime.pause_while_loading;
+    user_cfg.update_timeout_seconds = runtime.update_timeout_seconds;
+    user_cfg.update_retries = runtime.update_retries;
+    user_cfg.update_proxy = runtime.update_proxy.clone();
+    user_cfg.last_chance_highlight = runtime.last_chance_highlight;
+    user_cfg.last_chance_color = runtime.last_chance_color;
 }
 
 // === File I/O ===
@@ -788,14 +2443,52 @@ pub fn get_user_config_path() -> Option<PathBuf> {
     get_addon_dir("event_timers").map(|p| p.join(USER_CONFIG_FILENAME))
 }
 
+/// Local UTC offset the recurrence expressions were compiled against (MIN until the first frame)
+static RECURRENCE_UTC_OFFSET: AtomicI64 = AtomicI64::new(i64::MIN);
+
+/// Recompile recurrence expressions when the local UTC offset changes (daylight saving time),
+/// so "Sat 18:00" keeps meaning 18:00 local time. Call once per frame.
+pub fn refresh_recurrences() {
+    let offset = crate::time_utils::local_utc_offset_seconds();
+    let previous = RECURRENCE_UTC_OFFSET.swap(offset, Ordering::Relaxed);
+    if previous == offset || previous == i64::MIN {
+        return;
+    }
+
+    let snapshot = RUNTIME_CONFIG.snapshot();
+    let has_recurrences = snapshot.tracks.iter().flat_map(|t| &t.events).any(|e| !e.recurrence.trim().is_empty());
+    if !has_recurrences {
+        return;
+    }
+
+    let mut config = RUNTIME_CONFIG.lock();
+    let mut recompiled = 0;
+    for track in &mut config.tracks {
+        let base_time = track.base_time;
+        for event in track.events.iter_mut().filter(|e| !e.recurrence.trim().is_empty()) {
+            if event.compile_recurrence(base_time).is_ok() {
+                recompiled += 1;
+            }
+        }
+    }
+    addon_log::info(format!("UTC offset changed, recompiled {} recurring events", recompiled));
+}
+
 pub fn load_user_config() {
     if let Some(path) = get_user_config_path() {
         if path.exists() {
             if let Ok(json_str) = fs::read_to_string(&path) {
-                if let Ok(loaded) = serde_json::from_str::<UserConfig>(&json_str) {
-                    *USER_CONFIG.lock() = loaded;
-                    apply_user_overrides();
-                    return;
+                match serde_json::from_str::<UserConfig>(&json_str) {
+                    Ok(mut loaded) => {
+                        // Configs saved before the size lock was split out locked both position and size
+                        if !json_str.contains("\"is_window_size_locked\"") {
+                            loaded.is_window_size_locked = loaded.is_window_locked;
+                        }
+                        *USER_CONFIG.lock() = loaded;
+                        apply_user_overrides();
+                        return;
+                    }
+                    Err(e) => addon_log::error(format!("Failed to parse user config, using defaults: {}", e)),
                 }
             }
         }
@@ -813,7 +2506,9 @@ pub fn save_user_config() {
             fs::create_dir_all(dir).ok();
         }
         if let Ok(json_str) = serde_json::to_string_pretty(&*user_cfg) {
-            fs::write(&path, json_str).ok();
+            if let Err(e) = fs::write(&path, json_str) {
+                addon_log::error(format!("Failed to save user config: {}", e));
+            }
         }
     }
 }
@@ -840,3 +2535,134 @@ pub fn get_track_visual_config(
         padding: global_padding,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::{NotificationState, NotifiedKey, OngoingNotificationKey};
+
+    /// A pack track with a stable id, its events given as (id, name)
+    fn track(id: &str, name: &str, category: &str, events: &[(&str, &str)]) -> EventTrack {
+        EventTrack {
+            id: id.to_string(),
+            name: name.to_string(),
+            category: category.to_string(),
+            events: events
+                .iter()
+                .map(|(id, name)| TimelineEvent { id: id.to_string(), name: name.to_string(), ..Default::default() })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// User config saved while the pack had the "Old" names
+    fn saved_config() -> UserConfig {
+        let mut user_cfg = UserConfig::default();
+        user_cfg.saved_names.insert(
+            "tequatl".to_string(),
+            SavedNames {
+                name: "Old Boss".to_string(),
+                category: "Old Category".to_string(),
+                events: HashMap::from([("spawn".to_string(), "Old Spawn".to_string())]),
+            },
+        );
+        user_cfg
+    }
+
+    fn renamed_pack() -> Vec<EventTrack> {
+        vec![track("tequatl", "World Boss", "Bosses", &[("spawn", "Spawn")])]
+    }
+
+    #[test]
+    fn finds_renamed_tracks_events_and_categories() {
+        let renames = Renames::find(&saved_config(), &renamed_pack());
+        assert_eq!(renames.tracks["Old Boss"], "World Boss");
+        assert_eq!(renames.events[&("World Boss".to_string(), "Old Spawn".to_string())], "Spawn");
+        assert_eq!(renames.categories["Old Category"], "Bosses");
+
+        let mut id = TrackedEventId::new("Old Boss", "Old Spawn");
+        assert_eq!(renames.event_id(&mut id), 2);
+        assert_eq!(id, TrackedEventId::new("World Boss", "Spawn"));
+    }
+
+    #[test]
+    fn nothing_to_migrate_without_renames() {
+        let pack = vec![track("tequatl", "Old Boss", "Old Category", &[("spawn", "Old Spawn")])];
+        assert!(Renames::find(&saved_config(), &pack).is_empty());
+        assert!(migrate_renamed_events(&mut saved_config(), &pack).is_none());
+    }
+
+    #[test]
+    fn category_still_in_the_pack_is_not_renamed() {
+        // The track moved, but its former category is still there
+        let mut pack = renamed_pack();
+        pack.push(track("", "Other", "Old Category", &[]));
+        assert!(Renames::find(&saved_config(), &pack).categories.is_empty());
+    }
+
+    #[test]
+    fn category_split_across_new_categories_is_left_alone() {
+        let mut user_cfg = saved_config();
+        user_cfg.saved_names.insert(
+            "shatterer".to_string(),
+            SavedNames { name: "Shatterer".to_string(), category: "Old Category".to_string(), events: HashMap::new() },
+        );
+        let mut pack = renamed_pack();
+        pack.push(track("shatterer", "Shatterer", "Dragons", &[]));
+        assert!(Renames::find(&user_cfg, &pack).categories.is_empty());
+    }
+
+    #[test]
+    fn migrates_event_and_category_settings() {
+        let mut user_cfg = saved_config();
+        let old_id = TrackedEventId::new("Old Boss", "Old Spawn");
+        user_cfg.tracked_events.insert(old_id.clone());
+        user_cfg.track_overrides.insert(
+            "Old Boss".to_string(),
+            TrackOverride { disabled_events: vec!["Old Spawn".to_string()], ..Default::default() },
+        );
+        user_cfg.notification_config.event_priorities.push(EventPriorityEntry { event_id: old_id, priority: EventPriority::High });
+        user_cfg.category_visibility.insert("Old Category".to_string(), false);
+        user_cfg.collapsed_categories.insert("Old Category".to_string());
+        user_cfg.category_order = vec!["Bosses".to_string(), "Old Category".to_string()];
+        user_cfg.category_keybinds = vec!["Old Category".to_string()];
+        user_cfg.category_panel_colors.insert("Old Category".to_string(), [1.0; 4]);
+        user_cfg.notification_config.upcoming_collapsed_groups = vec!["Old Category".to_string(), "Old Boss".to_string()];
+        user_cfg.custom_tracks.push(track("", "My Track", "Old Category", &[]));
+
+        assert!(migrate_renamed_events(&mut user_cfg, &renamed_pack()).is_some());
+
+        let new_id = TrackedEventId::new("World Boss", "Spawn");
+        assert!(user_cfg.tracked_events.contains(&new_id));
+        assert_eq!(user_cfg.track_overrides["World Boss"].disabled_events, vec!["Spawn".to_string()]);
+        assert_eq!(user_cfg.notification_config.event_priorities[0].event_id, new_id);
+        assert_eq!(user_cfg.category_visibility, HashMap::from([("Bosses".to_string(), false)]));
+        assert_eq!(user_cfg.collapsed_categories, HashSet::from(["Bosses".to_string()]));
+        // The renamed category becomes a duplicate of the one already listed
+        assert_eq!(user_cfg.category_order, vec!["Bosses".to_string()]);
+        assert_eq!(user_cfg.category_keybinds, vec!["Bosses".to_string()]);
+        assert!(user_cfg.category_panel_colors.contains_key("Bosses"));
+        assert_eq!(user_cfg.notification_config.upcoming_collapsed_groups, vec!["Bosses".to_string(), "World Boss".to_string()]);
+        assert_eq!(user_cfg.custom_tracks[0].category, "Bosses");
+    }
+
+    #[test]
+    fn migrates_notification_records() {
+        let renames = Renames::find(&saved_config(), &renamed_pack());
+        let (old_id, new_id) = (TrackedEventId::new("Old Boss", "Old Spawn"), TrackedEventId::new("World Boss", "Spawn"));
+        let other = TrackedEventId::new("Karka Queen", "Spawn");
+
+        let mut state = NotificationState::new();
+        state.notified_reminders.insert(NotifiedKey { event_id: old_id.clone(), start_time: 100, minutes_before: 5, repetition: 0 });
+        state.muted_occurrences.insert(OngoingNotificationKey { event_id: old_id.clone(), start_time: 100 });
+        state.event_last_notified.insert(old_id.clone(), 50);
+        state.event_last_notified.insert(other.clone(), 60);
+
+        assert_eq!(state.migrate_event_ids(|id| renames.event_id(id)), 6);
+        assert!(state.notified_reminders.iter().all(|key| key.event_id == new_id));
+        assert!(state.muted_occurrences.contains(&OngoingNotificationKey { event_id: new_id.clone(), start_time: 100 }));
+        assert_eq!(state.event_last_notified.get(&new_id), Some(&50));
+        assert_eq!(state.event_last_notified.get(&other), Some(&60));
+        assert!(!state.event_last_notified.contains_key(&old_id));
+    }
+}
@@ -0,0 +1,142 @@
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Parts of the per-frame work that are timed separately
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Section {
+    Notifications,
+    MainWindow,
+    Panels,
+    LockWait,
+}
+
+impl Section {
+    pub const ALL: [Section; 4] = [Section::Notifications, Section::MainWindow, Section::Panels, Section::LockWait];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Section::Notifications => "Notification update",
+            Section::MainWindow => "Main window",
+            Section::Panels => "Toasts & panels",
+            Section::LockWait => "Config lock wait",
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Timing of one section: last frame, smoothed average and the worst frame of the last second
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SectionTiming {
+    pub last_ms: f32,
+    pub average_ms: f32,
+    pub peak_ms: f32,
+}
+
+#[derive(Debug, Default)]
+pub struct FrameStats {
+    pub sections: [SectionTiming; 4],
+    /// Total addon time for the last frame and its smoothed average
+    pub total_last_ms: f32,
+    pub total_average_ms: f32,
+    current: [Duration; 4],
+    peak_window: [f32; 4],
+    peak_window_start: Option<Instant>,
+}
+
+impl FrameStats {
+    pub fn timing(&self, section: Section) -> SectionTiming {
+        self.sections[section.index()]
+    }
+}
+
+/// Global frame statistics (only collected while the overlay is enabled)
+pub static FRAME_STATS: Lazy<Mutex<FrameStats>> = Lazy::new(|| Mutex::new(FrameStats::default()));
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Weight of the newest frame in the smoothed averages
+const SMOOTHING: f32 = 0.05;
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Run `f`, adding its duration to `section` for the current frame
+pub fn measure<R>(section: Section, f: impl FnOnce() -> R) -> R {
+    if !is_enabled() {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    add(section, start.elapsed());
+    result
+}
+
+/// Lock a mutex, recording how long the caller was blocked waiting for it
+pub fn timed_lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    if let Some(guard) = mutex.try_lock() {
+        return guard;
+    }
+    if !is_enabled() {
+        return mutex.lock();
+    }
+    let start = Instant::now();
+    let guard = mutex.lock();
+    add(Section::LockWait, start.elapsed());
+    guard
+}
+
+fn add(section: Section, duration: Duration) {
+    FRAME_STATS.lock().current[section.index()] += duration;
+}
+
+/// Fold the current frame's timings into the statistics. Call once at the end of the frame.
+pub fn end_frame() {
+    if !is_enabled() {
+        return;
+    }
+
+    let mut guard = FRAME_STATS.lock();
+    let stats = &mut *guard;
+    let now = Instant::now();
+    let reset_peaks = stats
+        .peak_window_start
+        .is_none_or(|start| now.duration_since(start) >= Duration::from_secs(1));
+    if reset_peaks {
+        stats.peak_window_start = Some(now);
+    }
+
+    let mut total = 0.0;
+    for section in Section::ALL {
+        let i = section.index();
+        let ms = stats.current[i].as_secs_f32() * 1000.0;
+        stats.current[i] = Duration::ZERO;
+
+        // Lock waits overlap the sections they happen in
+        if section != Section::LockWait {
+            total += ms;
+        }
+
+        let timing = &mut stats.sections[i];
+        timing.last_ms = ms;
+        timing.average_ms += (ms - timing.average_ms) * SMOOTHING;
+        if reset_peaks {
+            timing.peak_ms = stats.peak_window[i];
+            stats.peak_window[i] = ms;
+        } else {
+            stats.peak_window[i] = stats.peak_window[i].max(ms);
+        }
+    }
+
+    stats.total_last_ms = total;
+    stats.total_average_ms += (total - stats.total_average_ms) * SMOOTHING;
+}
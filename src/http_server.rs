@@ -0,0 +1,129 @@
+// Optional local HTTP endpoint serving the upcoming events as JSON (the same document as
+// stream/upcoming.json), for overlays, dashboards or home automation.
+//
+// Only bound to 127.0.0.1 and off by default, and no CORS headers are sent, so web pages open in
+// a browser can't read it. Every GET gets the latest export; the listener runs on its own thread
+// and polls a stop flag so it can be shut down or moved to another port.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::addon_log;
+
+/// Latest upcoming-events JSON, served as is
+static LATEST: Lazy<Mutex<String>> = Lazy::new(|| Mutex::new(String::from("{\"events\":[]}")));
+
+struct Server {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+/// The running server, or the port that failed to bind (not retried until the port changes)
+static SERVER: Lazy<Mutex<Option<Result<Server, u16>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Replace the JSON handed to the next request
+pub fn publish(json: &str) {
+    let mut latest = LATEST.lock();
+    latest.clear();
+    latest.push_str(json);
+}
+
+/// Start, stop or move the server to match the settings
+pub fn sync(enabled: bool, port: u32) {
+    let port = port.clamp(1024, u16::MAX as u32) as u16;
+    let mut server = SERVER.lock();
+    let current = match server.as_ref() {
+        Some(Ok(running)) => Some(running.port),
+        Some(Err(failed)) => Some(*failed),
+        None => None,
+    };
+    if enabled && current == Some(port) || !enabled && current.is_none() {
+        return;
+    }
+
+    // Settings changes happen on the render thread, so the old listener is only told to stop
+    if let Some(Ok(running)) = server.take() {
+        running.stop.store(true, Ordering::Relaxed);
+        addon_log::info(format!("HTTP endpoint on port {} stopped", running.port));
+    }
+    if enabled {
+        *server = Some(start(port));
+    }
+}
+
+/// Stop the server if it's running and wait for its thread, which must not outlive the addon
+pub fn stop() {
+    if let Some(Ok(running)) = SERVER.lock().take() {
+        shut_down(running);
+    }
+}
+
+fn start(port: u16) -> Result<Server, u16> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+        .map_err(|e| {
+            addon_log::warn(format!("Could not start the HTTP endpoint on port {}: {}", port, e));
+            port
+        })?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread = {
+        let stop = Arc::clone(&stop);
+        std::thread::spawn(move || run(listener, &stop))
+    };
+    addon_log::info(format!("HTTP endpoint listening on http://127.0.0.1:{}/", port));
+    Ok(Server { port, stop, thread })
+}
+
+fn shut_down(server: Server) {
+    server.stop.store(true, Ordering::Relaxed);
+    let _ = server.thread.join();
+    addon_log::info(format!("HTTP endpoint on port {} stopped", server.port));
+}
+
+fn run(listener: TcpListener, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            // A client hanging up early isn't worth a log line
+            Ok((stream, _)) => {
+                let _ = respond(stream);
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => std::thread::sleep(Duration::from_millis(100)),
+            Err(e) => {
+                addon_log::warn(format!("HTTP endpoint stopped accepting connections: {}", e));
+                return;
+            }
+        }
+    }
+}
+
+fn respond(mut stream: TcpStream) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+
+    // Only the request line matters; the rest of the request is ignored
+    let mut buffer = [0u8; 1024];
+    let read = stream.read(&mut buffer)?;
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    let method = request.split_whitespace().next().unwrap_or("");
+
+    let (status, body) = match method {
+        "GET" => ("200 OK", LATEST.lock().clone()),
+        _ => ("405 Method Not Allowed", String::new()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\n\
+         Cache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes())
+}
@@ -0,0 +1,132 @@
+// Runtime variants of the Quick Access icons, derived from the embedded or user-supplied PNGs.
+
+/// RGBA8 image decoded from a PNG
+struct Rgba {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+fn decode(png_bytes: &[u8]) -> Option<Rgba> {
+    let mut decoder = png::Decoder::new(png_bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().ok()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).ok()?;
+    buffer.truncate(info.buffer_size());
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => buffer.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => buffer.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => return None,
+    };
+    Some(Rgba { width: info.width, height: info.height, pixels })
+}
+
+fn encode(image: &Rgba) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut bytes, image.width, image.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().ok()?;
+        writer.write_image_data(&image.pixels).ok()?;
+    }
+    Some(bytes)
+}
+
+/// Icon variant for the Quick Access button: optionally recoloured to a light monochrome,
+/// greyed out with a red slash while muted, and/or with a numeric badge in the top-right corner
+pub fn quick_access_variant(png_bytes: &[u8], monochrome: bool, muted: bool, badge: Option<&str>) -> Option<Vec<u8>> {
+    let mut image = decode(png_bytes)?;
+    if monochrome {
+        draw_monochrome(&mut image);
+    }
+    if muted {
+        draw_muted(&mut image);
+    }
+    if let Some(text) = badge {
+        draw_badge(&mut image, text);
+    }
+    encode(&image)
+}
+
+fn set_pixel(image: &mut Rgba, x: i64, y: i64, color: [u8; 4]) {
+    if x < 0 || y < 0 || x >= image.width as i64 || y >= image.height as i64 {
+        return;
+    }
+    let index = ((y * image.width as i64 + x) * 4) as usize;
+    image.pixels[index..index + 4].copy_from_slice(&color);
+}
+
+fn draw_monochrome(image: &mut Rgba) {
+    for pixel in image.pixels.chunks_exact_mut(4) {
+        let luminance = 0.299 * pixel[0] as f32 + 0.587 * pixel[1] as f32 + 0.114 * pixel[2] as f32;
+        // Lift towards white so the silhouette reads on dark UI
+        let value = (96.0 + luminance * 0.625) as u8;
+        pixel[0] = value;
+        pixel[1] = value;
+        pixel[2] = value;
+    }
+}
+
+fn draw_muted(image: &mut Rgba) {
+    let (width, height) = (image.width as i64, image.height as i64);
+
+    for pixel in image.pixels.chunks_exact_mut(4) {
+        let grey = (0.299 * pixel[0] as f32 + 0.587 * pixel[1] as f32 + 0.114 * pixel[2] as f32) * 0.6;
+        pixel[0] = grey as u8;
+        pixel[1] = grey as u8;
+        pixel[2] = grey as u8;
+    }
+
+    // Slash from top-right to bottom-left, about an eighth of the icon wide
+    let thickness = (width.min(height) / 16).max(1);
+    for y in 0..height {
+        let center = width - 1 - y * width / height;
+        for x in (center - thickness)..=(center + thickness) {
+            set_pixel(image, x, y, [230, 60, 60, 255]);
+        }
+    }
+}
+
+/// Red disc with white 3x5 pixel-font text, scaled to the icon size
+fn draw_badge(image: &mut Rgba, text: &str) {
+    let size = image.width.min(image.height) as i64;
+    let scale = (size / 20).max(1);
+    let radius = size * 9 / 32;
+    let (center_x, center_y) = (image.width as i64 - radius - 1, radius + 1);
+
+    for y in (center_y - radius)..=(center_y + radius) {
+        for x in (center_x - radius)..=(center_x + radius) {
+            let (dx, dy) = (x - center_x, y - center_y);
+            if dx * dx + dy * dy <= radius * radius {
+                set_pixel(image, x, y, [210, 40, 40, 255]);
+            }
+        }
+    }
+
+    let glyph_width = 4 * scale;
+    let text_width = text.chars().count() as i64 * glyph_width - scale;
+    let left = center_x - text_width / 2;
+    let top = center_y - 5 * scale / 2;
+    for (index, ch) in text.chars().enumerate() {
+        let bits = crate::image_export::glyph(ch);
+        for row in 0..5 {
+            for col in 0..3 {
+                if bits & (1 << (14 - (row * 3 + col))) == 0 {
+                    continue;
+                }
+                let x0 = left + index as i64 * glyph_width + col * scale;
+                let y0 = top + row * scale;
+                for y in y0..y0 + scale {
+                    for x in x0..x0 + scale {
+                        set_pixel(image, x, y, [255, 255, 255, 255]);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,312 @@
+// Software renderer for sharing the timeline as a PNG.
+//
+// The addon has no access to the game's render targets, so the visible timeline is
+// redrawn here into a plain RGBA buffer: one row per shown track, grouped by category,
+// with a time ruler and the now-line. Labels use a tiny built-in 3x5 pixel font
+// (upper case, digits and common punctuation), which keeps the export dependency-free
+// apart from the PNG encoder.
+
+use std::{collections::HashSet, fs, io::BufWriter, path::PathBuf};
+
+use chrono::{DateTime, Local};
+use nexus::paths::get_addon_dir;
+
+use crate::config::{RuntimeConfig, UnownedContentMode};
+use crate::json_loader::EventTrack;
+use crate::time_utils::{format_time_only, local_utc_offset_seconds};
+
+const IMAGE_WIDTH: u32 = 1400;
+const LABEL_WIDTH: u32 = 220;
+const RULER_HEIGHT: u32 = 24;
+const HEADER_HEIGHT: u32 = 18;
+const TRACK_HEIGHT: u32 = 24;
+const TRACK_GAP: u32 = 2;
+const MARGIN: u32 = 6;
+
+/// Font pixels are drawn as 2x2 blocks
+const FONT_SCALE: u32 = 2;
+const GLYPH_ADVANCE: u32 = 4 * FONT_SCALE;
+const GLYPH_HEIGHT: u32 = 5 * FONT_SCALE;
+
+const BACKGROUND: [f32; 4] = [0.08, 0.08, 0.09, 1.0];
+const TEXT_COLOR: [f32; 4] = [0.9, 0.9, 0.9, 1.0];
+const MUTED_TEXT: [f32; 4] = [0.6, 0.6, 0.6, 1.0];
+const GRID_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.12];
+const NOW_LINE: [f32; 4] = [1.0, 0.25, 0.25, 1.0];
+
+/// Rows of the export, in timeline order
+enum Row<'a> {
+    Header(&'a str),
+    Track(&'a EventTrack),
+}
+
+/// Render the timeline as currently configured (same tracks, view range and now position)
+/// and save it as a PNG in the addon's exports folder. Returns the written file.
+pub fn export_timeline_png(config: &RuntimeConfig, current_time: i64) -> Result<PathBuf, String> {
+    let rows = collect_rows(config);
+    if rows.is_empty() {
+        return Err("No visible tracks to export".to_string());
+    }
+
+    let content_height: u32 = rows
+        .iter()
+        .map(|row| match row {
+            Row::Header(_) => HEADER_HEIGHT,
+            Row::Track(_) => TRACK_HEIGHT + TRACK_GAP,
+        })
+        .sum();
+    let mut canvas = Canvas::new(IMAGE_WIDTH, RULER_HEIGHT + content_height + MARGIN * 2);
+    canvas.fill_rect(0, 0, canvas.width as i64, canvas.height as i64, BACKGROUND);
+
+    let view_range = config.view_range_seconds.max(60.0) as i64;
+    let view_start = current_time - (view_range as f32 * config.current_time_position) as i64;
+    let view_end = view_start + view_range;
+    let timeline_x = (LABEL_WIDTH + MARGIN) as i64;
+    let timeline_width = (IMAGE_WIDTH - LABEL_WIDTH - MARGIN * 2) as i64;
+    let time_to_x = |time: i64| timeline_x + (time - view_start) * timeline_width / view_range;
+
+    // Ruler: gridlines and local clock labels
+    let tick = ruler_tick(view_range);
+    let utc_offset = local_utc_offset_seconds();
+    let mut tick_time = view_start - (view_start + utc_offset).rem_euclid(tick) + tick;
+    while tick_time < view_end {
+        let x = time_to_x(tick_time);
+        canvas.fill_rect(x, RULER_HEIGHT as i64, 1, canvas.height as i64, GRID_COLOR);
+        let label = format_time_only(tick_time);
+        canvas.draw_text(x - text_width(&label) as i64 / 2, MARGIN as i64, &label, MUTED_TEXT);
+        tick_time += tick;
+    }
+
+    let stamp = DateTime::from_timestamp(current_time, 0)
+        .map(|d| d.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    canvas.draw_text(MARGIN as i64, MARGIN as i64, &stamp, TEXT_COLOR);
+
+    let mut y = (RULER_HEIGHT + MARGIN) as i64;
+    for row in &rows {
+        match row {
+            Row::Header(category) => {
+                canvas.draw_text(MARGIN as i64, y + (HEADER_HEIGHT - GLYPH_HEIGHT) as i64 / 2, category, MUTED_TEXT);
+                y += HEADER_HEIGHT as i64;
+            }
+            Row::Track(track) => {
+                let height = TRACK_HEIGHT as i64;
+                let text_y = y + (TRACK_HEIGHT - GLYPH_HEIGHT) as i64 / 2;
+                canvas.fill_rect(0, y, IMAGE_WIDTH as i64, height, config.global_track_background);
+                canvas.draw_text_clipped(MARGIN as i64 * 2, text_y, &track.name, TEXT_COLOR, LABEL_WIDTH as i64 - MARGIN as i64 * 2);
+
+                let lookback = track.events.iter().map(|e| e.duration + e.pre_duration).max().unwrap_or(0);
+                for (event, start) in track.occurrences_between(view_start - lookback, view_end) {
+                    if !event.show_on_timeline {
+                        continue;
+                    }
+                    // Unowned content follows the timeline: skipped when hidden, faded when dimmed
+                    let owned = config.owns_expansion(event.required_expansion(track));
+                    if !owned && config.unowned_content_mode == UnownedContentMode::Hide {
+                        continue;
+                    }
+
+                    let mut color = event.color.to_array();
+                    color[3] = if owned { 1.0 } else { 0.3 };
+                    let bar_start = time_to_x(start.max(view_start)).max(timeline_x);
+                    let bar_end = time_to_x((start + event.duration).min(view_end));
+                    if bar_end <= timeline_x {
+                        continue;
+                    }
+
+                    if event.pre_duration > 0 {
+                        let pre_start = time_to_x((start - event.pre_duration).max(view_start));
+                        canvas.fill_rect(pre_start, y + 2, bar_start - pre_start, height - 4, [color[0], color[1], color[2], color[3] * 0.35]);
+                    }
+                    canvas.fill_rect(bar_start, y + 2, bar_end - bar_start, height - 4, color);
+                    canvas.draw_text_clipped(bar_start + 4, text_y, &event.name, text_color_for(color), bar_end - bar_start - 8);
+                }
+                y += height + TRACK_GAP as i64;
+            }
+        }
+    }
+
+    let now_x = time_to_x(current_time);
+    canvas.fill_rect(now_x - 1, RULER_HEIGHT as i64, 2, canvas.height as i64, NOW_LINE);
+
+    let dir = get_addon_dir("event_timers")
+        .ok_or("Addon directory not available")?
+        .join("exports");
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let file_name = DateTime::from_timestamp(current_time, 0)
+        .map(|d| d.with_timezone(&Local).format("timeline_%Y%m%d_%H%M%S.png").to_string())
+        .unwrap_or_else(|| "timeline.png".to_string());
+    let path = dir.join(file_name);
+    canvas.save_png(&path)?;
+    Ok(path)
+}
+
+/// Categories and tracks in the same order and with the same visibility as the timeline
+fn collect_rows(config: &RuntimeConfig) -> Vec<Row<'_>> {
+    let mut categories: Vec<&str> = config.category_order.iter().map(String::as_str).collect();
+    let mut seen: HashSet<&str> = categories.iter().copied().collect();
+    for track in &config.tracks {
+        if seen.insert(&track.category) {
+            categories.push(&track.category);
+        }
+    }
+
+    let mut rows = Vec::new();
+    for category in categories {
+        if !*config.category_visibility.get(category).unwrap_or(&true) {
+            continue;
+        }
+        let tracks: Vec<&EventTrack> = config
+            .tracks
+            .iter()
+            .filter(|t| t.category == category && config.is_track_shown(t))
+            .collect();
+        if tracks.is_empty() {
+            continue;
+        }
+        if config.show_category_headers && !category.is_empty() {
+            rows.push(Row::Header(category));
+        }
+        rows.extend(tracks.into_iter().map(Row::Track));
+    }
+    rows
+}
+
+/// Gridline spacing that keeps roughly 6-12 labels across the view
+fn ruler_tick(view_range: i64) -> i64 {
+    [300, 600, 900, 1800, 3600, 7200, 10800, 21600]
+        .into_iter()
+        .find(|&tick| view_range / tick <= 12)
+        .unwrap_or(43200)
+}
+
+fn text_color_for(bg: [f32; 4]) -> [f32; 4] {
+    let luminance = 0.299 * bg[0] + 0.587 * bg[1] + 0.114 * bg[2];
+    if luminance > 0.5 { [0.0, 0.0, 0.0, 1.0] } else { [1.0, 1.0, 1.0, 1.0] }
+}
+
+fn text_width(text: &str) -> u32 {
+    text.chars().count() as u32 * GLYPH_ADVANCE
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        Self { width, height, pixels: vec![0; (width * height * 4) as usize] }
+    }
+
+    /// Alpha-blend a rectangle, clipped to the canvas
+    fn fill_rect(&mut self, x: i64, y: i64, w: i64, h: i64, color: [f32; 4]) {
+        let x0 = x.clamp(0, self.width as i64);
+        let y0 = y.clamp(0, self.height as i64);
+        let x1 = (x + w).clamp(0, self.width as i64);
+        let y1 = (y + h).clamp(0, self.height as i64);
+        let alpha = color[3].clamp(0.0, 1.0);
+        for py in y0..y1 {
+            for px in x0..x1 {
+                let index = ((py * self.width as i64 + px) * 4) as usize;
+                for (channel, &value) in color.iter().take(3).enumerate() {
+                    let dst = self.pixels[index + channel] as f32 / 255.0;
+                    let blended = value * alpha + dst * (1.0 - alpha);
+                    self.pixels[index + channel] = (blended.clamp(0.0, 1.0) * 255.0).round() as u8;
+                }
+                self.pixels[index + 3] = 255;
+            }
+        }
+    }
+
+    fn draw_text(&mut self, x: i64, y: i64, text: &str, color: [f32; 4]) {
+        self.draw_text_clipped(x, y, text, color, i64::MAX);
+    }
+
+    /// Draw text, dropping whole glyphs that would extend past `max_width`
+    fn draw_text_clipped(&mut self, x: i64, y: i64, text: &str, color: [f32; 4], max_width: i64) {
+        let scale = FONT_SCALE as i64;
+        let mut pen_x = x;
+        for ch in text.chars() {
+            if pen_x + 3 * scale - x > max_width {
+                break;
+            }
+            let bits = glyph(ch.to_ascii_uppercase());
+            for row in 0..5 {
+                for col in 0..3 {
+                    if bits & (1 << (14 - (row * 3 + col))) != 0 {
+                        self.fill_rect(pen_x + col * scale, y + row * scale, scale, scale, color);
+                    }
+                }
+            }
+            pen_x += GLYPH_ADVANCE as i64;
+        }
+    }
+
+    fn save_png(&self, path: &PathBuf) -> Result<(), String> {
+        let file = fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let mut encoder = png::Encoder::new(BufWriter::new(file), self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| format!("Failed to write PNG: {}", e))?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(|e| format!("Failed to write PNG: {}", e))
+    }
+}
+
+/// 3x5 glyph bitmap, rows top to bottom, most significant bit is the top-left pixel
+pub(crate) fn glyph(ch: char) -> u16 {
+    match ch {
+        'A' => 0b010_101_111_101_101,
+        'B' => 0b110_101_110_101_110,
+        'C' => 0b011_100_100_100_011,
+        'D' => 0b110_101_101_101_110,
+        'E' => 0b111_100_110_100_111,
+        'F' => 0b111_100_110_100_100,
+        'G' => 0b011_100_101_101_011,
+        'H' => 0b101_101_111_101_101,
+        'I' => 0b111_010_010_010_111,
+        'J' => 0b001_001_001_101_010,
+        'K' => 0b101_101_110_101_101,
+        'L' => 0b100_100_100_100_111,
+        'M' => 0b101_111_111_101_101,
+        'N' => 0b110_101_101_101_101,
+        'O' => 0b010_101_101_101_010,
+        'P' => 0b110_101_110_100_100,
+        'Q' => 0b010_101_101_110_011,
+        'R' => 0b110_101_110_101_101,
+        'S' => 0b011_100_010_001_110,
+        'T' => 0b111_010_010_010_010,
+        'U' => 0b101_101_101_101_111,
+        'V' => 0b101_101_101_101_010,
+        'W' => 0b101_101_111_111_101,
+        'X' => 0b101_101_010_101_101,
+        'Y' => 0b101_101_010_010_010,
+        'Z' => 0b111_001_010_100_111,
+        '0' => 0b111_101_101_101_111,
+        '1' => 0b010_110_010_010_111,
+        '2' => 0b110_001_010_100_111,
+        '3' => 0b110_001_010_001_110,
+        '4' => 0b101_101_111_001_001,
+        '5' => 0b111_100_110_001_110,
+        '6' => 0b011_100_111_101_111,
+        '7' => 0b111_001_010_010_010,
+        '8' => 0b111_101_111_101_111,
+        '9' => 0b111_101_111_001_110,
+        ':' => 0b000_010_000_010_000,
+        '-' => 0b000_000_111_000_000,
+        '.' => 0b000_000_000_000_010,
+        ',' => 0b000_000_000_010_100,
+        '\'' => 0b010_010_000_000_000,
+        '(' => 0b001_010_010_010_001,
+        ')' => 0b100_010_010_010_100,
+        '/' => 0b001_001_010_100_100,
+        '&' => 0b010_101_010_101_011,
+        '+' => 0b000_010_111_010_000,
+        '!' => 0b010_010_010_000_010,
+        '?' => 0b110_001_010_000_010,
+        _ => 0,
+    }
+}
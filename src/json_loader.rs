@@ -1,13 +1,29 @@
 use nexus::paths::get_addon_dir;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::{fs, path::PathBuf, time::{SystemTime, UNIX_EPOCH}};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{collections::{BTreeSet, HashMap}, fs, path::PathBuf, time::{SystemTime, UNIX_EPOCH}};
+
+use crate::addon_log;
+use crate::backups::{list_backups, BackupKind};
+use crate::condition::Condition;
+use crate::config::EventLanguage;
+use crate::timeline;
 
 // Embedded fallback JSON
 const EMBEDDED_JSON: &str = include_str!("../event_tracks.json");
 
+const ADDON_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Layout of event_tracks.json this version reads and writes. Raise it when fields are added
+/// (and list them below) so older installs can tell a newer pack apart.
+pub const SCHEMA_VERSION: u32 = 1;
+
 // === Public Data Structures ===
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct EventColor {
     pub r: f32,
     pub g: f32,
@@ -31,7 +47,7 @@ impl EventColor {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum TimelineType {
     #[serde(rename = "real_time")]
     RealTime,
@@ -39,38 +55,193 @@ pub enum TimelineType {
     GameTime,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// An extra labelled string that can be copied from an event (closest waypoint, squad message, ...)
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CopyAction {
+    pub label: String,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct TimelineEvent {
     pub name: String,
+    /// Stable identifier from the pack (optional); settings follow it when `name` changes
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    /// Translated names by language code ("de", "fr", "es"); `name` stays the identifier
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub name_localized: HashMap<String, String>,
+    /// Former names of this event; user settings saved under one are migrated to `name` on load
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
     pub start_offset: i64,
     pub duration: i64,
     pub cycle_duration: i64,
     pub color: EventColor,
     #[serde(default)]
     pub copy_text: String,
+    /// Additional copyable strings, offered in a menu alongside copy_text
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copy_actions: Vec<CopyAction>,
     #[serde(default = "default_true")]
     pub enabled: bool,
+    /// Drawn on the timeline; turning it off declutters the view but keeps notifications
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub show_on_timeline: bool,
+    /// Notified when tracked or watchlisted; turning it off keeps the bar but never pings
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub notify: bool,
+    /// Optional recurrence expression (e.g. "every 2h at :00 and :30", "Sat,Sun 18:00").
+    /// When set, start_offset and cycle_duration are derived from it.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub recurrence: String,
+    /// Spawn offsets compiled from `recurrence`, relative to the track's base time
+    #[serde(skip)]
+    pub spawn_offsets: Vec<i64>,
+    /// Name of the meta chain this event is a phase of (empty for standalone events)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub chain: String,
+    /// Warmup ("pre-event") length in seconds shown before the main bar
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub pre_duration: i64,
+    /// Free-form notes shown in tooltips ("bring CC", "needs HoT")
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub map_name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub rewards: String,
+    /// Expansion id required to take part (see EXPANSIONS); empty inherits the track's
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub expansion: String,
+    /// Popular meta whose map instances fill up before it starts; reminders come earlier
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fills_early: bool,
+    /// Optional visibility condition (e.g. `day_of_week == "Sat" || tyria_night`), see condition.rs.
+    /// Occurrences where it evaluates to false are hidden and not notified.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub condition: String,
+    /// Compiled `condition` (None when empty or invalid)
+    #[serde(skip)]
+    pub compiled_condition: Option<Condition>,
 }
 
 fn default_true() -> bool { true }
 
+fn is_true(value: &bool) -> bool { *value }
+
+fn is_zero(value: &i64) -> bool { *value == 0 }
+
+fn localized_name<'a>(name: &'a str, localized: &'a HashMap<String, String>, language: EventLanguage) -> &'a str {
+    language
+        .code()
+        .and_then(|code| localized.get(code))
+        .filter(|translated| !translated.is_empty())
+        .map_or(name, String::as_str)
+}
+
+impl TimelineEvent {
+    /// Name to show for `language`, falling back to the English name
+    pub fn display_name(&self, language: EventLanguage) -> &str {
+        localized_name(&self.name, &self.name_localized, language)
+    }
+
+    /// Start offsets of every spawn within one cycle
+    pub fn cycle_offsets(&self) -> &[i64] {
+        if self.spawn_offsets.is_empty() {
+            std::slice::from_ref(&self.start_offset)
+        } else {
+            &self.spawn_offsets
+        }
+    }
+
+    /// All copyable strings: the main copy_text first (as "Waypoint"), then any extra actions
+    pub fn copy_options(&self) -> Vec<CopyAction> {
+        let mut options = Vec::new();
+        if !self.copy_text.is_empty() {
+            options.push(CopyAction { label: "Waypoint".to_string(), text: self.copy_text.clone() });
+        }
+        options.extend(self.copy_actions.iter().filter(|a| !a.text.is_empty()).cloned());
+        options
+    }
+
+    /// Expansion required by this event, falling back to the track's (empty for core content)
+    pub fn required_expansion<'a>(&'a self, track: &'a EventTrack) -> &'a str {
+        if self.expansion.is_empty() { &track.expansion } else { &self.expansion }
+    }
+
+    /// Compile the recurrence expression against a track's base time.
+    /// Clears any previous compilation when the expression is empty.
+    pub fn compile_recurrence(&mut self, base_time: i64) -> Result<(), String> {
+        if self.recurrence.trim().is_empty() {
+            self.spawn_offsets.clear();
+            return Ok(());
+        }
+
+        let recurrence = crate::recurrence::parse_recurrence(&self.recurrence)?;
+        let offsets = recurrence.cycle_offsets(base_time, crate::time_utils::local_utc_offset_seconds());
+        self.cycle_duration = recurrence.period;
+        self.start_offset = offsets[0];
+        self.spawn_offsets = offsets;
+        Ok(())
+    }
+
+    /// Compile the visibility condition. An invalid condition is ignored (the event always shows).
+    pub fn compile_condition(&mut self) -> Result<(), String> {
+        self.compiled_condition = None;
+        if self.condition.trim().is_empty() {
+            return Ok(());
+        }
+        self.compiled_condition = Some(crate::condition::parse_condition(&self.condition)?);
+        Ok(())
+    }
+
+    /// Whether the occurrence starting at `start` passes the event's condition
+    pub fn occurs_at(&self, start: i64) -> bool {
+        self.compiled_condition.as_ref().is_none_or(|c| c.evaluate(start))
+    }
+}
+
 impl Default for TimelineEvent {
     fn default() -> Self {
         Self {
             name: "New Event".to_string(),
+            id: String::new(),
+            name_localized: HashMap::new(),
+            aliases: Vec::new(),
             start_offset: 0,
             duration: 300,
             cycle_duration: 7200,
             color: EventColor::default(),
             copy_text: String::new(),
+            copy_actions: Vec::new(),
             enabled: true,
+            show_on_timeline: true,
+            notify: true,
+            recurrence: String::new(),
+            spawn_offsets: Vec::new(),
+            chain: String::new(),
+            pre_duration: 0,
+            description: String::new(),
+            map_name: String::new(),
+            rewards: String::new(),
+            expansion: String::new(),
+            fills_early: false,
+            condition: String::new(),
+            compiled_condition: None,
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct EventTrack {
     pub name: String,
+    /// Stable identifier from the pack (optional); settings follow it when `name` changes
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    /// Translated names by language code ("de", "fr", "es"); `name` stays the identifier
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub name_localized: HashMap<String, String>,
     pub timeline_type: TimelineType,
     pub events: Vec<TimelineEvent>,
     pub base_time: i64,
@@ -80,43 +251,204 @@ pub struct EventTrack {
     pub height: f32,
     #[serde(default)]
     pub category: String,
+    /// Calculator used to derive base_time ("tyria_cycle", "local_day_start", ...).
+    /// "custom" (or empty, for older configs) keeps base_time as a fixed epoch.
+    #[serde(default)]
+    pub base_time_calculator: String,
+    /// Expansion id required by every event on this track (see EXPANSIONS)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub expansion: String,
+    /// Game map ids this track's events take place on (used to auto-solo the current map)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub map_ids: Vec<u32>,
+    /// Image shown before the name in the label column (absolute, or relative to the addon folder)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub icon: String,
+    /// Track events that data updates add to this track
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_track_new: bool,
+    /// Default reminder lead times in minutes for this track's events (e.g. 10 for world bosses)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reminder_minutes: Vec<u32>,
 }
 
 fn default_height() -> f32 { 40.0 }
 
+impl EventTrack {
+    /// Key for remembering this track across renames: its id, or its name when it has none
+    pub fn stable_key(&self) -> &str {
+        if self.id.is_empty() { &self.name } else { &self.id }
+    }
+
+    /// Name to show for `language`, falling back to the English name
+    pub fn display_name(&self, language: EventLanguage) -> &str {
+        localized_name(&self.name, &self.name_localized, language)
+    }
+
+    /// Recompute base_time from the track's calculator (no-op for custom epochs)
+    pub fn refresh_base_time(&mut self) {
+        if is_known_calculator(&self.base_time_calculator) {
+            self.base_time = get_base_time_from_calculator(&self.base_time_calculator);
+        }
+    }
+
+    /// Enabled events running at `time`, with the start time of the running occurrence
+    pub fn occurrences_active_at(&self, time: i64) -> Vec<(&TimelineEvent, i64)> {
+        self.events
+            .iter()
+            .filter(|e| e.enabled)
+            .filter_map(|event| timeline::active_occurrence(event, self.base_time, time).map(|start| (event, start)))
+            .collect()
+    }
+
+    /// Every occurrence of the track's enabled events starting in [from, to), sorted by start time
+    pub fn occurrences_between(&self, from: i64, to: i64) -> Vec<(&TimelineEvent, i64)> {
+        let mut occurrences: Vec<_> = self.events
+            .iter()
+            .filter(|e| e.enabled)
+            .flat_map(|event| timeline::next_occurrences(event, self.base_time, from, to).map(move |start| (event, start)))
+            .collect();
+        occurrences.sort_by_key(|&(_, start)| start);
+        occurrences
+    }
+}
+
 impl Default for EventTrack {
     fn default() -> Self {
         Self {
             name: "New Track".to_string(),
+            id: String::new(),
+            name_localized: HashMap::new(),
             timeline_type: TimelineType::GameTime,
             events: Vec::new(),
             base_time: 0,
             visible: true,
             height: 40.0,
             category: String::new(),
+            base_time_calculator: "custom".to_string(),
+            expansion: String::new(),
+            map_ids: Vec::new(),
+            icon: String::new(),
+            auto_track_new: false,
+            reminder_minutes: Vec::new(),
         }
     }
 }
 
 // === JSON File Structures ===
 
+/// Schedule times are in minutes; fractional minutes (12.5) or "mm:ss" strings ("12:30")
+/// allow seconds precision
 #[derive(Deserialize, Debug)]
 struct JsonSchedule {
     name: String,
-    offset: i32,
     #[serde(default)]
-    interval: i32,
-    duration: i32,
+    id: String,
+    #[serde(default)]
+    name_localized: HashMap<String, String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(deserialize_with = "deserialize_minutes")]
+    offset: i64,
+    #[serde(default, deserialize_with = "deserialize_minutes")]
+    interval: i64,
+    #[serde(deserialize_with = "deserialize_minutes")]
+    duration: i64,
+    #[serde(default, deserialize_with = "deserialize_minutes")]
+    pre_duration: i64,
+    color: [f32; 4],
+    #[serde(default)]
+    copy_text: String,
+    #[serde(default)]
+    copy_actions: Vec<CopyAction>,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    map_name: String,
+    #[serde(default)]
+    rewards: String,
+    #[serde(default)]
+    expansion: String,
+    #[serde(default)]
+    fills_early: bool,
+}
+
+/// One phase of a meta chain (pre-event, boss, loot window, ...)
+#[derive(Deserialize, Debug)]
+struct JsonChainPhase {
+    name: String,
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    name_localized: HashMap<String, String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(deserialize_with = "deserialize_minutes")]
+    duration: i64,
     color: [f32; 4],
     #[serde(default)]
     copy_text: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    rewards: String,
+    #[serde(default)]
+    fills_early: bool,
+}
+
+/// An ordered sequence of phases, laid out back to back from `offset`
+#[derive(Deserialize, Debug)]
+struct JsonChain {
+    name: String,
+    #[serde(deserialize_with = "deserialize_minutes")]
+    offset: i64,
+    #[serde(default, deserialize_with = "deserialize_minutes")]
+    interval: i64,
+    #[serde(default)]
+    copy_text: String,
+    #[serde(default)]
+    map_name: String,
+    #[serde(default)]
+    description: String,
+    phases: Vec<JsonChainPhase>,
+}
+
+/// Deserialize a minutes value (number or "mm:ss" string) into seconds
+fn deserialize_minutes<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Minutes {
+        Number(f64),
+        Text(String),
+    }
+
+    match Minutes::deserialize(deserializer)? {
+        Minutes::Number(minutes) => Ok((minutes * 60.0).round() as i64),
+        Minutes::Text(text) => crate::time_utils::parse_mmss(&text)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid time '{}', expected mm:ss", text))),
+    }
 }
 
 #[derive(Deserialize, Debug)]
 struct JsonTrack {
     name: String,
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    name_localized: HashMap<String, String>,
     timeline_type: TimelineType,
     base_time_calculator: String,
+    #[serde(default)]
+    expansion: String,
+    #[serde(default)]
+    map_ids: Vec<u32>,
+    #[serde(default)]
+    icon: String,
+    #[serde(default)]
+    reminder_minutes: Vec<u32>,
     #[serde(default = "default_true")]
     visible: bool,
     #[serde(default = "default_height")]
@@ -124,6 +456,8 @@ struct JsonTrack {
     #[serde(default)]
     schedules: Vec<JsonSchedule>,
     #[serde(default)]
+    chains: Vec<JsonChain>,
+    #[serde(default)]
     events: Vec<TimelineEvent>,
 }
 
@@ -137,11 +471,155 @@ struct JsonCategory {
 #[derive(Deserialize, Debug)]
 struct JsonRoot {
     version: String,
+    /// Pack layout version (see SCHEMA_VERSION); packs from before it was added are 1
+    #[serde(default = "default_schema_version")]
+    schema_version: u32,
     #[serde(default)]
     hash: String,
+    /// Oldest addon version that understands everything in the pack
+    #[serde(default)]
+    min_addon_version: String,
     categories: Vec<JsonCategory>,
 }
 
+fn default_schema_version() -> u32 { 1 }
+
+// Fields this version reads at each level of a pack. Anything else comes from a newer schema
+// (or is a typo) and is skipped by the parser, see unknown_fields.
+const ROOT_FIELDS: &[&str] = &["version", "schema_version", "hash", "min_addon_version", "categories"];
+const CATEGORY_FIELDS: &[&str] = &["name", "tracks"];
+const TRACK_FIELDS: &[&str] = &[
+    "name", "id", "name_localized", "timeline_type", "base_time_calculator", "expansion", "map_ids", "icon",
+    "reminder_minutes", "visible", "height", "schedules", "chains", "events",
+];
+const EVENT_FIELDS: &[&str] = &[
+    "name", "id", "name_localized", "aliases", "start_offset", "duration", "cycle_duration", "color", "copy_text",
+    "copy_actions", "enabled", "show_on_timeline", "notify", "recurrence", "chain", "pre_duration", "description",
+    "map_name", "rewards", "expansion", "fills_early", "condition",
+];
+const SCHEDULE_FIELDS: &[&str] = &[
+    "name", "id", "name_localized", "aliases", "offset", "interval", "duration", "pre_duration", "color", "copy_text",
+    "copy_actions", "description", "map_name", "rewards", "expansion", "fills_early",
+];
+const CHAIN_FIELDS: &[&str] = &["name", "offset", "interval", "copy_text", "map_name", "description", "phases"];
+const PHASE_FIELDS: &[&str] = &[
+    "name", "id", "name_localized", "aliases", "duration", "color", "copy_text", "description", "rewards", "fills_early",
+];
+
+/// Only the pack's version requirement, readable even when the rest uses a newer schema
+#[derive(Deserialize)]
+struct PackRequirements {
+    #[serde(default)]
+    min_addon_version: String,
+}
+
+// === Pack Export ===
+
+#[derive(Serialize)]
+struct PackRoot<'a> {
+    version: String,
+    schema_version: u32,
+    hash: String,
+    categories: Vec<PackCategory<'a>>,
+}
+
+#[derive(Serialize)]
+struct PackCategory<'a> {
+    name: &'a str,
+    tracks: Vec<PackTrack<'a>>,
+}
+
+/// Written in the same shape JsonTrack reads, with every schedule and chain already
+/// expanded into plain events
+#[derive(Serialize)]
+struct PackTrack<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    id: &'a str,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    name_localized: &'a HashMap<String, String>,
+    timeline_type: TimelineType,
+    base_time_calculator: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    expansion: &'a str,
+    #[serde(skip_serializing_if = "<[u32]>::is_empty")]
+    map_ids: &'a [u32],
+    #[serde(skip_serializing_if = "str::is_empty")]
+    icon: &'a str,
+    #[serde(skip_serializing_if = "<[u32]>::is_empty")]
+    reminder_minutes: &'a [u32],
+    visible: bool,
+    height: f32,
+    events: Vec<TimelineEvent>,
+}
+
+/// Serialize tracks as an event_tracks.json pack, categories in `category_order` first.
+/// Tracks with a custom epoch are rebased onto local_day_start, since packs can't carry one.
+pub fn export_pack_json(tracks: &[EventTrack], category_order: &[String]) -> Result<String, String> {
+    let mut category_names: Vec<&str> = category_order.iter().map(String::as_str).collect();
+    for track in tracks {
+        if !category_names.contains(&track.category.as_str()) {
+            category_names.push(&track.category);
+        }
+    }
+
+    let categories = category_names
+        .into_iter()
+        .map(|name| PackCategory {
+            name,
+            tracks: tracks.iter().filter(|t| t.category == name).map(pack_track).collect(),
+        })
+        .filter(|c| !c.tracks.is_empty())
+        .collect();
+
+    let mut root = PackRoot {
+        version: chrono::Local::now().format("%Y.%m.%d").to_string(),
+        schema_version: SCHEMA_VERSION,
+        hash: String::new(),
+        categories,
+    };
+    // Hashed as it reads back from the file, so numbers round the same way they will on check
+    let unhashed = serde_json::to_string_pretty(&root).map_err(|e| format!("Failed to serialize pack: {}", e))?;
+    let written: Value = serde_json::from_str(&unhashed).map_err(|e| format!("Failed to serialize pack: {}", e))?;
+    root.hash = pack_hash(&written);
+    serde_json::to_string_pretty(&root).map_err(|e| format!("Failed to serialize pack: {}", e))
+}
+
+fn pack_track(track: &EventTrack) -> PackTrack<'_> {
+    let (calculator, shift) = if is_known_calculator(&track.base_time_calculator) {
+        (track.base_time_calculator.as_str(), 0)
+    } else {
+        ("local_day_start", track.base_time - calculate_local_day_start_time())
+    };
+
+    let events = track
+        .events
+        .iter()
+        .map(|event| {
+            let mut event = event.clone();
+            if shift != 0 && event.cycle_duration > 0 {
+                event.start_offset = (event.start_offset + shift).rem_euclid(event.cycle_duration);
+            }
+            event
+        })
+        .collect();
+
+    PackTrack {
+        name: &track.name,
+        id: &track.id,
+        name_localized: &track.name_localized,
+        timeline_type: track.timeline_type,
+        base_time_calculator: calculator,
+        expansion: &track.expansion,
+        map_ids: &track.map_ids,
+        icon: &track.icon,
+        reminder_minutes: &track.reminder_minutes,
+        visible: track.visible,
+        height: track.height,
+        events,
+    }
+}
+
 // === Time Calculators ===
 
 fn calculate_tyria_base_time() -> i64 {
@@ -180,13 +658,47 @@ fn calculate_local_day_start_time() -> i64 {
     current_utc_timestamp - seconds_since_local_midnight
 }
 
-fn get_base_time_from_calculator(calculator: &str) -> i64 {
+/// Paid content that events can require: (id, display label)
+pub const EXPANSIONS: [(&str, &str); 9] = [
+    ("hot", "Heart of Thorns"),
+    ("ls3", "Living World Season 3"),
+    ("pof", "Path of Fire"),
+    ("ls4", "Living World Season 4"),
+    ("ibs", "The Icebrood Saga"),
+    ("eod", "End of Dragons"),
+    ("soto", "Secrets of the Obscure"),
+    ("jw", "Janthir Wilds"),
+    ("voe", "Visions of Eternity"),
+];
+
+/// Selectable base time calculators: (id, display label)
+pub const BASE_TIME_CALCULATORS: [(&str, &str); 4] = [
+    ("tyria_cycle", "Tyria Cycle (2h)"),
+    ("cantha_cycle", "Cantha Cycle (2h)"),
+    ("local_day_start", "Local Day Start (24h)"),
+    ("custom", "Custom Epoch"),
+];
+
+pub fn is_known_calculator(calculator: &str) -> bool {
+    matches!(calculator, "tyria_cycle" | "cantha_cycle" | "local_day_start")
+}
+
+/// Default cycle length for a calculator, in minutes
+pub fn cycle_minutes_for_calculator(calculator: &str) -> i32 {
+    match calculator {
+        "tyria_cycle" | "cantha_cycle" => 2 * 60,  // 2 hours
+        "local_day_start" => 24 * 60,              // 24 hours
+        _ => 24 * 60,
+    }
+}
+
+pub fn get_base_time_from_calculator(calculator: &str) -> i64 {
     match calculator {
         "tyria_cycle" => calculate_tyria_base_time(),
         "cantha_cycle" => calculate_cantha_base_time(),
         "local_day_start" => calculate_local_day_start_time(),
         _ => {
-            eprintln!("Unknown base_time_calculator: {}, using local_day_start", calculator);
+            addon_log::warn(format!("Unknown base_time_calculator: {}, using local_day_start", calculator));
             calculate_local_day_start_time()
         }
     }
@@ -194,41 +706,285 @@ fn get_base_time_from_calculator(calculator: &str) -> i64 {
 
 // === Event Expansion ===
 
-fn expand_schedule(schedule: &JsonSchedule, cycle_minutes: i32) -> Vec<TimelineEvent> {
+fn expand_schedule(schedule: &JsonSchedule, cycle_seconds: i64) -> Vec<TimelineEvent> {
     if schedule.interval == 0 {
         // Single event, no repetition
         return vec![TimelineEvent {
             name: schedule.name.clone(),
-            start_offset: (schedule.offset * 60) as i64,
-            duration: (schedule.duration * 60) as i64,
-            cycle_duration: (cycle_minutes * 60) as i64,
+            id: schedule.id.clone(),
+            name_localized: schedule.name_localized.clone(),
+            aliases: schedule.aliases.clone(),
+            start_offset: schedule.offset,
+            duration: schedule.duration,
+            cycle_duration: cycle_seconds,
             color: EventColor::from_array(schedule.color),
             copy_text: schedule.copy_text.clone(),
+            copy_actions: schedule.copy_actions.clone(),
             enabled: true,
+            show_on_timeline: true,
+            notify: true,
+            pre_duration: schedule.pre_duration,
+            description: schedule.description.clone(),
+            map_name: schedule.map_name.clone(),
+            rewards: schedule.rewards.clone(),
+            expansion: schedule.expansion.clone(),
+            fills_early: schedule.fills_early,
+            ..Default::default()
         }];
     }
     
     // Repeating event
-    let repetitions = cycle_minutes / schedule.interval;
+    let repetitions = cycle_seconds / schedule.interval;
     (0..repetitions)
         .map(|i| {
             let spawn_time = schedule.offset + i * schedule.interval;
             TimelineEvent {
                 name: schedule.name.clone(),
-                start_offset: (spawn_time * 60) as i64,
-                duration: (schedule.duration * 60) as i64,
-                cycle_duration: (cycle_minutes * 60) as i64,
+                id: schedule.id.clone(),
+                name_localized: schedule.name_localized.clone(),
+                aliases: schedule.aliases.clone(),
+                start_offset: spawn_time,
+                duration: schedule.duration,
+                cycle_duration: cycle_seconds,
                 color: EventColor::from_array(schedule.color),
                 copy_text: schedule.copy_text.clone(),
+                copy_actions: schedule.copy_actions.clone(),
                 enabled: true,
+                show_on_timeline: true,
+                notify: true,
+                pre_duration: schedule.pre_duration,
+                description: schedule.description.clone(),
+                map_name: schedule.map_name.clone(),
+                rewards: schedule.rewards.clone(),
+                expansion: schedule.expansion.clone(),
+                fills_early: schedule.fills_early,
+                ..Default::default()
             }
         })
         .collect()
 }
 
+fn expand_chain(chain: &JsonChain, cycle_seconds: i64) -> Vec<TimelineEvent> {
+    let repetitions = if chain.interval > 0 { cycle_seconds / chain.interval } else { 1 };
+
+    let mut events = Vec::new();
+    for i in 0..repetitions {
+        // Each phase starts where the previous one ends
+        let mut phase_start = chain.offset + i * chain.interval;
+        for phase in &chain.phases {
+            let copy_text = if phase.copy_text.is_empty() { &chain.copy_text } else { &phase.copy_text };
+            let description = if phase.description.is_empty() { &chain.description } else { &phase.description };
+            events.push(TimelineEvent {
+                name: phase.name.clone(),
+                id: phase.id.clone(),
+                name_localized: phase.name_localized.clone(),
+                aliases: phase.aliases.clone(),
+                start_offset: phase_start,
+                duration: phase.duration,
+                cycle_duration: cycle_seconds,
+                color: EventColor::from_array(phase.color),
+                copy_text: copy_text.clone(),
+                enabled: true,
+                show_on_timeline: true,
+                notify: true,
+                chain: chain.name.clone(),
+                description: description.clone(),
+                map_name: chain.map_name.clone(),
+                rewards: phase.rewards.clone(),
+                fills_early: phase.fills_early,
+                ..Default::default()
+            });
+            phase_start += phase.duration;
+        }
+    }
+    events
+}
+
 // === JSON Loading ===
 
-fn get_json_path() -> Option<PathBuf> {
+/// Why event_tracks.json couldn't be used, for display in the UI
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    /// Tracks came from the newest valid backup instead
+    pub using_backup: bool,
+    /// The pack needs this addon version or newer; the built-in tracks are used instead
+    pub requires_version: Option<String>,
+}
+
+/// Error from the most recent load_tracks_from_json call (None when it parsed)
+static LOAD_ERROR: Lazy<Mutex<Option<LoadError>>> = Lazy::new(|| Mutex::new(None));
+
+/// What the loaded event_tracks.json says about itself
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PackInfo {
+    pub version: String,
+    pub schema_version: u32,
+    /// Fields this version doesn't know and skipped, as "track.layers" (each once)
+    pub skipped_fields: Vec<String>,
+}
+
+/// Pack behind the current default tracks (None when it failed to load)
+static PACK_INFO: Lazy<Mutex<Option<PackInfo>>> = Lazy::new(|| Mutex::new(None));
+
+/// Why the last downloaded update was not installed
+static REJECTED_UPDATE: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+pub fn load_error() -> Option<LoadError> {
+    LOAD_ERROR.lock().clone()
+}
+
+pub fn pack_info() -> Option<PackInfo> {
+    PACK_INFO.lock().clone()
+}
+
+pub fn rejected_update() -> Option<String> {
+    REJECTED_UPDATE.lock().clone()
+}
+
+pub fn set_rejected_update(reason: Option<String>) {
+    *REJECTED_UPDATE.lock() = reason;
+}
+
+/// "1.2.10" as [1, 2, 10], for comparing versions
+pub fn parse_version(version: &str) -> Vec<u32> {
+    version.split('.').map(|part| part.trim().parse().unwrap_or(0)).collect()
+}
+
+/// Addon version a pack asks for, if it's newer than this one
+pub fn required_addon_version(content: &str) -> Option<String> {
+    let required = serde_json::from_str::<PackRequirements>(content).ok()?.min_addon_version;
+    let required = required.trim();
+    (!required.is_empty() && parse_version(required) > parse_version(ADDON_VERSION)).then(|| required.to_string())
+}
+
+/// Integrity hash of a pack: SHA-256 (hex) of the whole document minus the hash field, as compact
+/// JSON with sorted keys so formatting doesn't change it. scripts/pack_hash.py computes the same.
+///
+/// It only catches corrupted or truncated downloads: anyone editing a pack can recompute it.
+pub fn pack_hash(root: &Value) -> String {
+    let mut unhashed = root.clone();
+    if let Value::Object(map) = &mut unhashed {
+        map.remove("hash");
+    }
+    let mut canonical = String::new();
+    write_canonical(&unhashed, &mut canonical);
+    Sha256::digest(canonical.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Compact JSON with object keys sorted, whatever order the map keeps them in
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(item, out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// Check a downloaded pack against the hash it declares. Ok(false) when it declares none.
+pub fn verify_pack_hash(content: &str) -> Result<bool, String> {
+    let root: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let declared = root["hash"].as_str().unwrap_or_default().trim().to_ascii_lowercase();
+    if declared.is_empty() {
+        return Ok(false);
+    }
+    let actual = pack_hash(&root);
+    if declared != actual {
+        return Err(format!("Content doesn't match its hash (expected {}, got {}), the download is corrupted or incomplete", declared, actual));
+    }
+    Ok(true)
+}
+
+fn read_pack_info(content: &str) -> PackInfo {
+    let root = serde_json::from_str::<Value>(content).unwrap_or_default();
+    PackInfo {
+        version: root["version"].as_str().unwrap_or_default().to_string(),
+        schema_version: root["schema_version"].as_u64().map_or(1, |v| v as u32),
+        skipped_fields: unknown_fields(&root),
+    }
+}
+
+/// Fields of a pack outside the ones listed for their level (ROOT_FIELDS, TRACK_FIELDS, ...)
+fn unknown_fields(root: &Value) -> Vec<String> {
+    fn items<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+        value.get(key).and_then(Value::as_array).into_iter().flatten()
+    }
+    fn check(value: &Value, level: &str, known: &[&str], unknown: &mut BTreeSet<String>) {
+        if let Some(object) = value.as_object() {
+            for key in object.keys().filter(|key| !known.contains(&key.as_str())) {
+                unknown.insert(format!("{}.{}", level, key));
+            }
+        }
+    }
+
+    let mut unknown = BTreeSet::new();
+    check(root, "pack", ROOT_FIELDS, &mut unknown);
+    for category in items(root, "categories") {
+        check(category, "category", CATEGORY_FIELDS, &mut unknown);
+        for track in items(category, "tracks") {
+            check(track, "track", TRACK_FIELDS, &mut unknown);
+            for event in items(track, "events") {
+                check(event, "event", EVENT_FIELDS, &mut unknown);
+            }
+            for schedule in items(track, "schedules") {
+                check(schedule, "schedule", SCHEDULE_FIELDS, &mut unknown);
+            }
+            for chain in items(track, "chains") {
+                check(chain, "chain", CHAIN_FIELDS, &mut unknown);
+                for phase in items(chain, "phases") {
+                    check(phase, "phase", PHASE_FIELDS, &mut unknown);
+                }
+            }
+        }
+    }
+    unknown.into_iter().collect()
+}
+
+/// Remember the pack that was loaded, logging what it uses that this version can't read
+fn set_pack_info(info: Option<PackInfo>) {
+    let mut current = PACK_INFO.lock();
+    if *current == info {
+        return;
+    }
+    if let Some(info) = &info {
+        if info.schema_version > SCHEMA_VERSION {
+            addon_log::warn(format!(
+                "event_tracks.json uses schema {} (this version reads {}), newer features are skipped",
+                info.schema_version, SCHEMA_VERSION
+            ));
+        }
+        if !info.skipped_fields.is_empty() {
+            addon_log::warn(format!("Skipped unknown event_tracks.json fields: {}", info.skipped_fields.join(", ")));
+        }
+    }
+    *current = info;
+}
+
+pub fn get_json_path() -> Option<PathBuf> {
     get_addon_dir("event_timers").map(|p| p.join("event_tracks.json"))
 }
 
@@ -260,50 +1016,223 @@ fn load_json_content() -> String {
     EMBEDDED_JSON.to_string()
 }
 
-pub fn load_tracks_from_json() -> (Vec<EventTrack>, Vec<String>) {
+/// Modification time of event_tracks.json and the local UTC offset a load was made with
+type LoadKey = (Option<SystemTime>, i64);
+type LoadedTracks = (Vec<EventTrack>, Vec<String>);
+
+/// Result of the last load, reused until the file changes (the settings re-read it every frame)
+static LOADED_TRACKS: Lazy<Mutex<Option<(LoadKey, LoadedTracks)>>> = Lazy::new(|| Mutex::new(None));
+
+/// Tracks and categories from event_tracks.json, parsed again only when the file changes or
+/// the UTC offset the recurrences were compiled against does
+pub fn load_tracks_from_json() -> LoadedTracks {
+    extract_embedded_json();
+    let modified = get_json_path().and_then(|path| fs::metadata(path).ok()).and_then(|meta| meta.modified().ok());
+    let key = (modified, crate::time_utils::local_utc_offset_seconds());
+
+    let mut cached = LOADED_TRACKS.lock();
+    if let Some((cached_key, loaded)) = cached.as_ref() {
+        if *cached_key == key {
+            return loaded.clone();
+        }
+    }
+    let loaded = read_tracks();
+    *cached = Some((key, loaded.clone()));
+    loaded
+}
+
+fn read_tracks() -> LoadedTracks {
     let json_content = load_json_content();
-    
-    match serde_json::from_str::<JsonRoot>(&json_content) {
-        Ok(root) => {
-            let mut all_tracks = Vec::new();
-            let mut category_names = Vec::new();
-            
-            for category in root.categories {
-                category_names.push(category.name.clone());
-                
-                for json_track in category.tracks {
-                    let base_time = get_base_time_from_calculator(&json_track.base_time_calculator);
-                    
-                    let mut events = json_track.events;
-                    
-                    // Expand schedules into events
-                    for schedule in &json_track.schedules {
-                        let cycle_minutes = match json_track.base_time_calculator.as_str() {
-                            "tyria_cycle" | "cantha_cycle" => 2 * 60,  // 2 hours
-                            "local_day_start" => 24 * 60,              // 24 hours
-                            _ => 24 * 60,
-                        };
-                        events.extend(expand_schedule(schedule, cycle_minutes));
-                    }
-                    
-                    all_tracks.push(EventTrack {
-                        name: json_track.name,
-                        timeline_type: json_track.timeline_type,
-                        events,
-                        base_time,
-                        visible: json_track.visible,
-                        height: json_track.height,
-                        category: category.name.clone(),
-                    });
+
+    // A pack made for a newer addon could parse into something subtly wrong; use the tracks
+    // this version shipped with instead
+    if let Some(required) = required_addon_version(&json_content) {
+        let error = LoadError {
+            message: format!("It needs Event Timers {} or newer (this is {}), update the addon to use it", required, ADDON_VERSION),
+            line: 0,
+            column: 0,
+            using_backup: false,
+            requires_version: Some(required.clone()),
+        };
+        let mut last_error = LOAD_ERROR.lock();
+        if last_error.as_ref() != Some(&error) {
+            addon_log::warn(format!("event_tracks.json needs addon version {}, using the built-in tracks", required));
+            *last_error = Some(error);
+        }
+        set_pack_info(Some(read_pack_info(EMBEDDED_JSON)));
+        return parse_tracks(EMBEDDED_JSON).unwrap_or_default();
+    }
+
+    match parse_tracks(&json_content) {
+        Ok(loaded) => {
+            *LOAD_ERROR.lock() = None;
+            set_pack_info(Some(read_pack_info(&json_content)));
+            loaded
+        }
+        Err(e) => {
+            // Keep the newest good copy (saved before each update) rather than dropping every track.
+            // Resolved once per change of the file, the result is cached with it
+            let backup = list_backups()
+                .into_iter()
+                .filter(|b| b.kind == BackupKind::TrackData)
+                .filter_map(|b| fs::read_to_string(&b.path).ok())
+                .find_map(|content| parse_tracks(&content).ok());
+
+            let error = LoadError {
+                message: e.to_string(),
+                line: e.line(),
+                column: e.column(),
+                using_backup: backup.is_some(),
+                requires_version: None,
+            };
+            set_pack_info(None);
+            // This runs again whenever defaults are re-read, only report a new failure once
+            let mut last_error = LOAD_ERROR.lock();
+            if last_error.as_ref() != Some(&error) {
+                addon_log::error(format!("Failed to parse event_tracks.json: {}", e));
+                if error.using_backup {
+                    addon_log::warn("Using the newest working backup of event_tracks.json");
+                } else {
+                    addon_log::error("Using empty track list");
                 }
+                *last_error = Some(error);
             }
-            
-            (all_tracks, category_names)
+            backup.unwrap_or_default()
         }
-        Err(e) => {
-            eprintln!("Failed to parse event_tracks.json: {}", e);
-            eprintln!("Using empty track list");
-            (Vec::new(), Vec::new())
+    }
+}
+
+/// Check downloaded content before it replaces the local file
+pub fn validate_tracks_json(content: &str) -> Result<(), serde_json::Error> {
+    serde_json::from_str::<JsonRoot>(content).map(|_| ())
+}
+
+/// Check a single track object (as found in a category's "tracks" list)
+pub fn validate_track_json(content: &str) -> Result<(), serde_json::Error> {
+    serde_json::from_str::<JsonTrack>(content).map(|_| ())
+}
+
+fn parse_tracks(content: &str) -> Result<(Vec<EventTrack>, Vec<String>), serde_json::Error> {
+    let root = serde_json::from_str::<JsonRoot>(content)?;
+    let mut all_tracks = Vec::new();
+    let mut category_names = Vec::new();
+
+    for category in root.categories {
+        category_names.push(category.name.clone());
+
+        for json_track in category.tracks {
+            let base_time = get_base_time_from_calculator(&json_track.base_time_calculator);
+
+            let mut events = json_track.events;
+
+            // Expand schedules into events
+            for schedule in &json_track.schedules {
+                let cycle_minutes = cycle_minutes_for_calculator(&json_track.base_time_calculator);
+                events.extend(expand_schedule(schedule, cycle_minutes as i64 * 60));
+            }
+
+            // Expand meta chains into consecutive phase events
+            for chain in &json_track.chains {
+                let cycle_minutes = cycle_minutes_for_calculator(&json_track.base_time_calculator);
+                events.extend(expand_chain(chain, cycle_minutes as i64 * 60));
+            }
+
+            for event in &mut events {
+                if let Err(e) = event.compile_recurrence(base_time) {
+                    addon_log::warn(format!("Invalid recurrence for event '{}': {}", event.name, e));
+                }
+                if let Err(e) = event.compile_condition() {
+                    addon_log::warn(format!("Invalid condition for event '{}': {}", event.name, e));
+                }
+            }
+
+            all_tracks.push(EventTrack {
+                name: json_track.name,
+                id: json_track.id,
+                name_localized: json_track.name_localized,
+                timeline_type: json_track.timeline_type,
+                events,
+                base_time,
+                visible: json_track.visible,
+                height: json_track.height,
+                category: category.name.clone(),
+                base_time_calculator: json_track.base_time_calculator,
+                expansion: json_track.expansion,
+                map_ids: json_track.map_ids,
+                icon: json_track.icon,
+                auto_track_new: false,
+                reminder_minutes: json_track.reminder_minutes,
+            });
         }
     }
-}
\ No newline at end of file
+
+    Ok((all_tracks, category_names))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACK: &str = r#"{
+  "version": "2025.10.01",
+  "schema_version": 1,
+  "categories": [{ "name": "Core", "tracks": [{ "name": "World Bosses", "base_time": 0 }] }]
+}"#;
+
+    fn with_hash(content: &str, hash: &str) -> String {
+        let mut root: Value = serde_json::from_str(content).unwrap();
+        root["hash"] = Value::String(hash.to_string());
+        serde_json::to_string_pretty(&root).unwrap()
+    }
+
+    #[test]
+    fn canonical_form_sorts_keys_and_drops_whitespace() {
+        let value: Value = serde_json::from_str(r#"{ "b": [1, 2.5, "x"], "a": { "d": null, "c": true } }"#).unwrap();
+        let mut out = String::new();
+        write_canonical(&value, &mut out);
+        assert_eq!(out, r#"{"a":{"c":true,"d":null},"b":[1,2.5,"x"]}"#);
+    }
+
+    #[test]
+    fn hash_ignores_formatting_key_order_and_the_hash_field() {
+        let original: Value = serde_json::from_str(PACK).unwrap();
+        let reordered: Value = serde_json::from_str(
+            r#"{"categories":[{"tracks":[{"base_time":0,"name":"World Bosses"}],"name":"Core"}],"schema_version":1,"version":"2025.10.01","hash":"abc"}"#,
+        )
+        .unwrap();
+        assert_eq!(pack_hash(&original), pack_hash(&reordered));
+    }
+
+    #[test]
+    fn hash_covers_fields_outside_the_categories() {
+        let original: Value = serde_json::from_str(PACK).unwrap();
+        let mut bumped = original.clone();
+        bumped["version"] = Value::String("2025.10.02".to_string());
+        assert_ne!(pack_hash(&original), pack_hash(&bumped));
+    }
+
+    #[test]
+    fn matching_hash_verifies() {
+        let hash = pack_hash(&serde_json::from_str(PACK).unwrap());
+        assert_eq!(verify_pack_hash(&with_hash(PACK, &hash)), Ok(true));
+        assert_eq!(verify_pack_hash(&with_hash(PACK, &hash.to_ascii_uppercase())), Ok(true));
+    }
+
+    #[test]
+    fn mismatched_hash_is_rejected() {
+        let hash = pack_hash(&serde_json::from_str(PACK).unwrap());
+        let edited = with_hash(&PACK.replace("World Bosses", "World Bosses!"), &hash);
+        assert!(verify_pack_hash(&edited).is_err());
+    }
+
+    #[test]
+    fn missing_hash_is_not_verified() {
+        assert_eq!(verify_pack_hash(PACK), Ok(false));
+        assert_eq!(verify_pack_hash(&with_hash(PACK, "  ")), Ok(false));
+    }
+
+    #[test]
+    fn shipped_pack_matches_its_hash() {
+        assert_eq!(verify_pack_hash(EMBEDDED_JSON), Ok(true));
+    }
+}
@@ -3,30 +3,43 @@
 use nexus::{
     gui::{register_render, render, RenderType},
     keybind::register_keybind_with_string,
-    quick_access::add_quick_access,
-    texture::load_texture_from_memory,
     AddonFlags, UpdateProvider,
 };
 use std::ffi::c_char;
 
+mod addon_log;
+mod backups;
+mod category_keybinds;
+mod condition;
 mod config;
+mod diagnostics;
+mod http_server;
+mod icons;
+mod image_export;
 mod json_loader;
+mod mumble;
 mod notification_logic;
 mod notifications;
+mod quick_access;
+mod recurrence;
+mod sound;
+mod stream_export;
 mod time_utils;
+mod timeline;
+mod track_icons;
 mod ui;
+mod watchlist;
+mod webhook;
 
-use config::{load_user_config, save_user_config, RUNTIME_CONFIG};
+use config::{load_user_config, refresh_recurrences, save_user_config, RUNTIME_CONFIG};
 use notification_logic::update_notifications;
+use diagnostics::{measure, Section};
 use ui::{
-    check_for_event_tracks_update, render_main_window, render_settings,
-    render_toast_notifications, render_upcoming_panel,
+    check_for_event_tracks_update, render_agenda_window, render_center_alert, render_diagnostics_overlay, render_log_window,
+    render_main_window, render_settings, render_toast_notifications, render_track_editor_window,
+    render_track_wizard, render_upcoming_panel, render_whats_new_window,
 };
 
-// Embed icon files directly in the binary
-const QA_ICON: &[u8] = include_bytes!("../qa_icon.png");
-const QA_ICON_HOVER: &[u8] = include_bytes!("../qa_icon_hovered.png");
-
 extern "C-unwind" fn toggle_window_keybind(_identifier: *const c_char, is_release: bool) {
     if !is_release {
         let mut config = RUNTIME_CONFIG.lock();
@@ -41,6 +54,26 @@ extern "C-unwind" fn toggle_toasts_keybind(_identifier: *const c_char, is_releas
     }
 }
 
+extern "C-unwind" fn toggle_mute_keybind(_identifier: *const c_char, is_release: bool) {
+    if !is_release {
+        let mut config = RUNTIME_CONFIG.lock();
+        config.notification_config.muted = !config.notification_config.muted;
+    }
+}
+
+extern "C-unwind" fn toggle_snooze_keybind(_identifier: *const c_char, is_release: bool) {
+    if !is_release {
+        let mut config = RUNTIME_CONFIG.lock();
+        let now = time_utils::get_current_unix_time();
+        if config.notification_config.snooze_remaining(now).is_some() {
+            config.notification_config.snoozed_until = None;
+        } else {
+            let minutes = config.notification_config.snooze_keybind_minutes;
+            config.notification_config.snooze(now, minutes);
+        }
+    }
+}
+
 extern "C-unwind" fn toggle_upcoming_panel_keybind(_identifier: *const c_char, is_release: bool) {
     if !is_release {
         let mut config = RUNTIME_CONFIG.lock();
@@ -59,13 +92,17 @@ nexus::export! {
 }
 
 fn load() {
+    backups::auto_backup_user_config();
+    // Before the user config, whose rename migration also moves the notification records
+    notifications::load_dedup_state();
     load_user_config();
+    ui::whats_new::check_version();
     
     // Check for event_tracks.json updates on load
     check_for_event_tracks_update();
     
     // Setup Quick Access icon
-    setup_quick_access();
+    quick_access::setup();
     
     register_keybind_with_string("Toggle Event Timers", toggle_window_keybind, "ALT+E")
         .revert_on_unload();
@@ -75,12 +112,37 @@ fn load() {
 
     register_keybind_with_string("Toggle Upcoming Panel", toggle_upcoming_panel_keybind, "")
         .revert_on_unload();
+
+    register_keybind_with_string("Mute All Notifications", toggle_mute_keybind, "")
+        .revert_on_unload();
+
+    register_keybind_with_string("Snooze Notifications", toggle_snooze_keybind, "")
+        .revert_on_unload();
+
+    category_keybinds::register_all();
     
     register_render(RenderType::Render, render!(|ui| {
-        update_notifications();
-        render_main_window(ui);
-        render_toast_notifications(ui);
-        render_upcoming_panel(ui);
+        quick_access::sync_icon();
+        refresh_recurrences();
+        condition::refresh_map_id();
+        // Loading screens and character select: nothing to show, and times jump when they end
+        let loading = RUNTIME_CONFIG.snapshot().pause_while_loading && mumble::is_loading_screen();
+        measure(Section::Notifications, || update_notifications(loading));
+        if !loading {
+            measure(Section::MainWindow, || render_main_window(ui));
+            measure(Section::Panels, || {
+                render_toast_notifications(ui);
+                render_center_alert(ui);
+                render_upcoming_panel(ui);
+                render_agenda_window(ui);
+                render_track_editor_window(ui);
+                render_track_wizard(ui);
+                render_log_window(ui);
+                render_whats_new_window(ui);
+            });
+        }
+        diagnostics::end_frame();
+        render_diagnostics_overlay(ui);
     }))
     .revert_on_unload();
     
@@ -90,22 +152,8 @@ fn load() {
     .revert_on_unload();
 }
 
-fn setup_quick_access() {
-    // Load textures from embedded bytes
-    load_texture_from_memory("EVENT_TIMERS_QA_ICON", QA_ICON, None);
-    load_texture_from_memory("EVENT_TIMERS_QA_ICON_HOVER", QA_ICON_HOVER, None);
-    
-    // Add quick access button
-    add_quick_access(
-        "EVENT_TIMERS_QA",
-        "EVENT_TIMERS_QA_ICON",
-        "EVENT_TIMERS_QA_ICON_HOVER",
-        "Toggle Event Timers",
-        "Toggle Event Timers Window"
-    )
-    .revert_on_unload();
-}
-
 fn unload() {
+    http_server::stop();
     save_user_config();
+    notifications::save_dedup_state();
 }
\ No newline at end of file
@@ -0,0 +1,47 @@
+// Game state read from the MumbleLink shared memory provided by Nexus
+
+use nexus::data_link::{get_mumble_link, mumble::UiState};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+/// ui_tick stops advancing during loading screens; this long without a tick counts as one
+const STALE_TICK: Duration = Duration::from_secs(1);
+
+/// Last ui_tick seen and when it changed
+static LAST_TICK: Lazy<Mutex<(u32, Instant)>> = Lazy::new(|| Mutex::new((0, Instant::now())));
+
+/// Id of the map the player is currently on, if the game is running and in a map
+pub fn current_map_id() -> Option<u32> {
+    let link = get_mumble_link()?;
+    // ui_tick stays at 0 until the game has written the link at least once
+    if link.ui_tick == 0 || link.context.map_id == 0 {
+        return None;
+    }
+    Some(link.context.map_id)
+}
+
+/// Whether the game window has focus (assumed when the link isn't available)
+pub fn game_has_focus() -> bool {
+    match get_mumble_link() {
+        Some(link) if link.ui_tick != 0 => link.context.ui_state.contains(UiState::GAME_HAS_FOCUS),
+        _ => true,
+    }
+}
+
+/// Whether the game is in a loading screen (the link stopped updating)
+pub fn is_loading_screen() -> bool {
+    let Some(link) = get_mumble_link() else {
+        return false;
+    };
+    if link.ui_tick == 0 {
+        return false;
+    }
+
+    let mut last = LAST_TICK.lock();
+    if last.0 != link.ui_tick {
+        *last = (link.ui_tick, Instant::now());
+        return false;
+    }
+    last.1.elapsed() > STALE_TICK
+}
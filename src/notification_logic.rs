@@ -1,26 +1,43 @@
-use crate::config::{TrackedEventId, RUNTIME_CONFIG};
+use crate::config::{CatchUpPolicy, EventLanguage, EventPriority, ReminderConfig, RuntimeConfig, TrackedEventId, UnownedContentMode, RUNTIME_CONFIG};
+use crate::addon_log;
+use crate::mumble;
+use crate::http_server;
 use crate::json_loader::{EventTrack, TimelineEvent};
-use crate::notifications::{UpcomingEvent, NOTIFICATION_STATE};
-use crate::time_utils::get_current_unix_time;
-
-/// Main update function - call once per frame from render loop
-pub fn update_notifications() {
-    let current_time = get_current_unix_time();
-
-    let (tracked_events, oneshot_events, notification_config, tracks) = {
-        let config = RUNTIME_CONFIG.lock();
-        (
-            config.tracked_events.clone(),
-            config.oneshot_events.clone(),
-            config.notification_config.clone(),
-            config.tracks.clone(),
-        )
-    };
+use crate::notifications::{NotificationState, UpcomingEvent, NOTIFICATION_STATE};
+use crate::sound;
+use crate::stream_export;
+use crate::timeline;
+use crate::time_utils::{get_current_unix_time, get_display_time, is_time_simulated};
+use crate::watchlist;
+use crate::webhook::{self, WebhookFire, WebhookTarget};
+use std::collections::HashMap;
+
+/// Main update function - call once per frame from render loop. `paused` during loading
+/// screens, where only the pause is recorded.
+pub fn update_notifications(paused: bool) {
+    // In planning mode the Upcoming list follows the simulated time, but nothing fires
+    let current_time = get_display_time();
+    let simulated = is_time_simulated();
+
+    // Read-only snapshot: no per-frame clone of the tracks, no waiting on the settings
+    let snapshot = RUNTIME_CONFIG.snapshot();
+    let tracked_events = &snapshot.tracked_events;
+    let oneshot_events = &snapshot.oneshot_events;
+    let notification_config = &snapshot.notification_config;
+    let tracks = &snapshot.tracks;
 
     // Early exit if no tracked events
-    if tracked_events.is_empty() && oneshot_events.is_empty() {
+    if tracked_events.is_empty() && oneshot_events.is_empty() && notification_config.watchlist.is_empty() {
         let mut state = NOTIFICATION_STATE.lock();
+        state.set_paused(paused, current_time);
+        state.end_catch_up(current_time);
+        // Keep the refresh clock running, or the first refresh after tracking an event would
+        // take the idle time for a clock jump and drop its reminders as missed
+        state.set_refresh_time(current_time);
         state.upcoming_events.clear();
+        // The overlay files and the endpoint still have to show that nothing is coming up
+        http_server::sync(snapshot.http_server_enabled, snapshot.http_server_port);
+        export_upcoming(&state, &snapshot, current_time, simulated);
         return;
     }
 
@@ -28,10 +45,18 @@ pub fn update_notifications() {
     let mut oneshot_to_remove: Vec<TrackedEventId> = Vec::new();
 
     let mut state = NOTIFICATION_STATE.lock();
+    if state.set_paused(paused, current_time) {
+        return;
+    }
+
+    // Away from the game: hold new toasts until the player is back
+    let away = notification_config.hold_while_away && (!mumble::game_has_focus() || mumble::is_loading_screen());
+    state.set_holding(away, current_time, notification_config.max_visible_toasts);
 
     // Update toast fade/removal
     state.update_toasts(
         notification_config.toast_duration_seconds,
+        notification_config.toast_duration_seconds * notification_config.high_priority_duration_factor,
         notification_config.max_visible_toasts,
     );
 
@@ -39,34 +64,73 @@ pub fn update_notifications() {
     if !state.needs_refresh(current_time) {
         return;
     }
+    if !simulated {
+        state.detect_clock_jump(current_time);
+    }
     state.set_refresh_time(current_time);
 
-    // Clean up old notification records
-    state.cleanup_old_notifications(current_time);
+    // Clean up old notification records (not against a simulated clock, which would drop live dedup state)
+    if !simulated {
+        state.cleanup_old_notifications(current_time);
+    }
 
     let mut upcoming: Vec<UpcomingEvent> = Vec::new();
-
-    for track in &tracks {
+    let mut localized_names = HashMap::new();
+    let language = snapshot.event_language;
+    // Final-countdown beep for this second, Some(true) when an event spawns right now
+    let mut countdown_beep: Option<bool> = None;
+    let watch_patterns = watchlist::compile_all(&notification_config.watchlist);
+    // Mute and snooze go by the real clock, also in planning mode
+    let silenced = notification_config.is_silenced(get_current_unix_time());
+    let catch_up = notification_config.catch_up_policy;
+    let last_chance_highlight = snapshot.last_chance_highlight;
+    // Map the player is on, for checking in to events there (live time only)
+    let attendance_map = if notification_config.auto_attendance && !simulated { mumble::current_map_id() } else { None };
+    let mut attended: Vec<(TrackedEventId, i64)> = Vec::new();
+
+    // Reminders fired this second, for the webhook
+    let mut webhook_fires: Vec<WebhookFire> = Vec::new();
+
+    for track in tracks {
         if !track.visible {
             continue;
         }
 
+        let track_reminders = if notification_config.use_track_reminder_defaults {
+            track_default_reminders(track, &notification_config.reminders)
+        } else {
+            None
+        };
+        let reminders = track_reminders.as_deref().unwrap_or(&notification_config.reminders);
+
         for event in &track.events {
-            if !event.enabled {
+            if !event.enabled || !event.notify {
                 continue;
             }
 
             let event_id = TrackedEventId::new(&track.name, &event.name);
 
-            // Only process tracked or oneshot events
-            let is_tracked = tracked_events.contains(&event_id);
+            // Only process tracked, oneshot or watchlisted events
+            let is_tracked = tracked_events.contains(&event_id)
+                || watch_patterns.iter().any(|p| p.is_match(&event.name));
             let is_oneshot = oneshot_events.contains(&event_id);
             if !is_tracked && !is_oneshot {
                 continue;
             }
 
+            // Content the player doesn't own is hidden like on the timeline, or only listed
+            let owned = snapshot.owns_expansion(event.required_expansion(track));
+            if !owned && snapshot.unowned_content_mode == UnownedContentMode::Hide {
+                continue;
+            }
+
+            let (event_label, track_label) = (event.display_name(language), track.display_name(language));
+            if event_label != event.name || track_label != track.name {
+                localized_names.insert(event_id.clone(), (event_label.to_string(), track_label.to_string()));
+            }
+
             // Calculate next/current occurrence of this event
-            if let Some((start_time, seconds_until, seconds_into_event, event_duration, cycle_number)) =
+            if let Some((start_time, seconds_until, seconds_into_event, event_duration)) =
                 calculate_event_timing(track, event, current_time)
             {
                 // Add to upcoming events list
@@ -75,20 +139,90 @@ pub fn update_notifications() {
                     start_time,
                     seconds_until,
                     seconds_into: if seconds_into_event >= 0 { seconds_into_event } else { 0 },
+                    duration: event_duration,
                     color: event.color.to_array(),
                     copy_text: event.copy_text.clone(),
+                    copy_actions: event.copy_actions.clone(),
+                    description: event.description.clone(),
+                    map_name: event.map_name.clone(),
+                    rewards: event.rewards.clone(),
+                    category: track.category.clone(),
+                    note: notification_config.event_note(&event_id).unwrap_or_default().to_string(),
+                    unowned: !owned,
                 });
 
+                // On one of the track's maps while it runs: attended
+                if seconds_into_event >= 0
+                    && attendance_map.is_some_and(|map_id| track.map_ids.contains(&map_id))
+                    && !notification_config.attended(&event_id, start_time)
+                {
+                    attended.push((event_id.clone(), start_time));
+                    if notification_config.attendance_mutes_day {
+                        const DAY: i64 = 24 * 3600;
+                        let next_reset = start_time - start_time.rem_euclid(DAY) + DAY;
+                        state.set_occurrence_muted(&event_id, start_time, true);
+                        for later in timeline::next_occurrences(event, track.base_time, start_time + 1, next_reset) {
+                            state.set_occurrence_muted(&event_id, later, true);
+                        }
+                    }
+                }
+
                 // For oneshot events, remove after the event starts
-                if is_oneshot && seconds_into_event >= 0 {
+                if is_oneshot && seconds_into_event >= 0 && !simulated {
                     oneshot_to_remove.push(event_id.clone());
                 }
 
-                // Check each configured reminder
-                if notification_config.toast_enabled {
-                    for reminder in &notification_config.reminders {
-                        let reminder_seconds = (reminder.minutes_before as i64) * 60;
+                // No toasts, alerts, beeps or webhooks for content the player can't join
+                if !owned {
+                    continue;
+                }
 
+                // Low priority events are only listed; high priority ones skip the global toast cooldown
+                let priority = notification_config.priority(&event_id);
+                // Reminders before the start count down to when the player has to set off, earlier
+                // still for metas whose maps fill up ahead of time
+                let mut lead_seconds = notification_config.travel_minutes(&event_id) as i64 * 60;
+                if notification_config.fills_early(&event_id, event) {
+                    lead_seconds += notification_config.fills_early_minutes as i64 * 60;
+                }
+                let cooldown_ok = priority == EventPriority::High || state.can_add_toast(current_time);
+
+                // Center-screen countdown for imminent high priority events (not while away, so it isn't missed)
+                if notification_config.center_alert_enabled
+                    && priority == EventPriority::High
+                    && !silenced
+                    && !simulated
+                    && !away
+                    && seconds_until > 0
+                    && seconds_until <= notification_config.center_alert_lead_seconds as i64
+                    && !state.is_occurrence_muted(&event_id, start_time)
+                {
+                    state.try_center_alert(&event_id, start_time, current_time, notification_config.center_alert_max_per_hour);
+                }
+
+                // One beep per second however many events are counting down
+                if notification_config.upcoming_countdown_beep
+                    && notification_config.upcoming_countdown_seconds > 0
+                    && priority != EventPriority::Low
+                    && !silenced
+                    && !simulated
+                    && !away
+                    && !state.is_occurrence_muted(&event_id, start_time)
+                {
+                    let spawn = seconds_until == 0 && seconds_into_event == 0;
+                    if spawn || (seconds_until > 0 && seconds_until <= notification_config.upcoming_countdown_seconds as i64) {
+                        countdown_beep = Some(countdown_beep.unwrap_or(false) || spawn);
+                    }
+                }
+
+                // Check each configured reminder, for the toasts and the webhook
+                let show_toasts = notification_config.toast_enabled && priority != EventPriority::Low;
+                if (show_toasts || snapshot.webhook_enabled)
+                    && !silenced
+                    && !simulated
+                    && !state.is_occurrence_muted(&event_id, start_time)
+                {
+                    for reminder in reminders {
                         if reminder.minutes_before == 0 {
                             // "During event" reminder - triggers at configurable intervals while event is active
                             // but not on the very last interval
@@ -97,51 +231,114 @@ pub fn update_notifications() {
                                 let remaining_seconds = event_duration - seconds_into_event;
                                 // Don't notify on the last interval
                                 if remaining_seconds > interval_seconds {
+                                    // An interval that passed during a gap isn't announced late (unless
+                                    // everything missed should be shown)
+                                    let interval = seconds_into_event / interval_seconds;
+                                    let interval_at = start_time + interval * interval_seconds;
+                                    if catch_up != CatchUpPolicy::FireAll && state.missed_during_gap(interval_at, current_time) {
+                                        if state.should_show_ongoing(&event_id, start_time, current_time, interval_seconds) {
+                                            state.mark_ongoing_notified(&event_id, start_time, current_time);
+                                        }
+                                        state.mark_webhook_sent(&event_id, start_time, 0, interval as u32);
+                                        continue;
+                                    }
                                     // Use start_time for deduplication (handles events spanning cycle boundaries)
                                     // Check: global cooldown, per-event cooldown, and ongoing interval
-                                    if state.can_add_toast(current_time)
+                                    if show_toasts
+                                        && cooldown_ok
                                         && state.can_notify_event(&event_id, current_time)
                                         && state.should_show_ongoing(&event_id, start_time, current_time, interval_seconds)
                                     {
-                                        // Use negative value to indicate "time ago" (time since event started)
-                                        let minutes_ago = -((seconds_into_event / 60) as i32);
+                                        // Counting to the start, so the toast shows the time since it started
                                         state.add_toast(
                                             event_id.clone(),
                                             start_time,
-                                            minutes_ago,
+                                            start_time + event_duration,
+                                            start_time,
                                             event.copy_text.clone(),
-                                            reminder.name.clone(),
-                                            reminder.text_color,
+                                            reminder,
+                                            priority,
                                             current_time,
-                                        );
+                                        )
+                                        .last_chance = last_chance_highlight
+                                            && timeline::is_last_before_reset(event, track.base_time, start_time);
                                         state.mark_ongoing_notified(&event_id, start_time, current_time);
                                         state.mark_event_notified(&event_id, current_time);
                                     }
+                                    if snapshot.webhook_enabled
+                                        && state.mark_webhook_sent(&event_id, start_time, 0, interval as u32)
+                                    {
+                                        webhook_fires.push(webhook_fire(track, event, reminder, start_time, 0, language));
+                                    }
                                 }
                             }
                         } else {
-                            // Normal "X minutes before" reminder
+                            // Pre-event reminders count down to the warmup start, end reminders to the
+                            // end of the running occurrence
+                            let (target_start, target_until) = if reminder.before_end {
+                                if seconds_into_event < 0 {
+                                    continue;
+                                }
+                                (start_time + event_duration, event_duration - seconds_into_event)
+                            } else if reminder.before_pre_event {
+                                if event.pre_duration <= 0 {
+                                    continue;
+                                }
+                                (start_time - event.pre_duration, seconds_until - event.pre_duration - lead_seconds)
+                            } else {
+                                (start_time, seconds_until - lead_seconds)
+                            };
+
+                            // Normal "X minutes before" reminder, possibly repeating closer to the start
                             // Use start_time for deduplication (handles events spanning cycle boundaries)
                             // Check: global cooldown, per-event cooldown, and reminder-specific dedup
-                            if seconds_until > 0
-                                && seconds_until <= reminder_seconds
-                                && state.can_add_toast(current_time)
+                            let Some(repetition) = reminder.due_repetition(target_until) else {
+                                continue;
+                            };
+                            let due_at = current_time + target_until - reminder.repetition_lead(repetition);
+                            if catch_up != CatchUpPolicy::FireAll && state.missed_during_gap(due_at, current_time) {
+                                if !state.was_notified(&event_id, target_start, reminder.minutes_before, repetition) {
+                                    if catch_up == CatchUpPolicy::Summary && show_toasts {
+                                        state.add_missed_toast(
+                                            event_id.clone(),
+                                            start_time,
+                                            start_time + event_duration,
+                                            target_start,
+                                            reminder,
+                                            priority,
+                                            current_time,
+                                        );
+                                    }
+                                    state.mark_notified(&event_id, target_start, reminder.minutes_before, repetition);
+                                }
+                                state.mark_webhook_sent(&event_id, target_start, reminder.minutes_before, repetition);
+                                continue;
+                            }
+                            if show_toasts
+                                && cooldown_ok
                                 && state.can_notify_event(&event_id, current_time)
-                                && !state.was_notified(&event_id, start_time, reminder.minutes_before)
+                                && !state.was_notified(&event_id, target_start, reminder.minutes_before, repetition)
                             {
-                                let minutes_until = ((seconds_until + 59) / 60) as i32;
                                 state.add_toast(
                                     event_id.clone(),
                                     start_time,
-                                    minutes_until,
+                                    start_time + event_duration,
+                                    target_start,
                                     event.copy_text.clone(),
-                                    reminder.name.clone(),
-                                    reminder.text_color,
+                                    reminder,
+                                    priority,
                                     current_time,
-                                );
-                                state.mark_notified(&event_id, start_time, reminder.minutes_before);
+                                )
+                                .last_chance = last_chance_highlight
+                                    && timeline::is_last_before_reset(event, track.base_time, start_time);
+                                state.mark_notified(&event_id, target_start, reminder.minutes_before, repetition);
                                 state.mark_event_notified(&event_id, current_time);
                             }
+                            if snapshot.webhook_enabled
+                                && state.mark_webhook_sent(&event_id, target_start, reminder.minutes_before, repetition)
+                            {
+                                webhook_fires.push(webhook_fire(track, event, reminder, start_time, target_until, language));
+                            }
                         }
                     }
                 }
@@ -156,58 +353,102 @@ pub fn update_notifications() {
     upcoming.truncate(notification_config.max_upcoming_events);
 
     state.upcoming_events = upcoming;
+    state.localized_names = localized_names;
+    state.end_catch_up(current_time);
+
+    http_server::sync(snapshot.http_server_enabled, snapshot.http_server_port);
+    export_upcoming(&state, &snapshot, current_time, simulated);
 
     // Drop state lock before acquiring config lock
     drop(state);
 
+    if let Some(spawn) = countdown_beep {
+        sound::countdown_beep(spawn);
+    }
+
+    if !webhook_fires.is_empty() {
+        webhook::send(webhook_fires, WebhookTarget::from_config(&snapshot));
+    }
+
     // Remove fired oneshot events
-    if !oneshot_to_remove.is_empty() {
+    if !oneshot_to_remove.is_empty() || !attended.is_empty() {
         let mut config = RUNTIME_CONFIG.lock();
         for event_id in oneshot_to_remove {
             config.oneshot_events.remove(&event_id);
         }
+        for (event_id, start_time) in attended {
+            addon_log::info(format!("Checked in to {} ({})", event_id.event_name, event_id.track_name));
+            config.notification_config.record_attendance(&event_id, start_time, current_time);
+        }
+    }
+}
+
+/// Hand the Upcoming list to the stream overlay files and the HTTP endpoint (live time only)
+fn export_upcoming(state: &NotificationState, snapshot: &RuntimeConfig, current_time: i64, simulated: bool) {
+    if (snapshot.stream_export_enabled || snapshot.http_server_enabled) && !simulated {
+        let interval = if snapshot.stream_export_enabled { snapshot.stream_export_interval } else { 1 };
+        let count = if snapshot.stream_export_enabled { snapshot.stream_export_count } else { 20 };
+        stream_export::update(state, current_time, interval, count as usize, snapshot.stream_export_enabled);
+    }
+}
+
+/// The reminders with their start countdowns replaced by the track's default lead times, or None
+/// when the track has none (or no reminder counts down to the start). The first start countdown
+/// lends its name, colour and repeats to the track's lead times.
+fn track_default_reminders(track: &EventTrack, reminders: &[ReminderConfig]) -> Option<Vec<ReminderConfig>> {
+    if track.reminder_minutes.is_empty() {
+        return None;
+    }
+    let counts_to_start = |r: &ReminderConfig| r.minutes_before > 0 && !r.before_end && !r.before_pre_event;
+    let template = reminders.iter().find(|r| counts_to_start(r))?;
+
+    let mut track_reminders: Vec<ReminderConfig> = reminders.iter().filter(|r| !counts_to_start(r)).cloned().collect();
+    track_reminders.extend(
+        track
+            .reminder_minutes
+            .iter()
+            .filter(|&&minutes| minutes > 0)
+            .map(|&minutes| ReminderConfig { minutes_before: minutes, ..template.clone() }),
+    );
+    Some(track_reminders)
+}
+
+/// What the webhook gets to know about a fired reminder
+fn webhook_fire(
+    track: &EventTrack,
+    event: &TimelineEvent,
+    reminder: &ReminderConfig,
+    start_time: i64,
+    seconds_until: i64,
+    language: EventLanguage,
+) -> WebhookFire {
+    WebhookFire {
+        event: event.display_name(language).to_string(),
+        track: track.display_name(language).to_string(),
+        category: track.category.clone(),
+        reminder: reminder.name.clone(),
+        start_time,
+        seconds_until,
+        map: event.map_name.clone(),
+        copy_text: event.copy_text.clone(),
     }
 }
 
 /// Calculate the timing for an event
-/// Returns (absolute_start_time, seconds_until_start, seconds_into_event, event_duration, cycle_number)
+/// Returns (absolute_start_time, seconds_until_start, seconds_into_event, event_duration)
 /// seconds_into_event is >= 0 if the event is currently active, < 0 otherwise
-/// cycle_number is a stable identifier for this occurrence (used for deduplication)
 fn calculate_event_timing(
     track: &EventTrack,
     event: &TimelineEvent,
     current_time: i64,
-) -> Option<(i64, i64, i64, i64, i64)> {
-    let elapsed_since_base = current_time - track.base_time;
-    let time_in_cycle = elapsed_since_base.rem_euclid(event.cycle_duration);
-
-    // Calculate stable cycle number for deduplication
-    let cycle_number = elapsed_since_base / event.cycle_duration;
-
-    // Check if event is currently active
-    let event_end_in_cycle = event.start_offset + event.duration;
-    if time_in_cycle >= event.start_offset && time_in_cycle < event_end_in_cycle {
-        // Event is active now
-        let cycle_start = current_time - time_in_cycle;
-        let start_time = cycle_start + event.start_offset;
-        let seconds_into = time_in_cycle - event.start_offset;
-        return Some((start_time, 0, seconds_into, event.duration, cycle_number));
-    }
-
-    // Calculate time to next occurrence
-    let mut time_to_start = event.start_offset - time_in_cycle;
-    let mut next_cycle_number = cycle_number;
-
-    // If event already passed in this cycle, get the next cycle
-    if time_to_start <= 0 {
-        time_to_start += event.cycle_duration;
-        next_cycle_number += 1;
+) -> Option<(i64, i64, i64, i64)> {
+    // Prefer the running occurrence, otherwise the soonest upcoming one
+    let start_time = timeline::current_or_next(event, track.base_time, current_time)?;
+    if start_time <= current_time {
+        Some((start_time, 0, current_time - start_time, event.duration))
+    } else {
+        Some((start_time, start_time - current_time, -1, event.duration))
     }
-
-    let start_time = current_time + time_to_start;
-
-    // Event not active yet, so seconds_into is negative (indicates not active)
-    Some((start_time, time_to_start, -1, event.duration, next_cycle_number))
 }
 
 /// Helper to check if an event is currently tracked
@@ -1,8 +1,19 @@
+use nexus::paths::get_addon_dir;
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
-use std::collections::{HashSet, VecDeque};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
 
-use crate::config::TrackedEventId;
+use crate::addon_log;
+use crate::config::{EventPriority, ReminderConfig, TrackedEventId};
+use crate::json_loader::CopyAction;
+use crate::time_utils::get_current_unix_time;
+
+/// A refresh gap this long (refreshes normally come every second) is taken as a clock jump
+const CLOCK_JUMP_SECONDS: i64 = 30;
+
+const DEDUP_STATE_FILENAME: &str = "notification_state.json";
 
 /// Represents a toast notification in the queue
 #[derive(Debug, Clone)]
@@ -13,8 +24,14 @@ pub struct ToastNotification {
     pub event_id: TrackedEventId,
     /// Timestamp when the event starts (unix seconds)
     pub event_start_time: i64,
-    /// Time remaining until event (recalculated each frame)
-    pub minutes_until: i32,
+    /// Timestamp when that occurrence ends
+    pub event_end_time: i64,
+    /// Seconds until the event starts (negative = seconds since it started)
+    pub seconds_until: i64,
+    /// Time seconds_until counts to: the start, the pre-event start or the end
+    pub target_time: i64,
+    /// seconds_until is the time left before the event ends
+    pub until_end: bool,
     /// When this toast was created (for fade timing)
     pub created_at: std::time::Instant,
     /// Current opacity (1.0 = fully visible, 0.0 = hidden)
@@ -27,16 +44,33 @@ pub struct ToastNotification {
     pub reminder_name: String,
     /// Color for the reminder text
     pub reminder_color: [f32; 4],
+    pub priority: EventPriority,
+    /// Condensed stand-in for several toasts held while the player was away
+    pub is_summary: bool,
+    /// The occurrence is the event's last before daily reset
+    pub last_chance: bool,
+}
+
+/// Large center-screen countdown for an imminent high-priority event
+#[derive(Debug, Clone)]
+pub struct CenterAlert {
+    pub event_id: TrackedEventId,
+    /// Absolute start time of the occurrence being counted down to
+    pub start_time: i64,
+    pub shown_at: std::time::Instant,
 }
 
 /// Key for tracking which reminders have been shown for an event occurrence
 /// Uses start_time for deduplication (handles events spanning cycle boundaries)
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct NotifiedKey {
     pub event_id: TrackedEventId,
     /// Absolute start time of this event occurrence
     pub start_time: i64,
     pub minutes_before: u32,
+    /// Which firing of a repeating reminder (0 for the first)
+    #[serde(default)]
+    pub repetition: u32,
 }
 
 /// Represents an upcoming event for the panel
@@ -49,14 +83,35 @@ pub struct UpcomingEvent {
     pub seconds_until: i64,
     /// Seconds since event started (0 if not yet started)
     pub seconds_into: i64,
+    /// Length of the event in seconds
+    pub duration: i64,
     /// Event color for visual matching
     pub color: [f32; 4],
     /// Copy text if available
     pub copy_text: String,
+    /// Extra copy actions offered in the context menu
+    pub copy_actions: Vec<CopyAction>,
+    pub description: String,
+    pub map_name: String,
+    pub rewards: String,
+    /// Category of the event's track
+    pub category: String,
+    /// The user's note for the event (empty for none)
+    pub note: String,
+    /// Needs an expansion the player doesn't own (listed dimmed, never announced)
+    pub unowned: bool,
 }
 
-/// Key for tracking last ongoing notification time per event
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+impl UpcomingEvent {
+    /// Seconds this occurrence overlaps another one (0 when they don't)
+    pub fn overlap_with(&self, other: &UpcomingEvent) -> i64 {
+        let end = (self.start_time + self.duration).min(other.start_time + other.duration);
+        (end - self.start_time.max(other.start_time)).max(0)
+    }
+}
+
+/// Key for one occurrence of an event: last ongoing notification time, mutes
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct OngoingNotificationKey {
     pub event_id: TrackedEventId,
     /// Absolute start time of this event occurrence
@@ -84,12 +139,45 @@ pub struct NotificationState {
     /// This prevents spam for the same event regardless of reminder type
     pub event_last_notified: std::collections::HashMap<TrackedEventId, i64>,
 
+    /// Occurrences muted by the user ("mute until next spawn"): no reminders fire for them
+    pub muted_occurrences: HashSet<OngoingNotificationKey>,
+
     /// Global cooldown - last time ANY toast was added (prevents rapid spam)
     last_toast_time: i64,
 
+    /// Center-screen alert currently shown
+    pub center_alert: Option<CenterAlert>,
+
+    /// Occurrences that already had a center alert
+    center_alerted: HashSet<OngoingNotificationKey>,
+
+    /// Reminders already sent to the webhook. Kept apart from `notified_reminders` since the
+    /// webhook goes out even when the toast is held back (toasts off, low priority, cooldown).
+    /// "During event" reminders are keyed by the interval number.
+    webhook_sent: HashSet<NotifiedKey>,
+
+    /// When recent center alerts were shown, for the hourly cap
+    center_alert_times: VecDeque<i64>,
+
+    /// Toasts raised while the player was away, delivered on return (see set_holding)
+    held_toasts: Vec<ToastNotification>,
+    holding: bool,
+
+    /// When the current loading screen pause began (see set_paused)
+    paused_since: Option<i64>,
+    /// Start of the gap (a pause that just ended, or a clock jump) whose reminders the next
+    /// refresh handles by the catch-up policy
+    catch_up_from: Option<i64>,
+    /// Reminders missed during that gap, for the summary toast
+    missed_toasts: Vec<ToastNotification>,
+
     /// Cached list of upcoming events (refreshed each frame)
     pub upcoming_events: Vec<UpcomingEvent>,
 
+    /// Display names (event, track) of notified events whose names are translated, refreshed
+    /// each frame; events missing here show their ids
+    pub localized_names: HashMap<TrackedEventId, (String, String)>,
+
     /// Last time we refreshed upcoming events (unix timestamp)
     last_refresh_time: i64,
 
@@ -105,13 +193,32 @@ impl NotificationState {
             notified_reminders: HashSet::new(),
             ongoing_last_notified: std::collections::HashMap::new(),
             event_last_notified: std::collections::HashMap::new(),
+            muted_occurrences: HashSet::new(),
             last_toast_time: 0,
+            center_alert: None,
+            center_alerted: HashSet::new(),
+            webhook_sent: HashSet::new(),
+            center_alert_times: VecDeque::new(),
+            held_toasts: Vec::new(),
+            holding: false,
+            paused_since: None,
+            catch_up_from: None,
+            missed_toasts: Vec::new(),
             upcoming_events: Vec::new(),
+            localized_names: HashMap::new(),
             last_refresh_time: 0,
             preview_toast: None,
         }
     }
 
+    /// Names to show for an event and its track, in the configured language
+    pub fn labels<'a>(&'a self, event_id: &'a TrackedEventId) -> (&'a str, &'a str) {
+        match self.localized_names.get(event_id) {
+            Some((event_name, track_name)) => (event_name, track_name),
+            None => (&event_id.event_name, &event_id.track_name),
+        }
+    }
+
     /// Check if we can add a new toast (global cooldown of 2 seconds between toasts)
     pub fn can_add_toast(&self, current_time: i64) -> bool {
         current_time - self.last_toast_time >= 2
@@ -137,13 +244,19 @@ impl NotificationState {
             id: self.next_toast_id,
             event_id: TrackedEventId::new("Example Track", "Example Event"),
             event_start_time: 0,
-            minutes_until: 5,
+            event_end_time: 0,
+            seconds_until: 5 * 60,
+            target_time: 0,
+            until_end: false,
             created_at: std::time::Instant::now(),
             opacity: 1.0,
             dismissed: false,
             copy_text: "[&Example]".to_string(),
             reminder_name: reminder_name.to_string(),
             reminder_color,
+            priority: EventPriority::Normal,
+            is_summary: false,
+            last_chance: false,
         };
         self.next_toast_id += 1;
         self.preview_toast = Some(preview);
@@ -165,53 +278,225 @@ impl NotificationState {
         }
     }
 
-    /// Add a new toast notification
+    /// Add a new toast notification, returned for flags the caller knows about
+    #[allow(clippy::too_many_arguments)]
     pub fn add_toast(
         &mut self,
         event_id: TrackedEventId,
         event_start_time: i64,
-        minutes_until: i32,
+        event_end_time: i64,
+        target_time: i64,
         copy_text: String,
-        reminder_name: String,
-        reminder_color: [f32; 4],
+        reminder: &ReminderConfig,
+        priority: EventPriority,
         current_time: i64,
-    ) {
+    ) -> &mut ToastNotification {
         let toast = ToastNotification {
             id: self.next_toast_id,
             event_id,
             event_start_time,
-            minutes_until,
+            event_end_time,
+            seconds_until: target_time - current_time,
+            target_time,
+            until_end: reminder.before_end,
             created_at: std::time::Instant::now(),
             opacity: 1.0,
             dismissed: false,
             copy_text,
-            reminder_name,
-            reminder_color,
+            reminder_name: reminder.name.clone(),
+            reminder_color: reminder.text_color,
+            priority,
+            is_summary: false,
+            last_chance: false,
         };
         self.next_toast_id += 1;
         self.last_toast_time = current_time;
-        self.toast_queue.push_back(toast);
+        if self.holding {
+            self.held_toasts.push(toast);
+            self.held_toasts.last_mut().unwrap()
+        } else {
+            self.toast_queue.push_back(toast);
+            self.toast_queue.back_mut().unwrap()
+        }
+    }
+
+    /// Show a center alert for an occurrence, unless it already had one or the hourly cap is reached
+    pub fn try_center_alert(&mut self, event_id: &TrackedEventId, start_time: i64, current_time: i64, max_per_hour: u32) {
+        let key = OngoingNotificationKey {
+            event_id: event_id.clone(),
+            start_time,
+        };
+        if self.center_alerted.contains(&key) {
+            return;
+        }
+
+        while self.center_alert_times.front().is_some_and(|&t| current_time - t >= 3600) {
+            self.center_alert_times.pop_front();
+        }
+        if self.center_alert_times.len() >= max_per_hour as usize {
+            return;
+        }
+
+        self.center_alerted.insert(key);
+        self.center_alert_times.push_back(current_time);
+        self.center_alert = Some(CenterAlert {
+            event_id: event_id.clone(),
+            start_time,
+            shown_at: std::time::Instant::now(),
+        });
+    }
+
+    /// Hold new toasts while the player is away (game unfocused or loading); coming back
+    /// delivers what was held
+    pub fn set_holding(&mut self, holding: bool, current_time: i64, max_visible: usize) {
+        if holding == self.holding {
+            return;
+        }
+        self.holding = holding;
+        if !holding {
+            self.deliver_held(current_time, max_visible);
+        }
+    }
+
+    /// Show the latest held reminder per occurrence, or one summary toast if they wouldn't all fit.
+    /// Reminders for occurrences that ended while the player was away are dropped.
+    fn deliver_held(&mut self, current_time: i64, max_visible: usize) {
+        let mut latest: Vec<ToastNotification> = Vec::new();
+        let held = std::mem::take(&mut self.held_toasts).into_iter().filter(|toast| toast.event_end_time > current_time);
+        for toast in held.rev() {
+            if !latest.iter().any(|t| t.event_id == toast.event_id && t.event_start_time == toast.event_start_time) {
+                latest.push(toast);
+            }
+        }
+        if latest.is_empty() {
+            return;
+        }
+        latest.reverse();
+
+        for toast in &mut latest {
+            toast.seconds_until = toast.target_time - current_time;
+            toast.created_at = std::time::Instant::now();
+        }
+
+        if latest.len() <= max_visible {
+            self.toast_queue.extend(latest);
+        } else {
+            let summary = self.summary_toast(&latest, &format!("{} reminders while you were away", latest.len()));
+            self.toast_queue.push_back(summary);
+        }
+        self.last_toast_time = current_time;
+    }
+
+    /// One toast standing in for several, pointing at the soonest upcoming event (or the most
+    /// recent one if all have started)
+    fn summary_toast(&mut self, toasts: &[ToastNotification], title: &str) -> ToastNotification {
+        let next = toasts
+            .iter()
+            .filter(|t| t.seconds_until > 0)
+            .min_by_key(|t| t.seconds_until)
+            .or_else(|| toasts.iter().max_by_key(|t| t.event_start_time))
+            .cloned()
+            .unwrap_or_else(|| toasts[0].clone());
+        let names: Vec<String> = toasts.iter().map(|t| self.labels(&t.event_id).0.to_string()).collect();
+        let next_name = self.labels(&next.event_id).0.to_string();
+        let priority = if toasts.iter().any(|t| t.priority == EventPriority::High) {
+            EventPriority::High
+        } else {
+            EventPriority::Normal
+        };
+
+        let summary = ToastNotification {
+            id: self.next_toast_id,
+            event_id: TrackedEventId::new(&names.join(", "), title),
+            event_start_time: next.event_start_time,
+            event_end_time: next.event_end_time,
+            seconds_until: next.seconds_until,
+            target_time: next.target_time,
+            until_end: next.until_end,
+            created_at: std::time::Instant::now(),
+            opacity: 1.0,
+            dismissed: false,
+            copy_text: String::new(),
+            reminder_name: format!("Next: {}", next_name),
+            reminder_color: next.reminder_color,
+            priority,
+            is_summary: true,
+            last_chance: false,
+        };
+        self.next_toast_id += 1;
+        summary
     }
 
     /// Mark a reminder as shown for an event occurrence
-    pub fn mark_notified(&mut self, event_id: &TrackedEventId, start_time: i64, minutes_before: u32) {
+    pub fn mark_notified(&mut self, event_id: &TrackedEventId, start_time: i64, minutes_before: u32, repetition: u32) {
         self.notified_reminders.insert(NotifiedKey {
             event_id: event_id.clone(),
             start_time,
             minutes_before,
+            repetition,
         });
     }
 
     /// Check if a reminder was already shown for an event occurrence
-    pub fn was_notified(&self, event_id: &TrackedEventId, start_time: i64, minutes_before: u32) -> bool {
+    pub fn was_notified(&self, event_id: &TrackedEventId, start_time: i64, minutes_before: u32, repetition: u32) -> bool {
         self.notified_reminders.contains(&NotifiedKey {
             event_id: event_id.clone(),
             start_time,
             minutes_before,
+            repetition,
+        })
+    }
+
+    /// Record a reminder as sent to the webhook; false if it already was
+    pub fn mark_webhook_sent(&mut self, event_id: &TrackedEventId, start_time: i64, minutes_before: u32, repetition: u32) -> bool {
+        self.webhook_sent.insert(NotifiedKey {
+            event_id: event_id.clone(),
+            start_time,
+            minutes_before,
+            repetition,
         })
     }
 
     /// Clean up old notified entries (keep entries from last 24 hours)
+    /// Move the records of renamed events to their current ids. Returns how many were moved.
+    pub fn migrate_event_ids(&mut self, rename: impl Fn(&mut TrackedEventId) -> usize) -> usize {
+        let mut moved = 0;
+        let mut rename_key = |key: &mut OngoingNotificationKey| moved += rename(&mut key.event_id);
+        self.ongoing_last_notified = std::mem::take(&mut self.ongoing_last_notified)
+            .into_iter()
+            .map(|(mut key, time)| {
+                rename_key(&mut key);
+                (key, time)
+            })
+            .collect();
+        for set in [&mut self.muted_occurrences, &mut self.center_alerted] {
+            *set = std::mem::take(set)
+                .into_iter()
+                .map(|mut key| {
+                    rename_key(&mut key);
+                    key
+                })
+                .collect();
+        }
+        for set in [&mut self.notified_reminders, &mut self.webhook_sent] {
+            *set = std::mem::take(set)
+                .into_iter()
+                .map(|mut key| {
+                    moved += rename(&mut key.event_id);
+                    key
+                })
+                .collect();
+        }
+        self.event_last_notified = std::mem::take(&mut self.event_last_notified)
+            .into_iter()
+            .map(|(mut event_id, time)| {
+                moved += rename(&mut event_id);
+                (event_id, time)
+            })
+            .collect();
+        moved
+    }
+
     pub fn cleanup_old_notifications(&mut self, current_time: i64) {
         let cutoff = current_time - 86400; // 24 hours ago
         self.notified_reminders.retain(|key| {
@@ -220,6 +505,9 @@ impl NotificationState {
         self.ongoing_last_notified.retain(|key, _| {
             key.start_time > cutoff
         });
+        self.muted_occurrences.retain(|key| key.start_time > cutoff);
+        self.center_alerted.retain(|key| key.start_time > cutoff);
+        self.webhook_sent.retain(|key| key.start_time > cutoff);
         // Clean up per-event cooldown entries older than 5 minutes
         self.event_last_notified.retain(|_, &mut last_time| {
             current_time - last_time < 300
@@ -255,11 +543,36 @@ impl NotificationState {
         self.ongoing_last_notified.insert(key, current_time);
     }
 
-    /// Update toast states (opacity, removal)
-    pub fn update_toasts(&mut self, toast_duration: f32, max_visible: usize) {
-        let fade_start = toast_duration - 1.0; // Start fading 1 second before end
+    /// Mute or unmute one occurrence of an event. Muting also dismisses its visible toasts.
+    pub fn set_occurrence_muted(&mut self, event_id: &TrackedEventId, start_time: i64, muted: bool) {
+        let key = OngoingNotificationKey {
+            event_id: event_id.clone(),
+            start_time,
+        };
+        if muted {
+            self.muted_occurrences.insert(key);
+            for toast in &mut self.toast_queue {
+                if toast.event_id == *event_id && toast.event_start_time == start_time {
+                    toast.dismissed = true;
+                }
+            }
+        } else {
+            self.muted_occurrences.remove(&key);
+        }
+    }
+
+    pub fn is_occurrence_muted(&self, event_id: &TrackedEventId, start_time: i64) -> bool {
+        self.muted_occurrences.contains(&OngoingNotificationKey {
+            event_id: event_id.clone(),
+            start_time,
+        })
+    }
 
+    /// Update toast states (opacity, removal)
+    pub fn update_toasts(&mut self, toast_duration: f32, high_priority_duration: f32, max_visible: usize) {
         for toast in &mut self.toast_queue {
+            let toast_duration = if toast.priority == EventPriority::High { high_priority_duration } else { toast_duration };
+            let fade_start = toast_duration - 1.0; // Start fading 1 second before end
             let elapsed = toast.created_at.elapsed().as_secs_f32();
 
             if elapsed > fade_start {
@@ -281,6 +594,90 @@ impl NotificationState {
         }
     }
 
+    /// Pause notification work during a loading screen. Once it ends, the next refresh records
+    /// what came due in the meantime instead of toasting it all at once. Returns `paused`.
+    pub fn set_paused(&mut self, paused: bool, current_time: i64) -> bool {
+        match (paused, self.paused_since) {
+            (true, None) => self.paused_since = Some(current_time),
+            (false, Some(since)) => {
+                self.paused_since = None;
+                self.catch_up_from = Some(since);
+            }
+            _ => {}
+        }
+        paused
+    }
+
+    /// Treat a refresh gap longer than `CLOCK_JUMP_SECONDS` as a clock jump (sleep, suspend or
+    /// a long stall), so the reminders it skipped over go by the catch-up policy. A loading
+    /// screen that just ended already covers its own gap.
+    pub fn detect_clock_jump(&mut self, current_time: i64) {
+        let gap = current_time - self.last_refresh_time;
+        if self.last_refresh_time == 0 || self.catch_up_from.is_some() || gap <= CLOCK_JUMP_SECONDS {
+            return;
+        }
+        addon_log::info(format!("Clock jumped {}s ahead, catching up on missed reminders", gap));
+        self.catch_up_from = Some(self.last_refresh_time);
+    }
+
+    /// Whether something due at `due_at` came due during the gap being caught up on
+    pub fn missed_during_gap(&self, due_at: i64, current_time: i64) -> bool {
+        self.catch_up_from.is_some_and(|since| due_at >= since && due_at < current_time)
+    }
+
+    /// Remember a reminder missed during the gap, for the summary toast
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_missed_toast(
+        &mut self,
+        event_id: TrackedEventId,
+        event_start_time: i64,
+        event_end_time: i64,
+        target_time: i64,
+        reminder: &ReminderConfig,
+        priority: EventPriority,
+        current_time: i64,
+    ) {
+        self.missed_toasts.push(ToastNotification {
+            id: 0,
+            event_id,
+            event_start_time,
+            event_end_time,
+            seconds_until: target_time - current_time,
+            target_time,
+            until_end: reminder.before_end,
+            created_at: std::time::Instant::now(),
+            opacity: 1.0,
+            dismissed: false,
+            copy_text: String::new(),
+            reminder_name: reminder.name.clone(),
+            reminder_color: reminder.text_color,
+            priority,
+            is_summary: false,
+            last_chance: false,
+        });
+    }
+
+    /// The refresh after a gap is done: show the summary of what was missed, if any, and
+    /// handle reminders normally again
+    pub fn end_catch_up(&mut self, current_time: i64) {
+        self.catch_up_from = None;
+        let missed = std::mem::take(&mut self.missed_toasts);
+        if missed.is_empty() {
+            return;
+        }
+        let title = match missed.len() {
+            1 => "Reminder missed".to_string(),
+            count => format!("{} reminders missed", count),
+        };
+        let summary = self.summary_toast(&missed, &title);
+        self.last_toast_time = current_time;
+        if self.holding {
+            self.held_toasts.push(summary);
+        } else {
+            self.toast_queue.push_back(summary);
+        }
+    }
+
     /// Check if refresh is needed (called every frame, but only refreshes every second)
     pub fn needs_refresh(&self, current_time: i64) -> bool {
         current_time != self.last_refresh_time
@@ -297,6 +694,82 @@ impl Default for NotificationState {
     }
 }
 
+/// Deduplication state kept across addon reloads, so reloading right before an event
+/// doesn't repeat reminders that were already shown
+#[derive(Serialize, Deserialize, Default)]
+struct DedupState {
+    #[serde(default)]
+    notified_reminders: Vec<NotifiedKey>,
+    #[serde(default)]
+    ongoing_last_notified: Vec<(OngoingNotificationKey, i64)>,
+    #[serde(default)]
+    event_last_notified: Vec<(TrackedEventId, i64)>,
+    #[serde(default)]
+    muted_occurrences: Vec<OngoingNotificationKey>,
+    #[serde(default)]
+    center_alerted: Vec<OngoingNotificationKey>,
+    #[serde(default)]
+    webhook_sent: Vec<NotifiedKey>,
+}
+
+/// Write the deduplication state next to the user config (call on unload)
+pub fn save_dedup_state() {
+    let Some(path) = get_addon_dir("event_timers").map(|p| p.join(DEDUP_STATE_FILENAME)) else {
+        return;
+    };
+
+    let dedup = {
+        let mut state = NOTIFICATION_STATE.lock();
+        state.cleanup_old_notifications(get_current_unix_time());
+        DedupState {
+            notified_reminders: state.notified_reminders.iter().cloned().collect(),
+            ongoing_last_notified: state.ongoing_last_notified.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+            event_last_notified: state.event_last_notified.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+            muted_occurrences: state.muted_occurrences.iter().cloned().collect(),
+            center_alerted: state.center_alerted.iter().cloned().collect(),
+            webhook_sent: state.webhook_sent.iter().cloned().collect(),
+        }
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).ok();
+    }
+    match serde_json::to_string(&dedup) {
+        Ok(json_str) => {
+            if let Err(e) = fs::write(&path, json_str) {
+                addon_log::error(format!("Failed to save notification state: {}", e));
+            }
+        }
+        Err(e) => addon_log::error(format!("Failed to serialize notification state: {}", e)),
+    }
+}
+
+/// Restore the deduplication state saved by the last unload (call on load)
+pub fn load_dedup_state() {
+    let Some(path) = get_addon_dir("event_timers").map(|p| p.join(DEDUP_STATE_FILENAME)) else {
+        return;
+    };
+    let Ok(json_str) = fs::read_to_string(&path) else {
+        return;
+    };
+    let dedup = match serde_json::from_str::<DedupState>(&json_str) {
+        Ok(dedup) => dedup,
+        Err(e) => {
+            addon_log::warn(format!("Ignoring unreadable notification state: {}", e));
+            return;
+        }
+    };
+
+    let mut state = NOTIFICATION_STATE.lock();
+    state.notified_reminders.extend(dedup.notified_reminders);
+    state.ongoing_last_notified.extend(dedup.ongoing_last_notified);
+    state.event_last_notified.extend(dedup.event_last_notified);
+    state.muted_occurrences.extend(dedup.muted_occurrences);
+    state.center_alerted.extend(dedup.center_alerted);
+    state.webhook_sent.extend(dedup.webhook_sent);
+    state.cleanup_old_notifications(get_current_unix_time());
+}
+
 /// Global notification state
 pub static NOTIFICATION_STATE: Lazy<Mutex<NotificationState>> =
     Lazy::new(|| Mutex::new(NotificationState::new()));
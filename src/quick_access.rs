@@ -0,0 +1,255 @@
+// Quick Access button: icon style, state (muted, upcoming-event badge), tooltip and right-click menu.
+//
+// Nexus can't retexture or retitle a button in place, so a state change re-adds the button
+// with textures generated for that state. Generated textures are loaded once and reused.
+// A tooltip change alone re-adds it at most once a minute, the countdown in it is in minutes.
+// Custom icon PNGs are read from disk when first needed; unreadable files fall back to the
+// bundled artwork.
+
+use nexus::{
+    gui::render,
+    quick_access::{add_quick_access, add_quick_access_context_menu, remove_quick_access},
+    texture::load_texture_from_memory,
+};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use crate::addon_log;
+use crate::config::{QaIconStyle, RUNTIME_CONFIG, SNOOZE_CHOICES};
+use crate::icons;
+use crate::notifications::NOTIFICATION_STATE;
+use crate::time_utils::{format_duration, get_current_unix_time};
+
+// Embed icon files directly in the binary
+const QA_ICON: &[u8] = include_bytes!("../qa_icon.png");
+const QA_ICON_HOVER: &[u8] = include_bytes!("../qa_icon_hovered.png");
+
+const QA_ID: &str = "EVENT_TIMERS_QA";
+const ICON_ID: &str = "EVENT_TIMERS_QA_ICON";
+const HOVER_ID: &str = "EVENT_TIMERS_QA_ICON_HOVER";
+
+const DEFAULT_TOOLTIP: &str = "Toggle Event Timers Window";
+
+/// What the button currently shows
+#[derive(Debug, Clone)]
+struct IconState {
+    style: QaIconStyle,
+    icon_path: String,
+    hover_path: String,
+    muted: bool,
+    /// Badge text ("3", "9+"), None when there's nothing to show
+    badge: Option<String>,
+    /// "Next: <event> in 12m", at minute resolution to keep re-adds rare
+    tooltip: String,
+}
+
+impl IconState {
+    /// Same artwork, so only the tooltip could need a re-add
+    fn same_look(&self, other: &IconState) -> bool {
+        self.style == other.style
+            && self.icon_path == other.icon_path
+            && self.hover_path == other.hover_path
+            && self.muted == other.muted
+            && self.badge == other.badge
+    }
+
+    fn texture_suffix(&self) -> String {
+        let mut suffix = match self.style {
+            QaIconStyle::Classic => String::new(),
+            QaIconStyle::Monochrome => "_MONO".to_string(),
+            QaIconStyle::Custom => {
+                let mut hasher = DefaultHasher::new();
+                (&self.icon_path, &self.hover_path).hash(&mut hasher);
+                format!("_CUSTOM_{:x}", hasher.finish())
+            }
+        };
+        if self.muted {
+            suffix.push_str("_MUTED");
+        }
+        if let Some(badge) = &self.badge {
+            suffix.push_str("_BADGE_");
+            suffix.push_str(&badge.replace('+', "PLUS"));
+        }
+        suffix
+    }
+}
+
+static SHOWN_STATE: Lazy<Mutex<Option<IconState>>> = Lazy::new(|| Mutex::new(None));
+/// Second of the last check, the state is looked at no more than once a second
+static LAST_SYNC: AtomicI64 = AtomicI64::new(0);
+/// When the button was last added, to hold tooltip-only re-adds to one a minute
+static LAST_SHOWN: AtomicI64 = AtomicI64::new(0);
+const TOOLTIP_REFRESH_SECONDS: i64 = 60;
+/// Generated texture id -> id actually loaded for it (the plain icon if generation failed)
+static LOADED_TEXTURES: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Add the button and its context menu (call once on load)
+pub fn setup() {
+    load_texture_from_memory(ICON_ID, QA_ICON, None);
+    load_texture_from_memory(HOVER_ID, QA_ICON_HOVER, None);
+
+    show(current_state());
+
+    add_quick_access_context_menu(
+        "EVENT_TIMERS_QA_MENU",
+        Some(QA_ID),
+        render!(|ui| {
+            let mut config = RUNTIME_CONFIG.lock();
+            ui.checkbox("Mute All Notifications", &mut config.notification_config.muted);
+            let now = get_current_unix_time();
+            if let Some(left) = config.notification_config.snooze_remaining(now) {
+                if ui.button(format!("Resume Notifications ({} left)", format_duration(left))) {
+                    config.notification_config.snoozed_until = None;
+                }
+            } else {
+                ui.text("Snooze:");
+                for minutes in SNOOZE_CHOICES {
+                    ui.same_line();
+                    if ui.small_button(format!("{}m", minutes)) {
+                        config.notification_config.snooze(now, minutes);
+                    }
+                }
+            }
+            ui.checkbox("Show Upcoming Panel", &mut config.notification_config.upcoming_panel_enabled);
+        }),
+    )
+    .revert_on_unload();
+}
+
+/// Re-add the button when the style, mute or badge changed, or (once a minute at most) the
+/// tooltip did (call every frame)
+pub fn sync_icon() {
+    let now = get_current_unix_time();
+    if LAST_SYNC.swap(now, Ordering::Relaxed) == now {
+        return;
+    }
+    let state = current_state();
+    let tooltip_due = now - LAST_SHOWN.load(Ordering::Relaxed) >= TOOLTIP_REFRESH_SECONDS;
+    let up_to_date = SHOWN_STATE
+        .lock()
+        .as_ref()
+        .is_some_and(|shown| shown.same_look(&state) && (shown.tooltip == state.tooltip || !tooltip_due));
+    if !up_to_date {
+        remove_quick_access(QA_ID);
+        show(state);
+    }
+}
+
+fn current_state() -> IconState {
+    let now = get_current_unix_time();
+    let (style, icon_path, hover_path, muted, snooze_left, badge_enabled, badge_minutes) = {
+        let config = RUNTIME_CONFIG.snapshot();
+        let notifications = &config.notification_config;
+        (
+            config.qa_icon_style,
+            config.qa_icon_path.clone(),
+            config.qa_icon_hover_path.clone(),
+            notifications.is_silenced(now),
+            notifications.snooze_remaining(now),
+            notifications.qa_badge_enabled,
+            notifications.qa_badge_minutes,
+        )
+    };
+
+    let state = NOTIFICATION_STATE.lock();
+    let badge = if badge_enabled {
+        let window = badge_minutes as i64 * 60;
+        let count = state
+            .upcoming_events
+            .iter()
+            .filter(|e| !e.unowned && e.seconds_until > 0 && e.seconds_until <= window)
+            .count();
+        match count {
+            0 => None,
+            1..=9 => Some(count.to_string()),
+            _ => Some("9+".to_string()),
+        }
+    } else {
+        None
+    };
+
+    // Upcoming events are sorted soonest first, with active ones (seconds_until 0) at the top
+    let tooltip = if let Some(left) = snooze_left {
+        let minutes = (left + 59) / 60;
+        format!("Snoozed: {} left", format_duration(minutes * 60))
+    } else {
+        state
+            .upcoming_events
+            .iter()
+            .find(|e| !e.unowned && e.seconds_until > 0)
+            .map(|e| {
+                let minutes = (e.seconds_until + 59) / 60;
+                let (event_name, _) = state.labels(&e.event_id);
+                format!("Next: {} in {}", event_name, format_duration(minutes * 60))
+            })
+            .unwrap_or_else(|| DEFAULT_TOOLTIP.to_string())
+    };
+
+    IconState { style, icon_path, hover_path, muted, badge, tooltip }
+}
+
+fn show(state: IconState) {
+    let (icon, hover) = if state.style == QaIconStyle::Classic && !state.muted && state.badge.is_none() {
+        (ICON_ID.to_string(), HOVER_ID.to_string())
+    } else {
+        (load_variant(&state, false), load_variant(&state, true))
+    };
+
+    add_quick_access(QA_ID, icon, hover, "Toggle Event Timers", &state.tooltip)
+        .revert_on_unload();
+    LAST_SHOWN.store(get_current_unix_time(), Ordering::Relaxed);
+    *SHOWN_STATE.lock() = Some(state);
+}
+
+/// Generate and load a variant texture on first use; falls back to the plain icon
+fn load_variant(state: &IconState, hover: bool) -> String {
+    let (base_id, bundled) = if hover { (HOVER_ID, QA_ICON_HOVER) } else { (ICON_ID, QA_ICON) };
+    let id = format!("{}{}", base_id, state.texture_suffix());
+
+    LOADED_TEXTURES
+        .lock()
+        .entry(id.clone())
+        .or_insert_with(|| {
+            let monochrome = state.style == QaIconStyle::Monochrome;
+            let badge = state.badge.as_deref();
+            let generated = custom_png(state, hover)
+                .and_then(|png| icons::quick_access_variant(&png, monochrome, state.muted, badge))
+                .or_else(|| icons::quick_access_variant(bundled, monochrome, state.muted, badge));
+            match generated {
+                Some(png) => {
+                    load_texture_from_memory(&id, png, None);
+                    id.clone()
+                }
+                None => base_id.to_string(),
+            }
+        })
+        .clone()
+}
+
+/// The user's PNG for the Custom style (the hover icon defaults to the normal one)
+fn custom_png(state: &IconState, hover: bool) -> Option<Vec<u8>> {
+    if state.style != QaIconStyle::Custom {
+        return None;
+    }
+    let path = if hover && !state.hover_path.trim().is_empty() { &state.hover_path } else { &state.icon_path };
+    let path = path.trim();
+    if path.is_empty() {
+        return None;
+    }
+    match fs::read(path) {
+        Ok(png) if png.starts_with(b"\x89PNG") => Some(png),
+        Ok(_) => {
+            addon_log::warn(format!("Quick Access icon {} is not a PNG, using the bundled icon", path));
+            None
+        }
+        Err(e) => {
+            addon_log::warn(format!("Failed to read Quick Access icon {}: {}", path, e));
+            None
+        }
+    }
+}
@@ -0,0 +1,354 @@
+// Cron-like recurrence expressions for custom events.
+//
+// An expression is one or more clauses separated by ';':
+//   "every 2h at :00 and :30"   - every 2 hours (from local midnight), at minutes 0 and 30
+//   "every 15m"                 - every 15 minutes
+//   "Sat,Sun 18:00"             - weekly, Saturday and Sunday at 18:00 local time
+//   "Mon-Fri 20:00, 22:30"      - weekdays at 20:00 and 22:30
+//   "daily 12:00"               - every day at 12:00 (the day list can be omitted)
+//
+// Clauses are compiled into a single cycle (the least common multiple of the clause
+// periods) and a sorted list of spawn offsets within it, which plugs straight into the
+// regular start_offset/cycle_duration occurrence math. The offsets depend on the local UTC
+// offset, so they're recompiled when it changes (config::refresh_recurrences).
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+
+/// Upper bound on spawns per cycle, keeps per-frame occurrence scanning cheap
+const MAX_SPAWNS_PER_CYCLE: usize = 1000;
+
+/// A parsed recurrence expression, expressed in local wall-clock time
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recurrence {
+    /// Length of one full cycle in seconds (always divides a week)
+    pub period: i64,
+    /// Sorted spawn offsets in local time, measured from the start of the local cycle
+    /// (local midnight for sub-day cycles, Thursday 00:00 for weekly ones, matching the unix epoch)
+    pub local_offsets: Vec<i64>,
+}
+
+impl Recurrence {
+    /// Convert local-time offsets into offsets relative to a track's base time
+    pub fn cycle_offsets(&self, base_time: i64, utc_offset: i64) -> Vec<i64> {
+        let mut offsets: Vec<i64> = self
+            .local_offsets
+            .iter()
+            .map(|&local| (local - utc_offset - base_time).rem_euclid(self.period))
+            .collect();
+        offsets.sort_unstable();
+        offsets
+    }
+}
+
+/// Parse a recurrence expression into its cycle and spawn offsets
+pub fn parse_recurrence(expression: &str) -> Result<Recurrence, String> {
+    let clauses: Vec<Recurrence> = expression
+        .split(';')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(parse_clause)
+        .collect::<Result<_, _>>()?;
+
+    if clauses.is_empty() {
+        return Err("Empty recurrence expression".to_string());
+    }
+
+    // Combine all clauses into one shared cycle
+    let period = clauses.iter().fold(1, |acc, c| lcm(acc, c.period));
+    let mut local_offsets = Vec::new();
+    for clause in &clauses {
+        for repetition in 0..(period / clause.period) {
+            for &offset in &clause.local_offsets {
+                local_offsets.push(repetition * clause.period + offset);
+            }
+        }
+        if local_offsets.len() > MAX_SPAWNS_PER_CYCLE {
+            return Err(format!("Too many spawns per cycle (max {})", MAX_SPAWNS_PER_CYCLE));
+        }
+    }
+    local_offsets.sort_unstable();
+    local_offsets.dedup();
+
+    Ok(Recurrence { period, local_offsets })
+}
+
+fn parse_clause(clause: &str) -> Result<Recurrence, String> {
+    let normalized = clause.to_lowercase().replace(" and ", ",");
+    let tokens: Vec<&str> = normalized
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    if tokens.first() == Some(&"every") {
+        parse_interval_clause(&tokens[1..])
+    } else {
+        parse_calendar_clause(&tokens)
+    }
+}
+
+/// "every <N><unit> [at :MM, H:MM ...]"
+fn parse_interval_clause(tokens: &[&str]) -> Result<Recurrence, String> {
+    let mut rest = tokens;
+
+    // Accept both "2h" and "2 hours"
+    let period = match rest {
+        [amount, unit, tail @ ..] if amount.parse::<i64>().is_ok() && parse_unit(unit).is_some() => {
+            rest = tail;
+            amount
+                .parse::<i64>()
+                .unwrap()
+                .checked_mul(parse_unit(unit).unwrap())
+                .ok_or_else(|| format!("Interval '{} {}' is too long", amount, unit))?
+        }
+        [amount_unit, tail @ ..] => {
+            rest = tail;
+            parse_duration(amount_unit).ok_or_else(|| format!("Invalid interval '{}'", amount_unit))?
+        }
+        [] => return Err("Missing interval after 'every'".to_string()),
+    };
+
+    if period < MINUTE {
+        return Err("Interval must be at least 1 minute".to_string());
+    }
+    if WEEK % period != 0 {
+        return Err(format!("Interval '{}' must divide evenly into a week", format_period(period)));
+    }
+
+    let mut local_offsets = Vec::new();
+    match rest {
+        [] => local_offsets.push(0),
+        ["at", times @ ..] if !times.is_empty() => {
+            for time in times {
+                let offset = parse_cycle_time(time).ok_or_else(|| format!("Invalid time '{}'", time))?;
+                if offset >= period {
+                    return Err(format!("Time '{}' is outside the {} interval", time, format_period(period)));
+                }
+                local_offsets.push(offset);
+            }
+        }
+        [other, ..] => return Err(format!("Unexpected '{}' (expected 'at')", other)),
+    }
+
+    local_offsets.sort_unstable();
+    local_offsets.dedup();
+    Ok(Recurrence { period, local_offsets })
+}
+
+/// "[days] HH:MM[, HH:MM ...]"
+fn parse_calendar_clause(tokens: &[&str]) -> Result<Recurrence, String> {
+    let mut days: Vec<i64> = Vec::new();
+    let mut times: Vec<i64> = Vec::new();
+    let mut has_day_spec = false;
+
+    for token in tokens {
+        if let Some(time) = parse_clock_time(token) {
+            times.push(time);
+        } else if let Some(token_days) = parse_day_spec(token) {
+            has_day_spec = true;
+            days.extend(token_days);
+        } else {
+            return Err(format!("Unrecognized '{}'", token));
+        }
+    }
+
+    if times.is_empty() {
+        return Err("Missing time of day (e.g. 18:00)".to_string());
+    }
+
+    // No day list, or every day listed: a plain daily cycle
+    days.sort_unstable();
+    days.dedup();
+    if !has_day_spec || days.len() == 7 {
+        times.sort_unstable();
+        times.dedup();
+        return Ok(Recurrence { period: DAY, local_offsets: times });
+    }
+
+    let mut local_offsets: Vec<i64> = days
+        .iter()
+        .flat_map(|&day| times.iter().map(move |&time| week_offset(day, time)))
+        .collect();
+    local_offsets.sort_unstable();
+    local_offsets.dedup();
+    Ok(Recurrence { period: WEEK, local_offsets })
+}
+
+/// Offset within an epoch-aligned week (the unix epoch fell on a Thursday)
+fn week_offset(day_from_monday: i64, time_of_day: i64) -> i64 {
+    (day_from_monday - 3).rem_euclid(7) * DAY + time_of_day
+}
+
+/// Day names, ranges ("mon-fri") and keywords, as days from Monday (0..7)
+fn parse_day_spec(token: &str) -> Option<Vec<i64>> {
+    match token {
+        "daily" | "everyday" => return Some((0..7).collect()),
+        "weekdays" => return Some((0..5).collect()),
+        "weekends" | "weekend" => return Some(vec![5, 6]),
+        _ => {}
+    }
+
+    if let Some((from, to)) = token.split_once('-') {
+        let from = parse_day_name(from)?;
+        let to = parse_day_name(to)?;
+        let len = (to - from).rem_euclid(7) + 1;
+        return Some((0..len).map(|i| (from + i) % 7).collect());
+    }
+
+    parse_day_name(token).map(|d| vec![d])
+}
+
+fn parse_day_name(name: &str) -> Option<i64> {
+    const DAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+    if name.len() < 3 {
+        return None;
+    }
+    DAYS.iter()
+        .position(|d| name.starts_with(d))
+        .map(|i| i as i64)
+}
+
+/// "18:00" -> seconds since midnight
+fn parse_clock_time(token: &str) -> Option<i64> {
+    let (hours, minutes) = token.split_once(':')?;
+    let hours: i64 = hours.parse().ok()?;
+    let minutes: i64 = minutes.parse().ok()?;
+    if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+        return None;
+    }
+    Some(hours * HOUR + minutes * MINUTE)
+}
+
+/// ":30" (minute of the interval) or "1:30" (hours:minutes into the interval)
+fn parse_cycle_time(token: &str) -> Option<i64> {
+    let (hours, minutes) = token.split_once(':')?;
+    let hours: i64 = if hours.is_empty() { 0 } else { hours.parse().ok()? };
+    let minutes: i64 = minutes.parse().ok()?;
+    if hours < 0 || !(0..60).contains(&minutes) {
+        return None;
+    }
+    hours.checked_mul(HOUR)?.checked_add(minutes * MINUTE)
+}
+
+fn parse_unit(unit: &str) -> Option<i64> {
+    match unit {
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(MINUTE),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(HOUR),
+        "d" | "day" | "days" => Some(DAY),
+        "w" | "week" | "weeks" => Some(WEEK),
+        _ => None,
+    }
+}
+
+/// "2h", "90m", "1d"
+fn parse_duration(token: &str) -> Option<i64> {
+    let split = token.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = token.split_at(split);
+    amount.parse::<i64>().ok()?.checked_mul(parse_unit(unit)?)
+}
+
+fn format_period(seconds: i64) -> String {
+    if seconds % HOUR == 0 {
+        format!("{}h", seconds / HOUR)
+    } else {
+        format!("{}m", seconds / MINUTE)
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+fn lcm(a: i64, b: i64) -> i64 {
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offsets(expression: &str) -> (i64, Vec<i64>) {
+        let recurrence = parse_recurrence(expression).unwrap();
+        (recurrence.period, recurrence.local_offsets)
+    }
+
+    #[test]
+    fn interval_clauses() {
+        assert_eq!(offsets("every 15m"), (15 * MINUTE, vec![0]));
+        assert_eq!(offsets("every 2 hours"), (2 * HOUR, vec![0]));
+        assert_eq!(offsets("every 2h at :00 and :30"), (2 * HOUR, vec![0, 30 * MINUTE]));
+        assert_eq!(offsets("Every 2h at 1:15"), (2 * HOUR, vec![HOUR + 15 * MINUTE]));
+    }
+
+    #[test]
+    fn calendar_clauses() {
+        assert_eq!(offsets("daily 12:00"), (DAY, vec![12 * HOUR]));
+        assert_eq!(offsets("22:30, 20:00"), (DAY, vec![20 * HOUR, 22 * HOUR + 30 * MINUTE]));
+        assert_eq!(offsets("mon-sun 06:00"), (DAY, vec![6 * HOUR]));
+        // The week starts on Thursday, like the unix epoch
+        assert_eq!(offsets("Thu 00:00"), (WEEK, vec![0]));
+        assert_eq!(offsets("Sat,Sun 18:00"), (WEEK, vec![2 * DAY + 18 * HOUR, 3 * DAY + 18 * HOUR]));
+        assert_eq!(offsets("weekdays 20:00").1.len(), 5);
+        assert_eq!(offsets("fri-mon 20:00").1.len(), 4);
+    }
+
+    #[test]
+    fn clauses_share_one_cycle() {
+        let (period, local) = offsets("every 12h; Sat 18:00");
+        assert_eq!(period, WEEK);
+        assert_eq!(local.len(), 14 + 1);
+        assert!(local.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // Duplicate spawns across clauses collapse
+        assert_eq!(offsets("every 12h; daily 12:00"), (DAY, vec![0, 12 * HOUR]));
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        for expression in [
+            "",
+            " ; ",
+            "every",
+            "every 11m",
+            "every 30s",
+            "every 1h at :60",
+            "every 1h at 1:00",
+            "every 1h after :30",
+            "Sat",
+            "Sat 24:00",
+            "someday 12:00",
+        ] {
+            assert!(parse_recurrence(expression).is_err(), "{:?} should be rejected", expression);
+        }
+    }
+
+    #[test]
+    fn huge_numbers_are_rejected_without_overflowing() {
+        assert!(parse_recurrence("every 9223372036854775807 weeks").is_err());
+        assert!(parse_recurrence("every 9223372036854775807w").is_err());
+        assert!(parse_recurrence("every 1w at 9223372036854775807:00").is_err());
+    }
+
+    #[test]
+    fn spawns_per_cycle_are_capped() {
+        // Sharing a weekly cycle: 672 + 1 spawns fit, 1008 + 1 don't
+        assert_eq!(offsets("every 15m; Sat 18:05").1.len(), 673);
+        assert!(parse_recurrence("every 10m; Sat 18:05").is_err());
+    }
+
+    #[test]
+    fn cycle_offsets_convert_local_time_to_the_base_time() {
+        let recurrence = parse_recurrence("daily 18:00").unwrap();
+        // UTC+2: 18:00 local is 16:00 UTC
+        assert_eq!(recurrence.cycle_offsets(0, 2 * HOUR), vec![16 * HOUR]);
+        // UTC-5: 18:00 local is 23:00 UTC
+        assert_eq!(recurrence.cycle_offsets(0, -5 * HOUR), vec![23 * HOUR]);
+        // A base time past the spawn wraps around into the cycle
+        assert_eq!(recurrence.cycle_offsets(20 * HOUR, 0), vec![22 * HOUR]);
+
+        let recurrence = parse_recurrence("every 1h at :00; every 1h at :45").unwrap();
+        assert_eq!(recurrence.cycle_offsets(30 * MINUTE, 0), vec![15 * MINUTE, 30 * MINUTE]);
+    }
+}
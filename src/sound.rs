@@ -0,0 +1,26 @@
+// Short beeps for the final-seconds countdown.
+//
+// Beep blocks for its duration, so it runs on a worker thread to keep the render thread free.
+
+const TICK: (u32, u32) = (880, 60);
+const SPAWN: (u32, u32) = (1320, 250);
+
+/// Play a countdown tick, or the longer, higher spawn beep
+pub fn countdown_beep(spawn: bool) {
+    let (frequency, duration_ms) = if spawn { SPAWN } else { TICK };
+    std::thread::spawn(move || play(frequency, duration_ms));
+}
+
+#[cfg(windows)]
+fn play(frequency: u32, duration_ms: u32) {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn Beep(frequency: u32, duration: u32) -> i32;
+    }
+    unsafe {
+        Beep(frequency, duration_ms);
+    }
+}
+
+#[cfg(not(windows))]
+fn play(_frequency: u32, _duration_ms: u32) {}
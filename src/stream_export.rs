@@ -0,0 +1,119 @@
+// Upcoming tracked events written to the addon folder for stream overlays: stream/upcoming.json
+// for browser sources and stream/upcoming.txt for text sources reading from a file. The same JSON
+// is handed to the local HTTP endpoint.
+
+use nexus::paths::get_addon_dir;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::addon_log;
+use crate::http_server;
+use crate::notifications::NotificationState;
+use crate::time_utils::format_duration;
+
+/// When the files were last written (unix seconds)
+static LAST_WRITE: Lazy<Mutex<i64>> = Lazy::new(|| Mutex::new(0));
+
+/// The last write failed; logged once until a write succeeds again
+static WRITE_FAILED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize)]
+struct ExportedEvent<'a> {
+    event: &'a str,
+    track: &'a str,
+    category: &'a str,
+    start_time: i64,
+    /// Until the start, 0 while running
+    seconds_until: i64,
+    /// Left in the running occurrence, 0 before it starts
+    seconds_left: i64,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    map_name: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    copy_text: &'a str,
+}
+
+#[derive(Serialize)]
+struct Export<'a> {
+    generated_at: i64,
+    events: Vec<ExportedEvent<'a>>,
+}
+
+impl ExportedEvent<'_> {
+    /// "Tequatl in 12m" or "Tequatl now, 5m left"
+    fn line(&self) -> String {
+        if self.seconds_until > 0 {
+            format!("{} in {}", self.event, format_duration(self.seconds_until))
+        } else {
+            format!("{} now, {} left", self.event, format_duration(self.seconds_left))
+        }
+    }
+}
+
+/// Export the first `count` upcoming events, at most once every `interval` seconds, to the
+/// files and/or the HTTP endpoint
+pub fn update(state: &NotificationState, current_time: i64, interval: u32, count: usize, to_files: bool) {
+    {
+        let mut last = LAST_WRITE.lock();
+        if current_time - *last < interval.max(1) as i64 {
+            return;
+        }
+        *last = current_time;
+    }
+
+    let events: Vec<ExportedEvent> = state
+        .upcoming_events
+        .iter()
+        .filter(|upcoming| !upcoming.unowned)
+        .take(count)
+        .map(|upcoming| {
+            let (event, track) = state.labels(&upcoming.event_id);
+            ExportedEvent {
+                event,
+                track,
+                category: &upcoming.category,
+                start_time: upcoming.start_time,
+                seconds_until: upcoming.seconds_until.max(0),
+                seconds_left: if upcoming.seconds_until > 0 { 0 } else { upcoming.duration - upcoming.seconds_into },
+                map_name: &upcoming.map_name,
+                copy_text: &upcoming.copy_text,
+            }
+        })
+        .collect();
+
+    let text: String = events.iter().map(|event| event.line() + "\n").collect();
+    let json = match serde_json::to_string_pretty(&Export { generated_at: current_time, events }) {
+        Ok(json) => json,
+        Err(e) => {
+            addon_log::error(format!("Failed to serialize stream export: {}", e));
+            return;
+        }
+    };
+
+    http_server::publish(&json);
+    if to_files {
+        // Small files, but a slow disk shouldn't cost frames
+        std::thread::spawn(move || write(json, text));
+    }
+}
+
+fn write(json: String, text: String) {
+    let Some(dir) = get_addon_dir("event_timers").map(|dir| dir.join("stream")) else {
+        return;
+    };
+    let result = fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(dir.join("upcoming.json"), json))
+        .and_then(|_| fs::write(dir.join("upcoming.txt"), text));
+
+    match result {
+        Ok(()) => WRITE_FAILED.store(false, Ordering::Relaxed),
+        Err(e) => {
+            if !WRITE_FAILED.swap(true, Ordering::Relaxed) {
+                addon_log::warn(format!("Could not write the stream export to {}: {}", dir.display(), e));
+            }
+        }
+    }
+}
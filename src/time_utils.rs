@@ -1,10 +1,122 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::addon_log;
+
+// Planning mode: the UI shows the clock shifted by this many seconds
+static SIMULATION_ACTIVE: AtomicBool = AtomicBool::new(false);
+static SIMULATION_OFFSET: AtomicI64 = AtomicI64::new(0);
+
+/// Backward steps up to this many seconds (NTP nudges) are absorbed by counting on the
+/// monotonic clock until the system clock catches up
+const CLOCK_SKEW_TOLERANCE: i64 = 5;
+
+/// A system clock earlier than this (the Tyria time reference) is certainly set wrong
+const EARLIEST_PLAUSIBLE_TIME: i64 = 1759264200;
+
+/// How long a clock warning stays up after it was last seen
+const CLOCK_WARNING_DURATION: Duration = Duration::from_secs(300);
+
+/// Something wrong with the system clock that may throw event times off
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClockWarning {
+    /// The clock was set back by this many seconds
+    MovedBack(i64),
+    /// The clock couldn't be read; time is carried forward from the last good reading
+    Unreadable,
+    /// The clock reads a date before this version of the addon existed
+    Implausible,
+}
+
+impl ClockWarning {
+    pub fn message(self) -> String {
+        match self {
+            Self::MovedBack(seconds) => format!("System clock moved back {}, event times may be off", format_duration(seconds)),
+            Self::Unreadable => "System clock can't be read, event times are estimated".to_string(),
+            Self::Implausible => "System clock looks wrong, event times will be off".to_string(),
+        }
+    }
+}
+
+/// Last clock reading against a monotonic instant, to carry time forward when the system clock
+/// fails and to notice it jumping backwards
+struct ClockState {
+    anchor: Option<(Instant, i64)>,
+    warning: Option<(ClockWarning, Instant)>,
+}
+
+static CLOCK: Lazy<Mutex<ClockState>> = Lazy::new(|| Mutex::new(ClockState { anchor: None, warning: None }));
 
 pub fn get_current_unix_time() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as i64
+    let now = Instant::now();
+    let mut clock = CLOCK.lock();
+    let estimate = clock.anchor.map(|(at, unix)| unix + now.duration_since(at).as_secs() as i64);
+
+    let (time, warning) = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(wall) => {
+            let wall = wall.as_secs() as i64;
+            match estimate {
+                // Small corrections: keep counting from the last reading instead of going back
+                Some(estimate) if wall < estimate && estimate - wall <= CLOCK_SKEW_TOLERANCE => (estimate, None),
+                Some(estimate) if wall < estimate => (wall, Some(ClockWarning::MovedBack(estimate - wall))),
+                _ if wall < EARLIEST_PLAUSIBLE_TIME => (wall, Some(ClockWarning::Implausible)),
+                _ => (wall, None),
+            }
+        }
+        Err(_) => (estimate.unwrap_or(EARLIEST_PLAUSIBLE_TIME), Some(ClockWarning::Unreadable)),
+    };
+
+    // Estimated readings keep the old anchor, so time goes on advancing from it
+    if estimate != Some(time) || clock.anchor.is_none() {
+        clock.anchor = Some((now, time));
+    }
+    let Some(warning) = warning else {
+        return time;
+    };
+    let is_new = clock.warning.is_none_or(|(previous, _)| previous != warning);
+    clock.warning = Some((warning, now));
+    drop(clock);
+
+    // Logged outside the lock: the log stamps entries with this clock
+    if is_new {
+        addon_log::warn(warning.message());
+    }
+    time
+}
+
+/// Current clock problem, if one was seen in the last few minutes
+pub fn clock_warning() -> Option<ClockWarning> {
+    CLOCK.lock()
+        .warning
+        .filter(|(_, seen)| seen.elapsed() < CLOCK_WARNING_DURATION)
+        .map(|(warning, _)| warning)
+}
+
+/// Time the timeline and Upcoming list are rendered at: the real clock, or the
+/// simulated moment in planning mode (which keeps ticking from where it was set)
+pub fn get_display_time() -> i64 {
+    if is_time_simulated() {
+        get_current_unix_time() + SIMULATION_OFFSET.load(Ordering::Relaxed)
+    } else {
+        get_current_unix_time()
+    }
+}
+
+/// Enter planning mode at `target`, or return to live time with None
+pub fn set_simulated_time(target: Option<i64>) {
+    match target {
+        Some(target) => {
+            SIMULATION_OFFSET.store(target - get_current_unix_time(), Ordering::Relaxed);
+            SIMULATION_ACTIVE.store(true, Ordering::Relaxed);
+        }
+        None => SIMULATION_ACTIVE.store(false, Ordering::Relaxed),
+    }
+}
+
+pub fn is_time_simulated() -> bool {
+    SIMULATION_ACTIVE.load(Ordering::Relaxed)
 }
 
 pub fn calculate_tyria_time(utc_timestamp: i64) -> (i32, i32) {
@@ -37,4 +149,110 @@ pub fn format_time_only(timestamp: i64) -> String {
     let datetime = DateTime::from_timestamp(timestamp, 0)
         .expect("Invalid timestamp");
     datetime.with_timezone(&Local).format("%H:%M").to_string()
-}
\ No newline at end of file
+}
+
+/// Current local UTC offset in seconds (east of UTC is positive)
+pub fn local_utc_offset_seconds() -> i64 {
+    use chrono::Local;
+    Local::now().offset().local_minus_utc() as i64
+}
+
+/// Local midnight starting the day that contains `timestamp`
+pub fn local_day_start(timestamp: i64) -> i64 {
+    timestamp - (timestamp + local_utc_offset_seconds()).rem_euclid(24 * 3600)
+}
+
+/// Local date and time, e.g. "Sat 2025-10-04 21:00"
+pub fn format_date_time(timestamp: i64) -> String {
+    use chrono::{DateTime, Local};
+    let datetime = DateTime::from_timestamp(timestamp, 0)
+        .expect("Invalid timestamp");
+    datetime.with_timezone(&Local).format("%a %Y-%m-%d %H:%M").to_string()
+}
+
+/// Local time with seconds, e.g. "21:04:09"
+pub fn format_time_seconds(timestamp: i64) -> String {
+    use chrono::{DateTime, Local};
+    let datetime = DateTime::from_timestamp(timestamp, 0)
+        .expect("Invalid timestamp");
+    datetime.with_timezone(&Local).format("%H:%M:%S").to_string()
+}
+
+/// Weekday and local time, e.g. "Sat 21:00"
+pub fn format_weekday_time(timestamp: i64) -> String {
+    use chrono::{DateTime, Local};
+    let datetime = DateTime::from_timestamp(timestamp, 0)
+        .expect("Invalid timestamp");
+    datetime.with_timezone(&Local).format("%a %H:%M").to_string()
+}
+
+/// Like format_time_only, but includes seconds when the timestamp isn't on a whole minute
+pub fn format_time_precise(timestamp: i64) -> String {
+    use chrono::{DateTime, Local};
+    let datetime = DateTime::from_timestamp(timestamp, 0)
+        .expect("Invalid timestamp");
+    let format = if timestamp % 60 == 0 { "%H:%M" } else { "%H:%M:%S" };
+    datetime.with_timezone(&Local).format(format).to_string()
+}
+
+/// Format a duration for display: "45s", "12m", "12m 30s", "1h 5m"
+pub fn format_duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else if seconds < 3600 {
+        let mins = seconds / 60;
+        let secs = seconds % 60;
+        if secs > 0 {
+            format!("{}m {}s", mins, secs)
+        } else {
+            format!("{}m", mins)
+        }
+    } else {
+        let hours = seconds / 3600;
+        let mins = (seconds % 3600) / 60;
+        format!("{}h {}m", hours, mins)
+    }
+}
+
+/// Parse a local "YYYY-MM-DD HH:MM" date/time, or a bare "HH:MM" (next time that clock time comes up)
+pub fn parse_local_datetime(text: &str) -> Option<i64> {
+    use chrono::{Duration, Local, NaiveDateTime, NaiveTime, TimeZone};
+    let text = text.trim();
+
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M") {
+        return Local.from_local_datetime(&datetime).earliest().map(|d| d.timestamp());
+    }
+
+    let time = NaiveTime::parse_from_str(text, "%H:%M").ok()?;
+    let now = Local::now();
+    let today = Local.from_local_datetime(&now.date_naive().and_time(time)).earliest()?;
+    let target = if today <= now { today + Duration::days(1) } else { today };
+    Some(target.timestamp())
+}
+
+/// Format seconds as "mm:ss" for editing (minutes are not wrapped into hours)
+pub fn format_mmss(seconds: i64) -> String {
+    let sign = if seconds < 0 { "-" } else { "" };
+    let seconds = seconds.abs();
+    format!("{}{}:{:02}", sign, seconds / 60, seconds % 60)
+}
+
+/// Parse "mm", "mm:ss" or "h:mm:ss" into seconds; negative times are rejected
+pub fn parse_mmss(text: &str) -> Option<i64> {
+    let parts: Vec<i64> = text
+        .trim()
+        .split(':')
+        .map(|p| p.trim().parse::<i64>().ok())
+        .collect::<Option<_>>()?;
+    if parts.iter().any(|&p| p < 0) {
+        return None;
+    }
+
+    match parts.as_slice() {
+        [minutes] => Some(minutes * 60),
+        [minutes, secs] if *secs < 60 => Some(minutes * 60 + secs),
+        [hours, minutes, secs] if *minutes < 60 && *secs < 60 => Some(hours * 3600 + minutes * 60 + secs),
+        _ => None,
+    }
+}
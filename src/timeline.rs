@@ -0,0 +1,303 @@
+// Occurrence math for timeline events.
+//
+// An event spawns at each of its cycle offsets, once per `cycle_duration`, counted from
+// the track's base time; an occurrence is kept only when it passes the event's condition.
+// Everything here is a pure function of (event, base_time, time), so the timeline, the
+// notifications, the agenda and the exports all agree on when an event runs - including
+// occurrences that straddle a cycle boundary (offset + duration past the end of the cycle).
+
+use crate::condition::MAX_LOOKAHEAD_CYCLES;
+use crate::json_loader::TimelineEvent;
+
+/// Start of the cycle containing `time`
+fn cycle_start(event: &TimelineEvent, base_time: i64, time: i64) -> i64 {
+    time - (time - base_time).rem_euclid(event.cycle_duration)
+}
+
+/// Every spawn of one cycle, unsorted and unfiltered
+fn spawns_in_cycle(event: &TimelineEvent, cycle_start: i64) -> impl Iterator<Item = i64> + '_ {
+    let cycle = event.cycle_duration;
+    event.cycle_offsets().iter().map(move |&offset| cycle_start + offset.rem_euclid(cycle))
+}
+
+/// Start times of the occurrences starting in [from, until). Cycles come in order, but the
+/// spawns within one cycle are not sorted. Cheap enough to call per frame (no allocation).
+pub fn next_occurrences(event: &TimelineEvent, base_time: i64, from: i64, until: i64) -> impl Iterator<Item = i64> + '_ {
+    let cycle = event.cycle_duration.max(1);
+    let first_cycle = if event.cycle_duration > 0 { cycle_start(event, base_time, from) } else { until };
+    (0..)
+        .map(move |k| first_cycle + k * cycle)
+        .take_while(move |&start| start < until)
+        .flat_map(move |start| spawns_in_cycle(event, start))
+        .filter(move |&start| start >= from && start < until && event.occurs_at(start))
+}
+
+/// Start times of the first `count` occurrences at or after `from`, sorted.
+/// Gives up after MAX_LOOKAHEAD_CYCLES cycles, so rare conditions may return fewer.
+pub fn upcoming_occurrences(event: &TimelineEvent, base_time: i64, from: i64, count: usize) -> Vec<i64> {
+    let mut starts = Vec::new();
+    if event.cycle_duration <= 0 || count == 0 {
+        return starts;
+    }
+
+    let mut start = cycle_start(event, base_time, from);
+    for _ in 0..=MAX_LOOKAHEAD_CYCLES {
+        starts.extend(spawns_in_cycle(event, start).filter(|&s| s >= from && event.occurs_at(s)));
+        if starts.len() >= count {
+            break;
+        }
+        start += event.cycle_duration;
+    }
+    starts.sort_unstable();
+    starts.truncate(count);
+    starts
+}
+
+/// Start of the first occurrence strictly after `after`
+pub fn next_occurrence_after(event: &TimelineEvent, base_time: i64, after: i64) -> Option<i64> {
+    upcoming_occurrences(event, base_time, after + 1, 1).first().copied()
+}
+
+/// Start of the occurrence running at `time` (the latest one, should occurrences overlap)
+pub fn active_occurrence(event: &TimelineEvent, base_time: i64, time: i64) -> Option<i64> {
+    if event.cycle_duration <= 0 || event.duration <= 0 {
+        return None;
+    }
+
+    // Walk back over every cycle an occurrence still running at `time` could have started in,
+    // one more for an occurrence that started late in its cycle and runs past the end
+    let cycles_back = (event.duration - 1) / event.cycle_duration + 1;
+    let first_cycle = cycle_start(event, base_time, time) - cycles_back * event.cycle_duration;
+    (0..=cycles_back)
+        .flat_map(|k| spawns_in_cycle(event, first_cycle + k * event.cycle_duration))
+        .filter(|&start| start <= time && time < start + event.duration && event.occurs_at(start))
+        .max()
+}
+
+/// Whether the occurrence starting at `start` is the last of several its event has between
+/// two daily resets (00:00 UTC). Events that run once a day or less never are.
+pub fn is_last_before_reset(event: &TimelineEvent, base_time: i64, start: i64) -> bool {
+    const DAY: i64 = 24 * 3600;
+    let reset = start - start.rem_euclid(DAY);
+    next_occurrences(event, base_time, reset, start).next().is_some()
+        && next_occurrences(event, base_time, start + 1, reset + DAY).next().is_none()
+}
+
+/// The running occurrence if there is one, otherwise the next one to start
+pub fn current_or_next(event: &TimelineEvent, base_time: i64, time: i64) -> Option<i64> {
+    active_occurrence(event, base_time, time).or_else(|| next_occurrence_after(event, base_time, time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2025-10-01 00:00 UTC
+    const BASE: i64 = 1_759_276_800;
+    const HOUR: i64 = 3600;
+
+    fn event(offsets: &[i64], duration: i64, cycle: i64) -> TimelineEvent {
+        TimelineEvent {
+            start_offset: offsets[0],
+            spawn_offsets: if offsets.len() > 1 { offsets.to_vec() } else { Vec::new() },
+            duration,
+            cycle_duration: cycle,
+            ..Default::default()
+        }
+    }
+
+    fn with_condition(mut event: TimelineEvent, condition: &str) -> TimelineEvent {
+        event.condition = condition.to_string();
+        event.compile_condition().unwrap();
+        event
+    }
+
+    fn sorted(starts: impl Iterator<Item = i64>) -> Vec<i64> {
+        let mut starts: Vec<i64> = starts.collect();
+        starts.sort_unstable();
+        starts
+    }
+
+    #[test]
+    fn next_occurrences_covers_the_range() {
+        let e = event(&[600], 900, 2 * HOUR);
+        assert_eq!(
+            sorted(next_occurrences(&e, BASE, BASE, BASE + 6 * HOUR)),
+            vec![BASE + 600, BASE + 2 * HOUR + 600, BASE + 4 * HOUR + 600]
+        );
+        // `until` is exclusive, `from` inclusive
+        assert_eq!(sorted(next_occurrences(&e, BASE, BASE + 600, BASE + 2 * HOUR + 600)), vec![BASE + 600]);
+        assert_eq!(next_occurrences(&event(&[0], 900, 0), BASE, BASE, BASE + HOUR).count(), 0);
+    }
+
+    #[test]
+    fn times_before_base_time() {
+        let e = event(&[600], 900, 2 * HOUR);
+        assert_eq!(
+            sorted(next_occurrences(&e, BASE, BASE - 4 * HOUR, BASE)),
+            vec![BASE - 4 * HOUR + 600, BASE - 2 * HOUR + 600]
+        );
+        assert_eq!(active_occurrence(&e, BASE, BASE - 2 * HOUR + 700), Some(BASE - 2 * HOUR + 600));
+        assert_eq!(active_occurrence(&e, BASE, BASE - 2 * HOUR + 1500), None);
+        assert_eq!(next_occurrence_after(&e, BASE, BASE - HOUR), Some(BASE + 600));
+        assert_eq!(current_or_next(&e, BASE, BASE - 10), Some(BASE + 600));
+    }
+
+    #[test]
+    fn occurrence_straddling_the_cycle_end() {
+        // Starts 5 minutes before the end of its cycle and runs 10 minutes into the next one
+        let e = event(&[2 * HOUR - 300], 600, 2 * HOUR);
+        let start = BASE + 2 * HOUR - 300;
+        assert_eq!(active_occurrence(&e, BASE, BASE + 2 * HOUR + 100), Some(start));
+        assert_eq!(current_or_next(&e, BASE, BASE + 2 * HOUR + 100), Some(start));
+        // The end is exclusive
+        assert_eq!(active_occurrence(&e, BASE, start + 600), None);
+        assert_eq!(current_or_next(&e, BASE, start + 600), Some(start + 2 * HOUR));
+        assert_eq!(next_occurrence_after(&e, BASE, start), Some(start + 2 * HOUR));
+    }
+
+    #[test]
+    fn longer_than_its_cycle() {
+        // A new spawn every 10 minutes, each running 25: the latest one that's running wins
+        let e = event(&[0], 1500, 600);
+        assert_eq!(active_occurrence(&e, BASE, BASE + 1300), Some(BASE + 1200));
+        assert_eq!(active_occurrence(&e, BASE, BASE + 1200), Some(BASE + 1200));
+        assert_eq!(active_occurrence(&e, BASE, BASE + 1199), Some(BASE + 600));
+    }
+
+    #[test]
+    fn several_offsets_per_cycle() {
+        // Offsets needn't be sorted
+        let e = event(&[5400, 0, 1800], 600, 2 * HOUR);
+        assert_eq!(
+            sorted(next_occurrences(&e, BASE, BASE + 1, BASE + 2 * HOUR + 1)),
+            vec![BASE + 1800, BASE + 5400, BASE + 2 * HOUR]
+        );
+        assert_eq!(
+            upcoming_occurrences(&e, BASE, BASE + 1, 4),
+            vec![BASE + 1800, BASE + 5400, BASE + 2 * HOUR, BASE + 2 * HOUR + 1800]
+        );
+        assert_eq!(next_occurrence_after(&e, BASE, BASE + 1800), Some(BASE + 5400));
+        assert_eq!(active_occurrence(&e, BASE, BASE + 1900), Some(BASE + 1800));
+        assert_eq!(current_or_next(&e, BASE, BASE + 2500), Some(BASE + 5400));
+    }
+
+    #[test]
+    fn condition_filters_occurrences() {
+        let e = with_condition(event(&[0], 900, HOUR), "utc_hour == 2 || utc_hour == 5");
+        assert_eq!(
+            sorted(next_occurrences(&e, BASE, BASE, BASE + 24 * HOUR)),
+            vec![BASE + 2 * HOUR, BASE + 5 * HOUR]
+        );
+        assert_eq!(upcoming_occurrences(&e, BASE, BASE, 3), vec![BASE + 2 * HOUR, BASE + 5 * HOUR, BASE + 26 * HOUR]);
+        assert_eq!(next_occurrence_after(&e, BASE, BASE + 2 * HOUR), Some(BASE + 5 * HOUR));
+        // The midnight occurrence is filtered out, so nothing is running
+        assert_eq!(active_occurrence(&e, BASE, BASE + 100), None);
+        assert_eq!(current_or_next(&e, BASE, BASE + 100), Some(BASE + 2 * HOUR));
+        assert_eq!(active_occurrence(&e, BASE, BASE + 2 * HOUR + 100), Some(BASE + 2 * HOUR));
+
+        let never = with_condition(event(&[0], 900, HOUR), "utc_hour == 25");
+        assert!(upcoming_occurrences(&never, BASE, BASE, 1).is_empty());
+        assert_eq!(current_or_next(&never, BASE, BASE), None);
+    }
+
+    /// xorshift64*, so the property checks below are reproducible without a dependency
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+        }
+
+        /// Uniform in [from, to)
+        fn range(&mut self, from: i64, to: i64) -> i64 {
+            from + (self.next() % (to - from) as u64) as i64
+        }
+    }
+
+    /// Random events (offsets distinct within the cycle, possibly outside it) with a base time
+    /// and a time to look from, which is often right at a spawn or a cycle boundary
+    fn cases(count: usize) -> Vec<(TimelineEvent, i64, i64)> {
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+        (0..count)
+            .map(|_| {
+                let cycle = match rng.range(0, 7) {
+                    0 => 1,
+                    1 => 60,
+                    2 => 900,
+                    3 => 2 * HOUR,
+                    4 => 24 * HOUR,
+                    5 => 7 * 24 * HOUR,
+                    _ => rng.range(2, 100_000),
+                };
+                let mut offsets: Vec<i64> = Vec::new();
+                for _ in 0..rng.range(1, 5) {
+                    let offset = rng.range(-cycle, 2 * cycle);
+                    if offsets.iter().all(|o| (o - offset).rem_euclid(cycle) != 0) {
+                        offsets.push(offset);
+                    }
+                }
+                let duration = rng.range(1, 3 * cycle + 1);
+                let base_time = rng.range(0, 2_000_000_000);
+
+                let cycle_number = rng.range(-10, 10);
+                let nudge = rng.range(-1, 2);
+                let now = match rng.range(0, 3) {
+                    0 => base_time + cycle_number * cycle + offsets[0] + nudge,
+                    1 => base_time + cycle_number * cycle + nudge,
+                    _ => base_time + rng.range(-10 * cycle, 10 * cycle),
+                };
+                (event(&offsets, duration, cycle), base_time, now)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn property_next_occurrence_is_after_now() {
+        for (e, base, now) in cases(3000) {
+            let next = next_occurrence_after(&e, base, now).unwrap();
+            assert!(next > now, "{:?} base {} now {}: next {}", e.cycle_offsets(), base, now, next);
+            assert!(next - now <= e.cycle_duration);
+            assert_eq!(next_occurrences(&e, base, now + 1, next).count(), 0);
+            assert!(upcoming_occurrences(&e, base, now, 1)[0] >= now);
+        }
+    }
+
+    #[test]
+    fn property_occurrences_are_one_period_apart() {
+        for (e, base, now) in cases(3000) {
+            let spawns = e.cycle_offsets().len();
+            let starts = upcoming_occurrences(&e, base, now, 3 * spawns);
+            assert_eq!(starts.len(), 3 * spawns);
+            for (earlier, later) in starts.iter().zip(&starts[spawns..]) {
+                assert_eq!(later - earlier, e.cycle_duration, "{:?} base {} now {}", e.cycle_offsets(), base, now);
+            }
+            assert_eq!(sorted(next_occurrences(&e, base, starts[0], starts[starts.len() - 1] + 1)), starts);
+        }
+    }
+
+    #[test]
+    fn property_active_occurrence_is_the_previous_spawn() {
+        for (mut e, base, now) in cases(3000) {
+            // One spawn per cycle: the running occurrence can only be the one before the next
+            e.spawn_offsets.clear();
+            let previous = next_occurrence_after(&e, base, now).unwrap() - e.cycle_duration;
+            assert!(previous <= now);
+            let expected = (now < previous + e.duration).then_some(previous);
+            assert_eq!(active_occurrence(&e, base, now), expected, "offset {} base {} now {}", e.start_offset, base, now);
+        }
+    }
+
+    #[test]
+    fn property_active_occurrence_matches_a_scan() {
+        for (e, base, now) in cases(3000) {
+            let running = next_occurrences(&e, base, now - e.duration - e.cycle_duration, now + 1)
+                .filter(|&start| now < start + e.duration)
+                .max();
+            assert_eq!(active_occurrence(&e, base, now), running, "{:?} base {} now {}", e.cycle_offsets(), base, now);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// Textures for the per-track icons shown in the label column.
+//
+// Each icon file is handed to Nexus once it exists; until its texture arrives the label is
+// drawn without it.
+
+use nexus::paths::get_addon_dir;
+use nexus::texture::{get_texture, load_texture_from_file, Texture};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::addon_log;
+
+/// Icon paths already requested this session
+static REQUESTED: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Icon paths are absolute or relative to the addon folder
+fn resolve(path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        Some(path.to_path_buf())
+    } else {
+        get_addon_dir("event_timers").map(|dir| dir.join(path))
+    }
+}
+
+/// Texture for a track's icon path, requested from Nexus on first use
+pub fn texture(path: &str) -> Option<&'static Texture> {
+    let path = path.trim();
+    if path.is_empty() {
+        return None;
+    }
+
+    let id = format!("EVENT_TIMERS_TRACK_ICON_{}", path);
+    let mut requested = REQUESTED.lock();
+    if !requested.contains(path) {
+        // Missing files (or paths still being typed) are checked again next frame
+        let file = resolve(path).filter(|file| file.is_file())?;
+        load_texture_from_file(&id, file, None);
+        addon_log::info(format!("Loading track icon {}", path));
+        requested.insert(path.to_string());
+    }
+    get_texture(&id)
+}
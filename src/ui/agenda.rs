@@ -0,0 +1,195 @@
+use nexus::imgui::{Condition, TreeNodeFlags, Ui, Window};
+
+use crate::config::{TrackedEventId, UnownedContentMode, RUNTIME_CONFIG};
+use crate::time_utils::{format_duration, format_time_only, format_weekday_time, get_display_time, local_utc_offset_seconds};
+use crate::ui::notifications::CONFLICT_COLOR;
+use crate::ui::snapping;
+
+const AGENDA_SPAN: i64 = 24 * 3600;
+
+/// One row of the agenda
+struct AgendaEntry {
+    start: i64,
+    end: i64,
+    track_name: String,
+    event_name: String,
+    map_name: String,
+    copy_text: String,
+    color: [f32; 4],
+    dimmed: bool,
+    tracked: bool,
+    /// Other tracked entries overlapping this one: (event name, overlap in seconds)
+    conflicts: Vec<(String, i64)>,
+}
+
+/// Note the overlaps between tracked entries (sorted by start) on each of them
+fn find_conflicts(entries: &mut [AgendaEntry]) {
+    for i in 0..entries.len() {
+        if !entries[i].tracked {
+            continue;
+        }
+        for j in i + 1..entries.len() {
+            if entries[j].start >= entries[i].end {
+                break;
+            }
+            if !entries[j].tracked {
+                continue;
+            }
+            let overlap = entries[i].end.min(entries[j].end) - entries[j].start;
+            let (first_name, second_name) = (entries[i].event_name.clone(), entries[j].event_name.clone());
+            entries[i].conflicts.push((second_name, overlap));
+            entries[j].conflicts.push((first_name, overlap));
+        }
+    }
+}
+
+/// Render the 24h agenda window (call from main render loop)
+pub fn render_agenda_window(ui: &Ui) {
+    let now = get_display_time();
+
+    let (entries, mut tracked_only, copy_with_event_name, snap_windows, snap_threshold) = {
+        let config = RUNTIME_CONFIG.lock();
+        if !config.show_agenda_window {
+            return;
+        }
+
+        let mut entries = Vec::new();
+        for track in config.tracks.iter().filter(|t| config.is_track_shown(t)) {
+            for (event, start) in track.occurrences_between(now, now + AGENDA_SPAN) {
+                let event_id = TrackedEventId::new(&track.name, &event.name);
+                let tracked = config.tracked_events.contains(&event_id) || config.oneshot_events.contains(&event_id);
+                if config.agenda_tracked_only && !tracked {
+                    continue;
+                }
+
+                let owned = config.owns_expansion(event.required_expansion(track));
+                if !owned && config.unowned_content_mode == UnownedContentMode::Hide {
+                    continue;
+                }
+
+                entries.push(AgendaEntry {
+                    start,
+                    end: start + event.duration,
+                    track_name: track.display_name(config.event_language).to_string(),
+                    event_name: event.display_name(config.event_language).to_string(),
+                    map_name: event.map_name.clone(),
+                    copy_text: event.copy_text.clone(),
+                    color: event.color.to_array(),
+                    dimmed: !owned,
+                    tracked,
+                    conflicts: Vec::new(),
+                });
+            }
+        }
+        entries.sort_by_key(|e| e.start);
+        find_conflicts(&mut entries);
+
+        (entries, config.agenda_tracked_only, config.copy_with_event_name, config.snap_windows, config.snap_threshold)
+    };
+
+    let mut opened = true;
+    let mut copy_text_to_set: Option<String> = None;
+    let filter_before = tracked_only;
+
+    let mut window = Window::new("Agenda")
+        .size([380.0, 500.0], Condition::FirstUseEver)
+        .opened(&mut opened);
+    if let Some(pos) = snapping::take_pending_position("Agenda") {
+        window = window.position(pos, Condition::Always);
+    }
+
+    window.build(ui, || {
+        snapping::track_window(ui, "Agenda", snap_windows, snap_threshold);
+
+        ui.checkbox("Tracked events only", &mut tracked_only);
+        ui.same_line();
+        ui.text_disabled(format!("({} in the next 24h)", entries.len()));
+        ui.separator();
+
+        if entries.is_empty() {
+            ui.text_disabled("Nothing scheduled");
+            return;
+        }
+
+        // Group by local clock hour
+        let utc_offset = local_utc_offset_seconds();
+        let mut index = 0;
+        while index < entries.len() {
+            let hour_start = entries[index].start - (entries[index].start + utc_offset).rem_euclid(3600);
+            let group_end = entries[index..]
+                .iter()
+                .position(|e| e.start >= hour_start + 3600)
+                .map_or(entries.len(), |offset| index + offset);
+
+            let header = format!("{} ({})##agenda_{}", format_weekday_time(hour_start), group_end - index, hour_start);
+            if ui.collapsing_header(&header, TreeNodeFlags::DEFAULT_OPEN) {
+                for (row, entry) in entries[index..group_end].iter().enumerate() {
+                    let alpha = if entry.dimmed { 0.4 } else { 1.0 };
+                    let draw_list = ui.get_window_draw_list();
+                    let cursor_pos = ui.cursor_screen_pos();
+                    draw_list
+                        .add_rect(
+                            cursor_pos,
+                            [cursor_pos[0] + 4.0, cursor_pos[1] + ui.text_line_height()],
+                            [entry.color[0], entry.color[1], entry.color[2], entry.color[3] * alpha],
+                        )
+                        .filled(true)
+                        .build();
+                    ui.set_cursor_pos([ui.cursor_pos()[0] + 8.0, ui.cursor_pos()[1]]);
+
+                    ui.text_colored([1.0, 1.0, 1.0, alpha], format_time_only(entry.start));
+                    ui.same_line();
+                    ui.text_colored([1.0, 1.0, 1.0, alpha], &entry.event_name);
+                    let mut hovered = ui.is_item_hovered();
+                    if !entry.conflicts.is_empty() {
+                        ui.same_line();
+                        ui.text_colored(CONFLICT_COLOR, "(!)");
+                        hovered |= ui.is_item_hovered();
+                    }
+                    if hovered {
+                        ui.tooltip(|| {
+                            ui.text(format!("{}: {}", entry.track_name, entry.event_name));
+                            for (other, overlap) in &entry.conflicts {
+                                ui.text_colored(
+                                    CONFLICT_COLOR,
+                                    format!("{} overlaps {} by {}", entry.event_name, other, format_duration(*overlap)),
+                                );
+                            }
+                        });
+                    }
+                    if !entry.map_name.is_empty() {
+                        ui.same_line();
+                        ui.text_disabled(format!("({})", entry.map_name));
+                    }
+
+                    if !entry.copy_text.is_empty() {
+                        ui.same_line();
+                        if ui.small_button(format!("Copy##agenda_{}_{}", hour_start, row)) {
+                            copy_text_to_set = Some(if copy_with_event_name {
+                                format!("{}: {}", entry.event_name, entry.copy_text)
+                            } else {
+                                entry.copy_text.clone()
+                            });
+                        }
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text(format!("Copy waypoint: {}", entry.copy_text));
+                        }
+                    }
+                }
+            }
+
+            index = group_end;
+        }
+    });
+
+    // Copy to clipboard outside of lock
+    if let Some(text) = copy_text_to_set {
+        ui.set_clipboard_text(&text);
+    }
+
+    if !opened || tracked_only != filter_before {
+        let mut config = RUNTIME_CONFIG.lock();
+        config.show_agenda_window = opened;
+        config.agenda_tracked_only = tracked_only;
+    }
+}
@@ -0,0 +1,108 @@
+use nexus::imgui::{Condition, TableFlags, Ui, Window};
+
+use crate::config::RUNTIME_CONFIG;
+use crate::diagnostics::{self, Section, FRAME_STATS};
+
+/// Render the frame-cost overlay (call from main render loop, after everything it measures)
+pub fn render_diagnostics_overlay(ui: &Ui) {
+    let (enabled, track_count, event_count, tracked_count) = {
+        let config = RUNTIME_CONFIG.lock();
+        (
+            config.show_diagnostics,
+            config.tracks.len(),
+            config.tracks.iter().map(|t| t.events.len()).sum::<usize>(),
+            config.tracked_events.len() + config.oneshot_events.len(),
+        )
+    };
+
+    diagnostics::set_enabled(enabled);
+    if !enabled {
+        return;
+    }
+
+    let mut opened = true;
+    let mut report: Option<String> = None;
+
+    Window::new("Event Timers Diagnostics")
+        .size([340.0, 220.0], Condition::FirstUseEver)
+        .opened(&mut opened)
+        .build(ui, || {
+            let stats = FRAME_STATS.lock();
+            let framerate = ui.io().framerate;
+
+            ui.text(format!(
+                "Addon: {:.2} ms/frame (avg {:.2} ms)",
+                stats.total_last_ms, stats.total_average_ms
+            ));
+            ui.text_disabled(format!(
+                "Game: {:.0} FPS ({:.1} ms/frame)",
+                framerate,
+                1000.0 / framerate.max(1.0)
+            ));
+            ui.separator();
+
+            let table_flags = TableFlags::SIZING_STRETCH_PROP | TableFlags::ROW_BG;
+            if let Some(_t) = ui.begin_table_with_flags("##frame_costs", 4, table_flags) {
+                ui.table_setup_column("Section");
+                ui.table_setup_column("Last");
+                ui.table_setup_column("Avg");
+                ui.table_setup_column("Peak 1s");
+                ui.table_headers_row();
+
+                for section in Section::ALL {
+                    let timing = stats.timing(section);
+                    ui.table_next_row();
+                    ui.table_next_column();
+                    ui.text(section.label());
+                    ui.table_next_column();
+                    ui.text(format!("{:.2}", timing.last_ms));
+                    ui.table_next_column();
+                    ui.text(format!("{:.2}", timing.average_ms));
+                    ui.table_next_column();
+                    let peak_color = if timing.peak_ms > 2.0 { [1.0, 0.5, 0.3, 1.0] } else { [1.0, 1.0, 1.0, 1.0] };
+                    ui.text_colored(peak_color, format!("{:.2}", timing.peak_ms));
+                }
+            }
+
+            ui.separator();
+            ui.text_disabled(format!(
+                "{} tracks, {} events, {} tracked",
+                track_count, event_count, tracked_count
+            ));
+
+            if ui.button("Copy Report") {
+                let mut text = format!(
+                    "Event Timers {} | {} tracks, {} events, {} tracked | addon avg {:.2} ms, game {:.0} FPS\n",
+                    env!("CARGO_PKG_VERSION"),
+                    track_count,
+                    event_count,
+                    tracked_count,
+                    stats.total_average_ms,
+                    framerate
+                );
+                for section in Section::ALL {
+                    let timing = stats.timing(section);
+                    text.push_str(&format!(
+                        "{}: last {:.2} / avg {:.2} / peak {:.2} ms\n",
+                        section.label(),
+                        timing.last_ms,
+                        timing.average_ms,
+                        timing.peak_ms
+                    ));
+                }
+                report = Some(text);
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Copy these numbers for a performance report");
+            }
+        });
+
+    if let Some(text) = report {
+        ui.set_clipboard_text(&text);
+    }
+
+    if !opened {
+        RUNTIME_CONFIG.lock().show_diagnostics = false;
+        diagnostics::set_enabled(false);
+    }
+}
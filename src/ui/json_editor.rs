@@ -0,0 +1,193 @@
+use nexus::imgui::{InputTextMultiline, Ui};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::fs;
+
+use crate::addon_log;
+use crate::backups::{self, BackupKind};
+use crate::config::{apply_user_overrides, extract_user_overrides};
+use crate::json_loader::{get_json_path, validate_track_json, validate_tracks_json};
+
+/// Raw event_tracks.json editor state: the whole file or one track's object
+#[derive(Default)]
+struct EditorState {
+    loaded: bool,
+    /// Track being edited (None = whole file)
+    target: Option<String>,
+    /// Track names in the file, in file order
+    track_names: Vec<String>,
+    text: String,
+    /// Last validation/save result: (message, is_error)
+    status: Option<(String, bool)>,
+    /// Full document waiting to be written (saving reloads the config, so it runs before the lock)
+    pending_save: Option<String>,
+}
+
+thread_local! {
+    static EDITOR: RefCell<EditorState> = RefCell::new(EditorState::default());
+}
+
+fn read_document() -> Result<Value, String> {
+    let path = get_json_path().ok_or("Addon directory not available")?;
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("event_tracks.json is not valid JSON: {}", e))
+}
+
+fn tracks_mut(document: &mut Value) -> impl Iterator<Item = &mut Value> {
+    document["categories"]
+        .as_array_mut()
+        .into_iter()
+        .flatten()
+        .filter_map(|category| category["tracks"].as_array_mut())
+        .flatten()
+}
+
+/// Load the current target from disk into the editor
+fn load(state: &mut EditorState) {
+    state.loaded = true;
+    let mut document = match read_document() {
+        Ok(document) => document,
+        Err(e) => {
+            // Still allow fixing a broken file by hand
+            state.target = None;
+            state.track_names.clear();
+            state.text = get_json_path().and_then(|p| fs::read_to_string(p).ok()).unwrap_or_default();
+            state.status = Some((e, true));
+            return;
+        }
+    };
+
+    state.track_names = tracks_mut(&mut document)
+        .filter_map(|track| track["name"].as_str().map(str::to_string))
+        .collect();
+
+    let track_text = state.target.as_ref().and_then(|name| {
+        tracks_mut(&mut document)
+            .find(|t| t["name"].as_str() == Some(name.as_str()))
+            .and_then(|track| serde_json::to_string_pretty(track).ok())
+    });
+    state.text = match track_text {
+        Some(text) => text,
+        None => {
+            // Whole file: edit the text as-is, keeping its formatting
+            state.target = None;
+            get_json_path().and_then(|p| fs::read_to_string(p).ok()).unwrap_or_default()
+        }
+    };
+}
+
+/// Check the editor text against the schema and build the full document to write
+fn validate(state: &EditorState) -> Result<String, String> {
+    let describe = |e: serde_json::Error| format!("Line {}, column {}: {}", e.line(), e.column(), e);
+
+    match &state.target {
+        None => {
+            validate_tracks_json(&state.text).map_err(describe)?;
+            Ok(state.text.clone())
+        }
+        Some(name) => {
+            validate_track_json(&state.text).map_err(describe)?;
+            let edited: Value = serde_json::from_str(&state.text).map_err(describe)?;
+            let mut document = read_document()?;
+            let track = tracks_mut(&mut document)
+                .find(|t| t["name"].as_str() == Some(name.as_str()))
+                .ok_or_else(|| format!("Track '{}' is no longer in the file", name))?;
+            *track = edited;
+            let full = serde_json::to_string_pretty(&document).map_err(|e| e.to_string())?;
+            validate_tracks_json(&full).map_err(describe)?;
+            Ok(full)
+        }
+    }
+}
+
+/// Write a validated document and reload the tracks. Call before locking RUNTIME_CONFIG.
+pub fn apply_pending_save() {
+    let Some(document) = EDITOR.with(|e| e.borrow_mut().pending_save.take()) else {
+        return;
+    };
+
+    let result = (|| {
+        let path = get_json_path().ok_or("Addon directory not available")?;
+        if let Err(e) = backups::create_backup(BackupKind::TrackData) {
+            addon_log::warn(format!("Could not back up event_tracks.json: {}", e));
+        }
+        // Capture customizations against the current defaults before they change
+        extract_user_overrides();
+        fs::write(&path, document).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        apply_user_overrides();
+        Ok::<_, String>(())
+    })();
+
+    let status = match result {
+        Ok(()) => {
+            addon_log::info("event_tracks.json saved from the raw editor and reloaded");
+            ("Saved and reloaded".to_string(), false)
+        }
+        Err(e) => {
+            addon_log::error(&e);
+            (e, true)
+        }
+    };
+    EDITOR.with(|e| {
+        let mut state = e.borrow_mut();
+        state.status = Some(status);
+        state.loaded = false;
+    });
+}
+
+/// Advanced raw JSON editor (settings section)
+pub fn render_json_editor(ui: &Ui) {
+    EDITOR.with(|e| {
+        let mut state = e.borrow_mut();
+        if !state.loaded {
+            load(&mut state);
+        }
+
+        ui.text_disabled("Edit event_tracks.json directly. Changes are validated before saving and applied immediately.");
+        ui.text_disabled("A backup is made on every save. The next update from GitHub replaces local edits.");
+
+        // Target: the whole file or a single track
+        let mut labels = vec!["Whole file".to_string()];
+        labels.extend(state.track_names.iter().cloned());
+        let mut index = state
+            .target
+            .as_ref()
+            .and_then(|name| state.track_names.iter().position(|n| n == name))
+            .map_or(0, |i| i + 1);
+        ui.set_next_item_width(250.0);
+        if ui.combo_simple_string("Edit##raw_json_target", &mut index, &labels) {
+            state.target = if index == 0 { None } else { Some(state.track_names[index - 1].clone()) };
+            state.status = None;
+            load(&mut state);
+        }
+
+        let height = if state.target.is_some() { 300.0 } else { 450.0 };
+        InputTextMultiline::new(ui, "##raw_json", &mut state.text, [-1.0, height])
+            .allow_tab_input(true)
+            .build();
+
+        if ui.button("Validate") {
+            state.status = Some(match validate(&state) {
+                Ok(_) => ("Valid".to_string(), false),
+                Err(e) => (e, true),
+            });
+        }
+        ui.same_line();
+        if ui.button("Save & Reload") {
+            match validate(&state) {
+                Ok(document) => state.pending_save = Some(document),
+                Err(e) => state.status = Some((e, true)),
+            }
+        }
+        ui.same_line();
+        if ui.button("Revert") {
+            state.status = None;
+            load(&mut state);
+        }
+
+        if let Some((message, is_error)) = &state.status {
+            let color = if *is_error { [1.0, 0.4, 0.4, 1.0] } else { [0.5, 0.9, 0.5, 1.0] };
+            ui.text_colored(color, message);
+        }
+    });
+}
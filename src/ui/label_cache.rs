@@ -0,0 +1,39 @@
+// Formatted labels for text drawn every frame (toast lines, Upcoming panel times, the ruler clock).
+//
+// The text only changes when the displayed second does, so labels are formatted once and reused
+// until the second changes, instead of allocating a new string for every label each frame.
+
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use crate::time_utils::get_display_time;
+
+struct LabelCache {
+    second: i64,
+    labels: HashMap<u64, Rc<str>>,
+}
+
+thread_local! {
+    static CACHE: RefCell<LabelCache> = RefCell::new(LabelCache { second: 0, labels: HashMap::new() });
+}
+
+/// The label for `key`, formatted by `format` at most once per displayed second.
+/// Keys must identify everything the text depends on besides the time.
+pub fn label<K: Hash>(key: K, format: impl FnOnce() -> String) -> Rc<str> {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    let key = hasher.finish();
+
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let now = get_display_time();
+        if cache.second != now {
+            cache.second = now;
+            cache.labels.clear();
+        }
+        cache.labels.entry(key).or_insert_with(|| format().into()).clone()
+    })
+}
@@ -0,0 +1,84 @@
+use nexus::imgui::{ChildWindow, Condition, Ui, Window};
+use std::cell::RefCell;
+
+use crate::addon_log::{Level, LOG_BUFFER};
+use crate::config::RUNTIME_CONFIG;
+use crate::time_utils::format_time_seconds;
+
+// Levels shown in the log window (Error, Warning, Info, Debug) and whether to follow new lines
+thread_local! {
+    static LEVEL_FILTER: RefCell<[bool; 4]> = const { RefCell::new([true, true, true, false]) };
+    static AUTO_SCROLL: RefCell<bool> = const { RefCell::new(true) };
+}
+
+/// Render the log window (call from main render loop)
+pub fn render_log_window(ui: &Ui) {
+    if !RUNTIME_CONFIG.lock().show_log_window {
+        return;
+    }
+
+    let mut opened = true;
+    let mut copy_text: Option<String> = None;
+
+    Window::new("Event Timers Log")
+        .size([560.0, 320.0], Condition::FirstUseEver)
+        .opened(&mut opened)
+        .build(ui, || {
+            let mut filter = LEVEL_FILTER.with(|f| *f.borrow());
+            for (index, level) in Level::ALL.iter().enumerate() {
+                if index > 0 {
+                    ui.same_line();
+                }
+                ui.checkbox(level.label(), &mut filter[index]);
+            }
+            LEVEL_FILTER.with(|f| *f.borrow_mut() = filter);
+
+            ui.same_line();
+            let mut auto_scroll = AUTO_SCROLL.with(|a| *a.borrow());
+            ui.checkbox("Auto-scroll", &mut auto_scroll);
+            AUTO_SCROLL.with(|a| *a.borrow_mut() = auto_scroll);
+
+            let shown = |level: Level| filter[Level::ALL.iter().position(|l| *l == level).unwrap_or(0)];
+
+            if ui.button("Clear") {
+                LOG_BUFFER.lock().clear();
+            }
+            ui.same_line();
+            if ui.button("Copy") {
+                let buffer = LOG_BUFFER.lock();
+                let text: Vec<String> = buffer
+                    .iter()
+                    .filter(|entry| shown(entry.level))
+                    .map(|entry| format!("{} [{}] {}", format_time_seconds(entry.timestamp), entry.level.label(), entry.message))
+                    .collect();
+                copy_text = Some(text.join("\n"));
+            }
+            ui.separator();
+
+            ChildWindow::new("##log_lines").build(ui, || {
+                let buffer = LOG_BUFFER.lock();
+                let mut any = false;
+                for entry in buffer.iter().filter(|entry| shown(entry.level)) {
+                    any = true;
+                    ui.text_disabled(format_time_seconds(entry.timestamp));
+                    ui.same_line();
+                    ui.text_colored(entry.level.color(), &entry.message);
+                }
+                if !any {
+                    ui.text_disabled("No log messages");
+                }
+
+                if auto_scroll && ui.scroll_y() >= ui.scroll_max_y() {
+                    ui.set_scroll_here_y_with_ratio(1.0);
+                }
+            });
+        });
+
+    if let Some(text) = copy_text {
+        ui.set_clipboard_text(&text);
+    }
+
+    if !opened {
+        RUNTIME_CONFIG.lock().show_log_window = false;
+    }
+}
@@ -1,14 +1,26 @@
-use crate::config::{get_track_visual_config, LabelColumnPosition, TextAlignment, RUNTIME_CONFIG};
-use crate::json_loader::EventTrack;
+use crate::addon_log;
+use crate::config::{
+    get_track_visual_config, EventLanguage, FinishedTodayMode, LabelColumnPosition, OverviewCycle, RuntimeConfig, TextAlignment, TooltipMode, UnownedContentMode,
+    RUNTIME_CONFIG, SELECTED_EVENT, SELECTED_TRACK,
+};
+use crate::image_export::export_timeline_png;
+use crate::json_loader::{get_base_time_from_calculator, load_error, rejected_update, CopyAction, EventTrack, TimelineEvent};
+use crate::mumble::current_map_id;
 use crate::notification_logic::{toggle_event_tracking, toggle_oneshot_tracking};
-use crate::time_utils::{format_time_only, get_current_unix_time};
-use crate::ui::time_ruler::render_time_ruler;
-use nexus::imgui::{Condition, Key, MenuItem, MouseButton, StyleVar, Ui, Window, WindowFlags};
+use crate::notifications::NOTIFICATION_STATE;
+use crate::timeline;
+use crate::time_utils::{
+    calculate_tyria_time, clock_warning, format_date_time, format_duration, format_time_precise, get_display_time, is_time_simulated,
+    local_day_start, parse_local_datetime, set_simulated_time,
+};
+use crate::ui::{label_cache, snapping};
+use crate::ui::time_ruler::{now_line_hovered, render_cycle_minimap, render_time_ruler, MinimapAction, RulerProbe, NOW_LINE_GRAB};
+use nexus::imgui::{Condition, DrawListMut, InputText, Key, MenuItem, MouseButton, MouseCursor, StyleVar, Ui, Window, WindowFlags};
 use std::cell::RefCell;
 use std::collections::HashSet;
 
 use std::collections::HashSet as StdHashSet;
-use crate::config::TrackedEventId;
+use crate::config::{AttendanceRecord, EventPriority, EventTravelBuffer, TimeMarker, TrackedEventId, ATTENDANCE_DAYS};
 
 // Thread-local storage for right-clicked event info
 // Stores (track_name, event_name, is_currently_tracked, is_oneshot_tracked)
@@ -17,16 +29,83 @@ thread_local! {
     static OPEN_EVENT_MENU: RefCell<bool> = const { RefCell::new(false) };
     static PENDING_TRACK_TOGGLE: RefCell<Option<(String, String, bool)>> = const { RefCell::new(None) }; // (track, event, is_oneshot)
     static PENDING_WIKI_OPEN: RefCell<Option<String>> = const { RefCell::new(None) };
+    // Right-clicked occurrence that "mute until next spawn" applies to: (start_time, is_muted)
+    static CONTEXT_OCCURRENCE: RefCell<Option<(i64, bool)>> = const { RefCell::new(None) };
+    static PENDING_MUTE: RefCell<Option<(TrackedEventId, i64, bool)>> = const { RefCell::new(None) }; // (event, start, mute)
+    // Left-clicked event with several copy options: (event_name, options)
+    static COPY_MENU_EVENT: RefCell<Option<(String, Vec<CopyAction>)>> = const { RefCell::new(None) };
+    static OPEN_COPY_MENU: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    // Double-clicked event to open in the track editor: (track_name, event_name)
+    static PENDING_EDITOR_OPEN: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+    // Event targeted by clicks when several bars overlap under the cursor
+    static TOOLTIP_TARGET: RefCell<Option<TrackedEventId>> = const { RefCell::new(None) };
+    // Clicked track label: (track_name, toggle_visibility) - plain click solos, Ctrl+click hides
+    static PENDING_LABEL_CLICK: RefCell<Option<(String, bool)>> = const { RefCell::new(None) };
+    // Event bar being dragged on an editable track, and the edit to apply under the lock
+    static BAR_DRAG: RefCell<Option<BarDrag>> = const { RefCell::new(None) };
+    static PENDING_BAR_EDIT: RefCell<Option<(String, usize, i64, i64)>> = const { RefCell::new(None) }; // (track, event index, start_offset, duration)
+    // Button clicked on a category header: (category, action)
+    static PENDING_CATEGORY_ACTION: RefCell<Option<(String, CategoryAction)>> = const { RefCell::new(None) };
     // Cached tracked events for the current frame (to avoid re-locking)
     static CACHED_TRACKED_EVENTS: RefCell<StdHashSet<TrackedEventId>> = RefCell::new(StdHashSet::new());
     static CACHED_ONESHOT_EVENTS: RefCell<StdHashSet<TrackedEventId>> = RefCell::new(StdHashSet::new());
     // Cached copy setting for the current frame
     static CACHED_COPY_WITH_EVENT_NAME: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    // Cached tooltip settings for the current frame: (enabled, delay_seconds, mode)
+    static CACHED_TOOLTIP_SETTINGS: std::cell::Cell<(bool, f32, TooltipMode)> = const { std::cell::Cell::new((true, 0.0, TooltipMode::Compact)) };
+    // Cached language of event and track names for the current frame
+    static CACHED_EVENT_LANGUAGE: std::cell::Cell<EventLanguage> = const { std::cell::Cell::new(EventLanguage::English) };
+    // Cached look of non-running occurrences for the current frame
+    static CACHED_OCCURRENCE_STYLE: std::cell::Cell<OccurrenceStyle> = const { std::cell::Cell::new(OccurrenceStyle::DEFAULT) };
+    // Cached names of the tracks whose bars can be dragged for the current frame
+    static CACHED_EDITABLE_TRACKS: RefCell<StdHashSet<String>> = RefCell::new(StdHashSet::new());
+    // Cached accent for last-before-reset occurrences for the current frame (None when off)
+    static CACHED_LAST_CHANCE: std::cell::Cell<Option<[f32; 4]>> = const { std::cell::Cell::new(None) };
+    // Cached enabled time markers for the current frame
+    static CACHED_TIME_MARKERS: RefCell<Vec<TimeMarker>> = const { RefCell::new(Vec::new()) };
+    // Cached attended occurrences for the current frame
+    static CACHED_ATTENDANCE: RefCell<Vec<AttendanceRecord>> = const { RefCell::new(Vec::new()) };
+    // Cached travel buffers for the current frame
+    static CACHED_TRAVEL_BUFFERS: RefCell<Vec<EventTravelBuffer>> = const { RefCell::new(Vec::new()) };
+    // Config snapshot the expansion ownership is read from for the current frame
+    static CACHED_UNOWNED_CONTENT: RefCell<Option<std::sync::Arc<RuntimeConfig>>> = const { RefCell::new(None) };
+    // Seconds the view is panned away from the current time with the cycle minimap (or by the focus)
+    static VIEW_PAN: std::cell::Cell<f32> = const { std::cell::Cell::new(0.0) };
+    // Main window rectangle last frame (pos, size) and its eased opacity, for fading when inactive
+    static WINDOW_RECT: std::cell::Cell<Option<([f32; 2], [f32; 2])>> = const { std::cell::Cell::new(None) };
+    static FADE_ALPHA: std::cell::Cell<f32> = const { std::cell::Cell::new(1.0) };
+    // Size preset picked from the context menu, applied next frame: (size, position)
+    static PENDING_WINDOW_SIZE: RefCell<Option<WindowGeometry>> = const { RefCell::new(None) };
+    // Event whose next occurrence the view keeps in sight (session only)
+    static FOCUS_EVENT: RefCell<Option<TrackedEventId>> = const { RefCell::new(None) };
+    // Date/time typed into the planning mode field of the context menu
+    static PLANNING_INPUT: RefCell<String> = const { RefCell::new(String::new()) };
+    // Result of the last timeline image export: (message, is_error, ImGui time shown)
+    static EXPORT_STATUS: RefCell<Option<(String, bool, f64)>> = const { RefCell::new(None) };
+    // Track under the cursor for the tooltip delay: (track_name, hover_start, last_seen) in ImGui time
+    static TOOLTIP_HOVER: RefCell<Option<(String, f64, f64)>> = const { RefCell::new(None) };
     // Track ESC key state for debouncing
     static ESC_WAS_DOWN: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    // Hovered event (timeline bar or Upcoming row): last frame's result, and the one being built this frame
+    static HOVERED_EVENT: RefCell<Option<TrackedEventId>> = const { RefCell::new(None) };
+    static HOVERED_EVENT_NEXT: RefCell<Option<TrackedEventId>> = const { RefCell::new(None) };
+}
+
+/// Event hovered during the previous frame, used to highlight its other occurrences
+pub fn hovered_event() -> Option<TrackedEventId> {
+    HOVERED_EVENT.with(|h| h.borrow().clone())
+}
+
+/// Report an event as hovered this frame
+pub fn set_hovered_event(event_id: TrackedEventId) {
+    HOVERED_EVENT_NEXT.with(|h| *h.borrow_mut() = Some(event_id));
 }
 
 pub fn render_main_window(ui: &Ui) {
+    // Promote last frame's hover so every widget sees the same value this frame
+    let hovered = HOVERED_EVENT_NEXT.with(|h| h.borrow_mut().take());
+    HOVERED_EVENT.with(|h| *h.borrow_mut() = hovered);
+
     // Handle any pending track toggle (must be done before locking config)
     let pending = PENDING_TRACK_TOGGLE.with(|p| p.borrow_mut().take());
     if let Some((track_name, event_name, is_oneshot)) = pending {
@@ -37,6 +116,11 @@ pub fn render_main_window(ui: &Ui) {
         }
     }
 
+    // Handle pending occurrence mute
+    if let Some((event_id, start_time, mute)) = PENDING_MUTE.with(|p| p.borrow_mut().take()) {
+        NOTIFICATION_STATE.lock().set_occurrence_muted(&event_id, start_time, mute);
+    }
+
     // Handle pending wiki open
     let wiki_event = PENDING_WIKI_OPEN.with(|p| p.borrow_mut().take());
     if let Some(event_name) = wiki_event {
@@ -47,6 +131,79 @@ pub fn render_main_window(ui: &Ui) {
 
     let mut config = RUNTIME_CONFIG.lock();
 
+    // Handle pending editor open (double-clicked event bar)
+    let editor_event = PENDING_EDITOR_OPEN.with(|p| p.borrow_mut().take());
+    if let Some((track_name, event_name)) = editor_event {
+        if let Some(track_index) = config.tracks.iter().position(|t| t.name == track_name) {
+            let event_index = config.tracks[track_index].events.iter().position(|e| e.name == event_name);
+            *SELECTED_TRACK.lock() = Some(track_index);
+            *SELECTED_EVENT.lock() = event_index;
+        }
+    }
+
+    // Auto-solo: show only the current map's tracks, or everything when none match.
+    // Written only on change, since any write invalidates the config snapshot.
+    let map_solo = if config.auto_solo_map {
+        current_map_id().filter(|map_id| config.tracks.iter().any(|t| t.map_ids.contains(map_id)))
+    } else {
+        None
+    };
+    if config.map_solo != map_solo {
+        config.map_solo = map_solo;
+    }
+
+    // Handle pending label click: solo the track (click again to restore), or toggle its visibility
+    let label_click = PENDING_LABEL_CLICK.with(|p| p.borrow_mut().take());
+    if let Some((track_name, toggle_visibility)) = label_click {
+        if toggle_visibility {
+            if let Some(track) = config.tracks.iter_mut().find(|t| t.name == track_name) {
+                track.visible = !track.visible;
+            }
+            if config.solo_track.as_deref() == Some(track_name.as_str()) {
+                config.solo_track = None;
+            }
+        } else if config.solo_track.as_deref() == Some(track_name.as_str()) {
+            config.solo_track = None;
+        } else {
+            config.solo_track = Some(track_name);
+        }
+    }
+
+    // Handle pending bar drag edit
+    if let Some((track_name, event_index, start_offset, duration)) = PENDING_BAR_EDIT.with(|p| p.borrow_mut().take()) {
+        if let Some(event) = config.tracks.iter_mut()
+            .find(|t| t.name == track_name)
+            .and_then(|t| t.events.get_mut(event_index))
+        {
+            event.start_offset = start_offset;
+            event.duration = duration;
+        }
+    }
+
+    // Handle pending category header action
+    if let Some((category, action)) = PENDING_CATEGORY_ACTION.with(|p| p.borrow_mut().take()) {
+        match action {
+            CategoryAction::Hide => {
+                config.category_visibility.insert(category, false);
+            }
+            CategoryAction::ToggleCollapsed => {
+                if !config.collapsed_categories.remove(&category) {
+                    config.collapsed_categories.insert(category);
+                }
+            }
+            CategoryAction::SetTracked(tracked) => {
+                let event_ids = category_event_ids(&config, &category);
+                for event_id in event_ids {
+                    if tracked {
+                        config.tracked_events.insert(event_id);
+                    } else {
+                        config.tracked_events.remove(&event_id);
+                    }
+                }
+            }
+        }
+    }
+
     // Handle ESC key to close window (check globally, with debouncing)
     if config.close_on_escape && config.show_main_window {
         let esc_down = ui.is_key_down(Key::Escape);
@@ -76,6 +233,54 @@ pub fn render_main_window(ui: &Ui) {
         c.set(config.copy_with_event_name);
     });
 
+    // Cache expansion ownership for this frame (shares the published snapshot, nothing is copied)
+    CACHED_UNOWNED_CONTENT.with(|c| {
+        *c.borrow_mut() = Some(RUNTIME_CONFIG.snapshot());
+    });
+
+    // Cache editable tracks for this frame
+    CACHED_EDITABLE_TRACKS.with(|c| {
+        let mut editable = c.borrow_mut();
+        editable.clear();
+        editable.extend(config.tracks.iter().filter(|t| config.is_editable_track(&t.name)).map(|t| t.name.clone()));
+    });
+
+    CACHED_LAST_CHANCE.with(|c| c.set(config.last_chance_highlight.then_some(config.last_chance_color)));
+
+    // Cache time markers for this frame
+    CACHED_TIME_MARKERS.with(|c| {
+        let mut markers = c.borrow_mut();
+        markers.clear();
+        markers.extend(config.time_markers.iter().filter(|m| m.enabled).cloned());
+    });
+
+    // Cache attendance for this frame
+    CACHED_ATTENDANCE.with(|c| c.borrow_mut().clone_from(&config.notification_config.attendance));
+
+    // Cache travel buffers for this frame
+    CACHED_TRAVEL_BUFFERS.with(|c| c.borrow_mut().clone_from(&config.notification_config.travel_buffers));
+
+    // Cache name language for this frame
+    CACHED_EVENT_LANGUAGE.with(|c| c.set(config.event_language));
+
+    // Cache occurrence style for this frame
+    CACHED_OCCURRENCE_STYLE.with(|c| {
+        c.set(OccurrenceStyle {
+            past_brightness: config.past_event_brightness,
+            past_alpha: config.past_event_alpha,
+            future_brightness: config.future_event_brightness,
+            future_alpha: config.future_event_alpha,
+            hide_past: config.hide_past_events,
+            finished_today: config.finished_today_mode,
+        });
+    });
+
+    // Cache tooltip settings for this frame
+    CACHED_TOOLTIP_SETTINGS.with(|c| {
+        let enabled = config.tooltips_when_locked || !config.is_window_locked;
+        c.set((enabled, config.tooltip_delay, config.tooltip_mode));
+    });
+
     // Cache all config values ONCE at start
     let view_range = config.view_range_seconds;
     let timeline_width = config.timeline_width;
@@ -102,28 +307,86 @@ pub fn render_main_window(ui: &Ui) {
     let label_category_color = config.label_column_category_color;
 
     // Calculate time ONCE per frame
-    let current_time = get_current_unix_time();
+    let current_time = get_display_time();
+
+    // Holding ALT shows the whole current cycle (the saved range is left alone);
+    // otherwise the view may be panned with the minimap
+    if config.minimap_cycle == OverviewCycle::Off {
+        VIEW_PAN.with(|p| p.set(0.0));
+    }
+    if let Some(pan) = focus_pan(&config, current_time, view_range, time_position) {
+        VIEW_PAN.with(|p| p.set(pan));
+    }
+    let (view_range, time_position) = match config.alt_zoom_cycle.cycle() {
+        Some((cycle, calculator)) if ui.io().key_alt => {
+            let cycle_start = current_cycle_start(current_time, cycle, calculator);
+            (cycle as f32, (current_time - cycle_start) as f32 / cycle as f32)
+        }
+        _ => (view_range, time_position - VIEW_PAN.with(|p| p.get()) / view_range),
+    };
     let time_before_current = view_range * time_position;
     let time_after_current = view_range * (1.0 - time_position);
 
     let mut window_flags = WindowFlags::empty();
     if config.is_window_locked {
-        window_flags |= WindowFlags::NO_RESIZE | WindowFlags::NO_MOVE;
+        window_flags |= WindowFlags::NO_MOVE;
+    }
+    if config.is_window_size_locked {
+        window_flags |= WindowFlags::NO_RESIZE;
+    }
+    if config.disable_window_interaction {
+        // Click-through: no hover, tooltips or context menu (re-enable from the addon settings)
+        window_flags |= WindowFlags::NO_INPUTS;
     }
 
     let mut window = Window::new("Event Timers");
-    if config.is_window_locked {
-        window = window.title_bar(false);
+    let display_size = ui.io().display_size;
+    let relative_pos = if config.relative_window_positions {
+        let mut stored = config.main_window_position;
+        let pos = snapping::relative_position("Event Timers", &mut stored, display_size);
+        if stored != config.main_window_position {
+            config.main_window_position = stored;
+        }
+        pos
+    } else {
+        None
+    };
+    if let Some(pos) = snapping::take_pending_position("Event Timers").or(relative_pos) {
+        window = window.position(pos, Condition::Always);
     }
-    
+    if let Some((size, position)) = PENDING_WINDOW_SIZE.with(|p| p.borrow_mut().take()) {
+        window = window.size(size, Condition::Always);
+        if let Some(position) = position {
+            window = window.position(position, Condition::Always);
+        }
+    } else if config.locked_window_height > 0.0 && !config.is_window_size_locked {
+        let height = config.locked_window_height;
+        window = window.size_constraints([0.0, height], [f32::MAX, height]);
+    }
+    let snap_windows = config.snap_windows;
+    let snap_threshold = config.snap_threshold;
+
+    // Standard chrome (title bar with collapse/close buttons) is opt-in, frameless by default
+    let show_title_bar = config.show_title_bar;
+    let mut opened = true;
+    if show_title_bar {
+        window = window.opened(&mut opened);
+    }
+
+    let window_alpha = window_fade_alpha(ui, &config, current_time);
+    let alpha_token = ui.push_style_var(StyleVar::Alpha(window_alpha));
+
     window
         .flags(window_flags)
         .draw_background(!config.hide_background)
         .scroll_bar(config.show_scrollbar)
         .size([timeline_width, 600.0], Condition::FirstUseEver)
-        .title_bar(false)
-        .collapsible(false)
+        .title_bar(show_title_bar)
+        .collapsible(show_title_bar)
         .build(ui, || {
+            snapping::track_window(ui, "Event Timers", snap_windows, snap_threshold);
+            WINDOW_RECT.with(|r| r.set(Some((ui.window_pos(), ui.window_size()))));
+
             // Check if we need to open the event tracking menu (set by tooltip handler)
             let should_open_event_menu = OPEN_EVENT_MENU.with(|f| {
                 let val = *f.borrow();
@@ -133,16 +396,52 @@ pub fn render_main_window(ui: &Ui) {
 
             if should_open_event_menu {
                 ui.open_popup("event_track_menu");
+            } else if OPEN_COPY_MENU.with(|f| f.replace(false)) {
+                ui.open_popup("event_copy_menu");
             } else if ui.is_window_hovered() && ui.is_mouse_clicked(MouseButton::Right) {
                 ui.open_popup("window_context_menu");
             }
 
             ui.popup("window_context_menu", || {
                 let is_locked = config.is_window_locked;
-                if MenuItem::new("Lock Window").selected(is_locked).build(ui) {
+                if MenuItem::new("Lock Position").selected(is_locked).build(ui) {
                     config.is_window_locked = !is_locked;
                 }
 
+                let is_size_locked = config.is_window_size_locked;
+                if MenuItem::new("Lock Size").selected(is_size_locked).build(ui) {
+                    config.is_window_size_locked = !is_size_locked;
+                }
+
+                let height_locked = config.locked_window_height > 0.0;
+                if MenuItem::new("Lock Height").selected(height_locked).enabled(!is_size_locked).build(ui) {
+                    config.locked_window_height = if height_locked { 0.0 } else { ui.window_size()[1] };
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Keep the current height while the width stays resizable, e.g. for a thin strip");
+                }
+
+                ui.menu("Size Presets", || {
+                    for preset in WindowSizePreset::ALL {
+                        if MenuItem::new(preset.label()).build(ui) {
+                            let (size, position) = preset.geometry(ui.io().display_size, ui.window_size(), ui.window_pos());
+                            if height_locked {
+                                config.locked_window_height = size[1];
+                            }
+                            PENDING_WINDOW_SIZE.with(|p| *p.borrow_mut() = Some((size, position)));
+                        }
+                    }
+                });
+
+                if MenuItem::new("Disable Interaction").build(ui) {
+                    config.disable_window_interaction = true;
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Make the window click-through. Re-enable it in the addon settings.");
+                }
+
+                ui.separator();
+
                 let hide_bg = config.hide_background;
                 if MenuItem::new("Hide Background").selected(hide_bg).build(ui) {
                     config.hide_background = !hide_bg;
@@ -152,6 +451,57 @@ pub fn render_main_window(ui: &Ui) {
                 if MenuItem::new("Show Scrollbar").selected(show_sb).build(ui) {
                     config.show_scrollbar = !show_sb;
                 }
+
+                let show_tb = config.show_title_bar;
+                if MenuItem::new("Show Title Bar").selected(show_tb).build(ui) {
+                    config.show_title_bar = !show_tb;
+                }
+
+                ui.separator();
+
+                let show_agenda = config.show_agenda_window;
+                if MenuItem::new("Agenda (24h)").selected(show_agenda).build(ui) {
+                    config.show_agenda_window = !show_agenda;
+                }
+
+                let auto_solo = config.auto_solo_map;
+                if MenuItem::new("Auto-Solo Current Map").selected(auto_solo).build(ui) {
+                    config.auto_solo_map = !auto_solo;
+                }
+
+                // Planning mode: render everything at a chosen moment, without notifications
+                ui.separator();
+                ui.text_disabled("Planning Mode");
+                PLANNING_INPUT.with(|input| {
+                    let mut input = input.borrow_mut();
+                    ui.set_next_item_width(150.0);
+                    InputText::new(ui, "##planning_time", &mut input).hint("YYYY-MM-DD HH:MM").build();
+                    ui.same_line();
+                    if ui.button("Simulate") {
+                        if let Some(target) = parse_local_datetime(&input) {
+                            set_simulated_time(Some(target));
+                            ui.close_current_popup();
+                        }
+                    }
+                });
+                if is_time_simulated() && MenuItem::new("Back to Live").build(ui) {
+                    set_simulated_time(None);
+                }
+
+                ui.separator();
+                if MenuItem::new("Export Timeline Image").build(ui) {
+                    let status = match export_timeline_png(&config, current_time) {
+                        Ok(path) => (format!("Saved {}", path.display()), false),
+                        Err(e) => {
+                            addon_log::error(format!("Timeline export failed: {}", e));
+                            (e, true)
+                        }
+                    };
+                    EXPORT_STATUS.with(|s| *s.borrow_mut() = Some((status.0, status.1, ui.time())));
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Save the visible timeline as a PNG in the addon's exports folder");
+                }
             });
 
             // Event tracking context menu
@@ -186,8 +536,95 @@ pub fn render_main_window(ui: &Ui) {
                             }
                         }
 
+                        // Mute only matters for events that notify
+                        if was_tracked || was_oneshot {
+                            if let Some((start_time, is_muted)) = CONTEXT_OCCURRENCE.with(|o| *o.borrow()) {
+                                let mute_label = if is_muted { "Unmute This Occurrence" } else { "Mute Until Next Spawn" };
+                                if MenuItem::new(mute_label).build(ui) {
+                                    PENDING_MUTE.with(|p| {
+                                        *p.borrow_mut() = Some((TrackedEventId::new(&track_name, &event_name), start_time, !is_muted));
+                                    });
+                                }
+                            }
+
+                            let event_id = TrackedEventId::new(&track_name, &event_name);
+                            let current_priority = config.notification_config.priority(&event_id);
+                            ui.menu("Priority", || {
+                                for priority in EventPriority::ALL {
+                                    if MenuItem::new(priority.label()).selected(current_priority == priority).build(ui) {
+                                        config.notification_config.set_priority(&event_id, priority);
+                                    }
+                                }
+                            });
+
+                            let current_travel = config.notification_config.travel_minutes(&event_id);
+                            ui.menu("Travel Time", || {
+                                for minutes in TRAVEL_TIME_CHOICES {
+                                    let label = if minutes == 0 { "None".to_string() } else { format!("{} min early", minutes) };
+                                    if MenuItem::new(label).selected(current_travel == minutes).build(ui) {
+                                        config.notification_config.set_travel_minutes(&event_id, minutes);
+                                    }
+                                }
+                            });
+                            if ui.is_item_hovered() {
+                                ui.tooltip_text("Marks when to leave on the timeline and moves\nreminders earlier by the travel time");
+                            }
+
+                            let pack_fills_early = config.tracks.iter()
+                                .find(|t| t.name == track_name)
+                                .and_then(|t| t.events.iter().find(|e| e.name == event_name))
+                                .is_some_and(|e| e.fills_early);
+                            let user_fills_early = config.notification_config.fills_early_events.contains(&event_id);
+                            let mut note = config.notification_config.event_note(&event_id).unwrap_or_default().to_string();
+                            ui.menu("Note", || {
+                                ui.set_next_item_width(220.0);
+                                if InputText::new(ui, "##event_note", &mut note).hint("e.g. bring markers").build() {
+                                    config.notification_config.set_event_note(&event_id, note.clone());
+                                }
+                                ui.text_disabled("Shown in this event's toasts and the Upcoming panel");
+                            });
+
+                            if MenuItem::new("Fills Early")
+                                .selected(pack_fills_early || user_fills_early)
+                                .enabled(!pack_fills_early)
+                                .build(ui)
+                            {
+                                if user_fills_early {
+                                    config.notification_config.fills_early_events.retain(|id| *id != event_id);
+                                } else {
+                                    config.notification_config.fills_early_events.push(event_id.clone());
+                                }
+                            }
+                            if ui.is_item_hovered_with_flags(nexus::imgui::ItemHoveredFlags::ALLOW_WHEN_DISABLED) {
+                                let reason = if pack_fills_early { "\nTagged by the event data" } else { "" };
+                                ui.tooltip_text(format!(
+                                    "Reminders come {} min earlier (see Notifications settings){}",
+                                    config.notification_config.fills_early_minutes, reason
+                                ));
+                            }
+                        }
+
                         ui.separator();
 
+                        let event_id = TrackedEventId::new(&track_name, &event_name);
+                        let focused = FOCUS_EVENT.with(|f| f.borrow().as_ref() == Some(&event_id));
+                        if MenuItem::new(if focused { "Stop Focusing" } else { "Focus This Event" }).build(ui) {
+                            set_focus(if focused { None } else { Some(event_id) });
+                        }
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text("Keep this event's next occurrence in view as time passes");
+                        }
+                        if MenuItem::new("Hide from Timeline").build(ui) {
+                            if let Some(track) = config.tracks.iter_mut().find(|t| t.name == track_name) {
+                                for event in track.events.iter_mut().filter(|e| e.name == event_name) {
+                                    event.show_on_timeline = false;
+                                }
+                            }
+                        }
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text("Notifications continue; show it again from the track's event settings");
+                        }
+
                         // Open Wiki option
                         if MenuItem::new(format!("Open Wiki: {}", event_name)).build(ui) {
                             PENDING_WIKI_OPEN.with(|p| {
@@ -198,13 +635,110 @@ pub fn render_main_window(ui: &Ui) {
                 });
             });
             
-            if config.show_time_ruler {
-                // Calculate label offset for time ruler alignment
-                let label_offset = match label_column_pos {
-                    LabelColumnPosition::Left => label_column_width,
-                    _ => 0.0,
+            // Copy menu for events with more than one copyable string
+            ui.popup("event_copy_menu", || {
+                COPY_MENU_EVENT.with(|e| {
+                    if let Some((event_name, options)) = e.borrow().as_ref() {
+                        ui.text_disabled(event_name);
+                        ui.separator();
+                        for option in options {
+                            if MenuItem::new(format!("{}: {}", option.label, option.text)).build(ui) {
+                                let copy_text = if config.copy_with_event_name {
+                                    format!("{}: {}", event_name, option.text)
+                                } else {
+                                    option.text.clone()
+                                };
+                                ui.set_clipboard_text(&copy_text);
+                            }
+                        }
+                    }
+                });
+            });
+
+            if let Some(error) = load_error() {
+                let (color, fallback) = if error.using_backup {
+                    ([1.0, 0.8, 0.3, 1.0], ", using backup")
+                } else {
+                    ([1.0, 0.4, 0.4, 1.0], "")
                 };
-                render_time_ruler(
+                if let Some(required) = &error.requires_version {
+                    ui.text_colored([1.0, 0.8, 0.3, 1.0], format!("event_tracks.json needs addon {}, using built-in tracks", required));
+                } else {
+                    ui.text_colored(
+                        color,
+                        format!("event_tracks.json failed to load (line {}, column {}){}", error.line, error.column, fallback),
+                    );
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text(&error.message);
+                }
+                ui.same_line();
+                if ui.small_button("Show Log") {
+                    config.show_log_window = true;
+                }
+            }
+
+            if rejected_update().is_some() {
+                ui.text_colored([1.0, 0.8, 0.3, 1.0], "Data update rejected");
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("The current event_tracks.json was kept. See the addon settings for details.");
+                }
+            }
+
+            if let Some(warning) = clock_warning() {
+                ui.text_colored([1.0, 0.8, 0.3, 1.0], warning.message());
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Event times come from the system clock. Check the date, time and time zone\nin your system settings; this goes away a few minutes after the clock is fine.");
+                }
+            }
+
+            if is_time_simulated() {
+                ui.text_colored([1.0, 0.8, 0.2, 1.0], format!("Planning: {}", format_date_time(current_time)));
+                ui.same_line();
+                if ui.small_button("Back to Live") {
+                    set_simulated_time(None);
+                }
+            }
+
+            // Export result stays up for a few seconds
+            EXPORT_STATUS.with(|s| {
+                let mut status = s.borrow_mut();
+                if let Some((message, is_error, shown_at)) = status.as_ref() {
+                    if ui.time() - shown_at > 6.0 {
+                        *status = None;
+                    } else {
+                        let color = if *is_error { [1.0, 0.4, 0.4, 1.0] } else { [0.5, 0.9, 0.5, 1.0] };
+                        ui.text_colored(color, message);
+                    }
+                }
+            });
+
+            if let Some(focus) = FOCUS_EVENT.with(|f| f.borrow().clone()) {
+                let label = config.tracks.iter()
+                    .find(|t| t.name == focus.track_name)
+                    .and_then(|t| t.events.iter().find(|e| e.name == focus.event_name))
+                    .map_or(focus.event_name.as_str(), |e| e.display_name(config.event_language));
+                ui.text_colored(FOCUS_COLOR, format!("Focus: {}", label));
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("The view follows this event's next occurrence");
+                }
+                ui.same_line();
+                if ui.small_button("x##clear_focus") {
+                    set_focus(None);
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Stop focusing and return to the current time");
+                }
+            }
+
+            // Calculate label offset for time ruler alignment
+            let label_offset = match label_column_pos {
+                LabelColumnPosition::Left => label_column_width,
+                _ => 0.0,
+            };
+            let mut ruler_probe = None;
+            if config.show_time_ruler {
+                ruler_probe = render_time_ruler(
                     ui,
                     current_time,
                     view_range,
@@ -212,10 +746,46 @@ pub fn render_main_window(ui: &Ui) {
                     label_offset,
                     config.time_ruler_interval,
                     config.time_ruler_show_current_time,
+                    &config.time_markers,
                 );
             }
+
+            if let Some((cycle, calculator)) = config.minimap_cycle.cycle() {
+                let view_start = current_time - time_before_current as i64;
+                let cycle_start = current_cycle_start(current_time, cycle, calculator);
+                let action = render_cycle_minimap(ui, current_time, cycle_start, cycle, view_start, view_range, label_offset);
+                if action.is_some() {
+                    set_focus(None);
+                }
+                match action {
+                    Some(MinimapAction::CenterOn(center)) => {
+                        // Pan relative to where the view sits when following the current time
+                        let following_start = current_time as f32 - config.view_range_seconds * config.current_time_position;
+                        VIEW_PAN.with(|p| p.set(center as f32 - view_range / 2.0 - following_start));
+                    }
+                    Some(MinimapAction::Reset) => VIEW_PAN.with(|p| p.set(0.0)),
+                    None => {}
+                }
+            }
             
+            // Auto-fit: share the remaining window height between the visible tracks
+            let (override_all_track_heights, global_track_height) = if config.auto_fit_track_heights {
+                let (track_count, overhead) = measure_track_layout(ui, &config, show_headers, spacing_same, spacing_between);
+                if track_count > 0 {
+                    let available = ui.content_region_avail()[1] - overhead;
+                    let max_height = config.auto_fit_max_height.max(config.auto_fit_min_height);
+                    let fitted = (available / track_count as f32).clamp(config.auto_fit_min_height, max_height);
+                    (true, fitted.floor())
+                } else {
+                    (override_all_track_heights, global_track_height)
+                }
+            } else {
+                (override_all_track_heights, global_track_height)
+            };
+
             let _style_token = ui.push_style_var(StyleVar::ItemSpacing([0.0, 0.0]));
+            let layout_origin = ui.cursor_screen_pos();
+            let layout_width = ui.content_region_avail()[0];
             
             // Determine layout based on label column position
             match label_column_pos {
@@ -307,13 +877,85 @@ pub fn render_main_window(ui: &Ui) {
                     );
                 }
             }
+
+            // Ruler hover or scrub: a cursor across all tracks and what's running at that moment
+            if let Some(probe) = ruler_probe {
+                render_ruler_probe(ui, &config, &probe, [layout_origin[1], ui.cursor_screen_pos()[1]]);
+            }
+
+            // Drag handle on the edge between the label column and the timeline
+            if label_column_pos != LabelColumnPosition::None {
+                let boundary_x = match label_column_pos {
+                    LabelColumnPosition::Left => layout_origin[0] + label_column_width,
+                    _ => layout_origin[0] + layout_width - label_column_width,
+                };
+                let height = ui.cursor_screen_pos()[1] - layout_origin[1];
+                if let Some(delta) = label_column_splitter(ui, [boundary_x, layout_origin[1]], height) {
+                    let new_width = match label_column_pos {
+                        LabelColumnPosition::Left => label_column_width + delta,
+                        _ => label_column_width - delta,
+                    };
+                    config.label_column_width = new_width.clamp(50.0, (layout_width - 50.0).max(50.0));
+                }
+            }
         });
+
+    alpha_token.pop();
+
+    if !opened {
+        config.show_main_window = false;
+    }
+}
+
+/// Main window opacity: `fade_opacity` while fading is on, the cursor is farther than
+/// `fade_distance` from the window and no tracked event starts within `fade_event_minutes`.
+/// Eased over a fraction of a second so the window doesn't blink.
+fn window_fade_alpha(ui: &Ui, config: &crate::config::RuntimeConfig, current_time: i64) -> f32 {
+    let target = if !config.fade_when_inactive
+        || cursor_near_window(ui, config.fade_distance)
+        || tracked_event_starting(config, current_time, config.fade_event_minutes as i64 * 60)
+    {
+        1.0
+    } else {
+        config.fade_opacity.clamp(0.05, 1.0)
+    };
+
+    FADE_ALPHA.with(|alpha| {
+        let step = ui.io().delta_time * 4.0;
+        let current = alpha.get();
+        let next = if current < target { (current + step).min(target) } else { (current - step).max(target) };
+        alpha.set(next);
+        next
+    })
+}
+
+/// Whether the cursor is within `distance` pixels of the main window (as of last frame)
+fn cursor_near_window(ui: &Ui, distance: f32) -> bool {
+    let Some((pos, size)) = WINDOW_RECT.with(|r| r.get()) else {
+        return true;
+    };
+    let mouse = ui.io().mouse_pos;
+    mouse[0] >= pos[0] - distance
+        && mouse[0] <= pos[0] + size[0] + distance
+        && mouse[1] >= pos[1] - distance
+        && mouse[1] <= pos[1] + size[1] + distance
+}
+
+/// Whether a tracked (or next-only tracked) event starts within `within` seconds
+fn tracked_event_starting(config: &crate::config::RuntimeConfig, current_time: i64, within: i64) -> bool {
+    config.tracked_events.iter().chain(config.oneshot_events.iter()).any(|id| {
+        config.tracks.iter()
+            .filter(|t| t.name == id.track_name && config.is_track_shown(t))
+            .flat_map(|t| t.events.iter().filter(|e| e.enabled && e.name == id.event_name).map(move |e| (t, e)))
+            .filter_map(|(track, event)| timeline::next_occurrence_after(event, track.base_time, current_time))
+            .any(|start| start - current_time <= within)
+    })
 }
 
 #[allow(clippy::too_many_arguments)]
 fn render_timeline_content(
     ui: &Ui,
-    config: &parking_lot::MutexGuard<crate::config::RuntimeConfig>,
+    config: &crate::config::RuntimeConfig,
     show_headers: bool,
     spacing_same: f32,
     spacing_between: f32,
@@ -370,7 +1012,7 @@ fn render_timeline_content(
     
     // Then render any tracks with categories not in the order
     for track in config.tracks.iter() {
-        if !rendered_categories.contains(&track.category) && track.visible {
+        if !rendered_categories.contains(&track.category) && config.is_track_shown(track) {
             let is_category_visible = *config.category_visibility.get(&track.category).unwrap_or(&true);
             if is_category_visible {
                 render_tracks_for_category(
@@ -405,7 +1047,7 @@ fn render_timeline_content(
 #[allow(clippy::too_many_arguments)]
 fn render_with_label_column_left(
     ui: &Ui,
-    config: &parking_lot::MutexGuard<crate::config::RuntimeConfig>,
+    config: &crate::config::RuntimeConfig,
     label_column_width: f32,
     show_headers: bool,
     spacing_same: f32,
@@ -484,7 +1126,7 @@ fn render_with_label_column_left(
 #[allow(clippy::too_many_arguments)]
 fn render_with_label_column_right(
     ui: &Ui,
-    config: &parking_lot::MutexGuard<crate::config::RuntimeConfig>,
+    config: &crate::config::RuntimeConfig,
     label_column_width: f32,
     show_headers: bool,
     spacing_same: f32,
@@ -565,7 +1207,7 @@ fn render_with_label_column_right(
 
 fn render_label_column(
     ui: &Ui,
-    config: &parking_lot::MutexGuard<crate::config::RuntimeConfig>,
+    config: &crate::config::RuntimeConfig,
     show_headers: bool,
     spacing_same: f32,
     spacing_between: f32,
@@ -610,7 +1252,7 @@ fn render_label_column(
     
     // Render remaining categories
     for track in config.tracks.iter() {
-        if !rendered_categories.contains(&track.category) && track.visible {
+        if !rendered_categories.contains(&track.category) && config.is_track_shown(track) {
             let is_category_visible = *config.category_visibility.get(&track.category).unwrap_or(&true);
             if is_category_visible {
                 render_label_column_for_category(
@@ -638,7 +1280,7 @@ fn render_label_column(
 
 fn render_label_column_for_category(
     ui: &Ui,
-    config: &parking_lot::MutexGuard<crate::config::RuntimeConfig>,
+    config: &crate::config::RuntimeConfig,
     category: &str,
     rendered_categories: &mut HashSet<String>,
     show_headers: bool,
@@ -668,7 +1310,7 @@ fn render_label_column_for_category(
     let draw_list = ui.get_window_draw_list();
     
     for track in config.tracks.iter() {
-        if track.category != category || !track.visible {
+        if track.category != category || !config.is_track_shown(track) {
             continue;
         }
         
@@ -676,13 +1318,17 @@ fn render_label_column_for_category(
             if *needs_spacing {
                 ui.dummy([0.0, spacing_between]);
             }
+
+            if config.category_panels_enabled {
+                let block_height = category_block_height(ui, config, category, show_headers, spacing_same, override_all_track_heights, global_track_height);
+                draw_category_panel(ui, config, category, block_height);
+            }
             
             if show_headers && !category.is_empty() {
                 // Category header with same height as timeline header
                 let cursor_pos = ui.cursor_screen_pos();
                 let available_width = ui.content_region_avail()[0];
-                let text_size = ui.calc_text_size(category);
-                let header_height = text_size[1] + 10.0;
+                let header_height = category_header_height(ui, config, category);
                 
                 // Background for category (if enabled)
                 if label_bg_color[3] > 0.0 {
@@ -695,12 +1341,20 @@ fn render_label_column_for_category(
                 
                 // Category text (if enabled) - uses separate category color
                 if label_show_category {
-                    // Note: Font scaling in nexus imgui is limited, using regular text
-                    let text_pos = [cursor_pos[0] + 5.0, cursor_pos[1] + 5.0];
-                    draw_list.add_text(text_pos, label_category_color, category);
+                    let title = category_title(config, category);
+                    let text_size = ui.calc_text_size(&*title);
+                    let x_offset = aligned_text_offset(
+                        config.category_header_alignment,
+                        available_width,
+                        text_size[0],
+                        config.category_header_padding.max(5.0),
+                    );
+                    let text_pos = [cursor_pos[0] + x_offset, cursor_pos[1] + (header_height - text_size[1]) / 2.0];
+                    draw_list.add_text(text_pos, label_category_color, &*title);
                 }
                 
                 ui.dummy([0.0, header_height]);
+                category_header_actions(ui, config, category, cursor_pos, [cursor_pos[0] + available_width, cursor_pos[1] + header_height]);
             }
             
             first_visible_in_category = false;
@@ -708,6 +1362,10 @@ fn render_label_column_for_category(
         } else {
             ui.dummy([0.0, spacing_same]);
         }
+
+        if config.is_category_collapsed(category) {
+            break;
+        }
         
         // Track label - match exact height of timeline track
         let track_height = if override_all_track_heights {
@@ -728,17 +1386,39 @@ fn render_label_column_for_category(
             ).filled(true).build();
         }
         
+        // Icon (if any) as a square filling the row, before the name
+        let mut text_x = cursor_pos[0] + 5.0;
+        if let Some(icon) = crate::track_icons::texture(&track.icon) {
+            let size = (track_height - 4.0).clamp(8.0, 32.0);
+            let icon_min = [cursor_pos[0] + 3.0, cursor_pos[1] + (track_height - size) / 2.0];
+            draw_list.add_image(icon.id(), icon_min, [icon_min[0] + size, icon_min[1] + size]).build();
+            text_x = icon_min[0] + size + 4.0;
+        }
+
         // Draw track name (if enabled) - vertically centered
         if label_show_track {
             // Note: Font scaling in nexus imgui is limited, using regular text
-            let text_size = ui.calc_text_size(&track.name);
+            let track_label = track.display_name(config.event_language);
+            let text_size = ui.calc_text_size(track_label);
             let text_y_offset = (track_height - text_size[1]) / 2.0;
-            let text_pos = [cursor_pos[0] + 5.0, cursor_pos[1] + text_y_offset];
-            draw_list.add_text(text_pos, label_text_color, &track.name);
+            let text_pos = [text_x, cursor_pos[1] + text_y_offset];
+            draw_list.add_text(text_pos, label_text_color, track_label);
         }
         
         // Dummy with EXACT track height to match timeline
         ui.dummy([available_width, track_height]);
+
+        if ui.is_item_clicked() {
+            let toggle_visibility = ui.io().key_ctrl;
+            PENDING_LABEL_CLICK.with(|p| *p.borrow_mut() = Some((track.name.clone(), toggle_visibility)));
+        }
+        if ui.is_item_hovered() {
+            if config.solo_track.is_some() {
+                ui.tooltip_text("Click to show all tracks\nCtrl+click to hide this track");
+            } else {
+                ui.tooltip_text("Click to solo this track\nCtrl+click to hide this track");
+            }
+        }
     }
     
     rendered_categories.insert(category.to_string());
@@ -747,7 +1427,7 @@ fn render_label_column_for_category(
 #[allow(clippy::too_many_arguments)]
 fn render_tracks_for_category(
     ui: &Ui,
-    config: &parking_lot::MutexGuard<crate::config::RuntimeConfig>,
+    config: &crate::config::RuntimeConfig,
     category: &str,
     rendered_categories: &mut HashSet<String>,
     show_headers: bool,
@@ -783,7 +1463,7 @@ fn render_tracks_for_category(
     let needs_spacing = !rendered_categories.is_empty();
 
     for track in config.tracks.iter() {
-        if track.category != category || !track.visible {
+        if track.category != category || !config.is_track_shown(track) {
             continue;
         }
 
@@ -792,14 +1472,17 @@ fn render_tracks_for_category(
                 ui.dummy([0.0, spacing_between]);
             }
 
+            if config.category_panels_enabled {
+                let block_height = category_block_height(ui, config, category, show_headers, spacing_same, override_all_track_heights, global_track_height);
+                draw_category_panel(ui, config, category, block_height);
+            }
+
             // Only show header if label column is NOT active
             if show_headers && !category.is_empty() && !label_column_active {
-                render_category_header(ui, category, header_alignment, header_padding);
+                render_category_header(ui, config, category, header_alignment, header_padding);
             } else if show_headers && !category.is_empty() && label_column_active {
                 // Just add spacing to match the label column's category header height
-                let text_size = ui.calc_text_size(category);
-                let header_height = text_size[1] + 10.0;
-                ui.dummy([0.0, header_height]);
+                ui.dummy([0.0, category_header_height(ui, config, category)]);
             }
             
             first_visible_in_category = false;
@@ -807,6 +1490,10 @@ fn render_tracks_for_category(
             ui.dummy([0.0, spacing_same]);
         }
 
+        if config.is_category_collapsed(category) {
+            break;
+        }
+
         render_timeline_track(
             ui,
             track,
@@ -828,37 +1515,322 @@ fn render_tracks_for_category(
     rendered_categories.insert(category.to_string());
 }
 
-fn render_category_header(ui: &Ui, category: &str, alignment: TextAlignment, padding: f32) {
-    let available_width = ui.content_region_avail()[0];
-    let text_size = ui.calc_text_size(category);
-    
-    // Calculate X position based on alignment
-    let x_offset = match alignment {
-        TextAlignment::Left => padding,
-        TextAlignment::Center => (available_width - text_size[0]) / 2.0,
-        TextAlignment::Right => available_width - text_size[0] - padding,
+/// Total height of a category block: its header plus visible tracks and the spacing between them
+fn category_block_height(
+    ui: &Ui,
+    config: &crate::config::RuntimeConfig,
+    category: &str,
+    show_headers: bool,
+    spacing_same: f32,
+    override_all_track_heights: bool,
+    global_track_height: f32,
+) -> f32 {
+    let track_heights: Vec<f32> = config.tracks.iter()
+        .filter(|t| t.category == category && config.is_track_shown(t))
+        .map(|t| if override_all_track_heights { global_track_height } else { t.height })
+        .collect();
+    if track_heights.is_empty() {
+        return 0.0;
+    }
+
+    let header_height = if show_headers && !category.is_empty() {
+        category_header_height(ui, config, category)
+    } else {
+        0.0
     };
+    if config.is_category_collapsed(category) {
+        return header_height;
+    }
+    header_height + track_heights.iter().sum::<f32>() + spacing_same * (track_heights.len() - 1) as f32
+}
+
+/// Rounded background behind a whole category block, drawn before its contents
+fn draw_category_panel(
+    ui: &Ui,
+    config: &crate::config::RuntimeConfig,
+    category: &str,
+    height: f32,
+) {
+    let color = config.category_panel_colors.get(category).copied().unwrap_or(config.category_panel_color);
+    if color[3] <= 0.0 || height <= 0.0 {
+        return;
+    }
+
+    let min = ui.cursor_screen_pos();
+    let width = ui.content_region_avail()[0];
+    ui.get_window_draw_list()
+        .add_rect(min, [min[0] + width, min[1] + height], color)
+        .filled(true)
+        .rounding(config.category_panel_rounding)
+        .build();
+}
+
+/// Count visible tracks and the vertical space taken by everything else
+/// (category headers and spacing), mirroring the layout of render_timeline_content
+fn measure_track_layout(
+    ui: &Ui,
+    config: &crate::config::RuntimeConfig,
+    show_headers: bool,
+    spacing_same: f32,
+    spacing_between: f32,
+) -> (usize, f32) {
+    let mut categories: Vec<&String> = config.category_order.iter().collect();
+    for track in &config.tracks {
+        if !categories.contains(&&track.category) {
+            categories.push(&track.category);
+        }
+    }
+
+    let mut track_count = 0;
+    let mut overhead = 0.0;
+    let mut first_category = true;
+
+    for category in categories {
+        if !*config.category_visibility.get(category).unwrap_or(&true) {
+            continue;
+        }
+
+        let visible = config.tracks.iter()
+            .filter(|t| t.category == *category && config.is_track_shown(t))
+            .count();
+        if visible == 0 {
+            continue;
+        }
+
+        if !first_category {
+            overhead += spacing_between;
+        }
+        if show_headers && !category.is_empty() {
+            overhead += category_header_height(ui, config, category);
+        }
+        first_category = false;
+        if config.is_category_collapsed(category) {
+            continue;
+        }
+        overhead += spacing_same * (visible - 1) as f32;
+
+        track_count += visible;
+    }
+
+    (track_count, overhead)
+}
+
+/// Vertical cursor over the tracks at the ruler's probed time (faint while only hovering),
+/// with a tooltip of every visible event running at that moment
+fn render_ruler_probe(ui: &Ui, config: &crate::config::RuntimeConfig, probe: &RulerProbe, y_range: [f32; 2]) {
+    let (time, x) = (probe.time, probe.x);
+    let alpha = if probe.scrubbing { 0.6 } else { 0.25 };
+    ui.get_window_draw_list()
+        .add_line([x, y_range[0]], [x, y_range[1]], [1.0, 1.0, 1.0, alpha])
+        .thickness(1.5)
+        .build();
+
+    let (tyria_hours, tyria_minutes) = calculate_tyria_time(time);
+    ui.tooltip(|| {
+        ui.text(format!("{} (Tyria {:02}:{:02})", format_time_precise(time), tyria_hours, tyria_minutes));
+        ui.separator();
+
+        let mut any_active = false;
+        for track in config.tracks.iter().filter(|t| config.is_track_shown(t)) {
+            if !*config.category_visibility.get(&track.category).unwrap_or(&true) {
+                continue;
+            }
+            for (event, start) in track.occurrences_active_at(time) {
+                if !event.show_on_timeline || unowned_content_mode(track, event) == Some(UnownedContentMode::Hide) {
+                    continue;
+                }
+                any_active = true;
+                ui.text_colored(event.color.to_array(), "|");
+                ui.same_line();
+                ui.text(format!(
+                    "{} ({})",
+                    event.display_name(config.event_language),
+                    track.display_name(config.event_language)
+                ));
+                ui.same_line();
+                ui.text_disabled(format!("until {}", format_time_precise(start + event.duration)));
+            }
+        }
+        if !any_active {
+            ui.text_disabled("Nothing running");
+        }
+        if !probe.scrubbing {
+            ui.text_disabled("Drag to preview");
+        }
+    });
+}
+
+/// Invisible drag handle centered on `top[0]`. Returns the horizontal drag distance this frame.
+fn label_column_splitter(ui: &Ui, top: [f32; 2], height: f32) -> Option<f32> {
+    const HANDLE_WIDTH: f32 = 6.0;
+
+    ui.set_cursor_screen_pos([top[0] - HANDLE_WIDTH / 2.0, top[1]]);
+    ui.invisible_button("##label_column_splitter", [HANDLE_WIDTH, height.max(1.0)]);
+
+    let active = ui.is_item_active();
+    if active || ui.is_item_hovered() {
+        ui.set_mouse_cursor(Some(MouseCursor::ResizeEW));
+        ui.get_window_draw_list()
+            .add_line(top, [top[0], top[1] + height], [1.0, 1.0, 1.0, if active { 0.6 } else { 0.3 }])
+            .thickness(2.0)
+            .build();
+    }
+
+    if active && ui.is_mouse_dragging(MouseButton::Left) {
+        Some(ui.io().mouse_delta[0])
+    } else {
+        None
+    }
+}
+
+/// Height of a category header row, shared by the timeline, the label column and layout measurement
+fn category_header_height(
+    ui: &Ui,
+    config: &crate::config::RuntimeConfig,
+    category: &str,
+) -> f32 {
+    if config.category_header_height > 0.0 {
+        config.category_header_height
+    } else {
+        ui.calc_text_size(category)[1] * config.category_header_text_scale + 10.0
+    }
+}
+
+/// Horizontal offset of text within a row of the given width
+fn aligned_text_offset(alignment: TextAlignment, available_width: f32, text_width: f32, padding: f32) -> f32 {
+    match alignment {
+        TextAlignment::Left => padding,
+        TextAlignment::Center => (available_width - text_width) / 2.0,
+        TextAlignment::Right => available_width - text_width - padding,
+    }
+}
+
+fn render_category_header(
+    ui: &Ui,
+    config: &crate::config::RuntimeConfig,
+    category: &str,
+    alignment: TextAlignment,
+    padding: f32,
+) {
+    let available_width = ui.content_region_avail()[0];
+    let header_height = category_header_height(ui, config, category);
+
+    // Scale the font for both measuring and drawing the title
+    ui.set_window_font_scale(config.category_header_text_scale);
+    let title = category_title(config, category);
+    let text_size = ui.calc_text_size(&*title);
+    let x_offset = aligned_text_offset(alignment, available_width, text_size[0], padding);
     
     // Draw using background draw list for full width coverage
     let draw_list = ui.get_window_draw_list();
     let cursor_pos = ui.cursor_screen_pos();
-    let header_height = text_size[1] + 10.0;
     
     // Semi-transparent background
     draw_list
         .add_rect(
             cursor_pos,
             [cursor_pos[0] + available_width, cursor_pos[1] + header_height],
-            [0.15, 0.15, 0.15, 0.8],
+            config.category_header_bg_color,
         )
         .filled(true)
         .build();
     
-    // Category text with alignment
-    let text_pos = [cursor_pos[0] + x_offset, cursor_pos[1] + 5.0];
-    draw_list.add_text(text_pos, [0.8, 0.8, 0.2, 1.0], category);
+    // Category text with alignment, vertically centered
+    let text_pos = [cursor_pos[0] + x_offset, cursor_pos[1] + (header_height - text_size[1]) / 2.0];
+    draw_list.add_text(text_pos, config.category_header_text_color, &*title);
+    ui.set_window_font_scale(1.0);
     
     ui.dummy([available_width, header_height]);
+    category_header_actions(ui, config, category, cursor_pos, [cursor_pos[0] + available_width, cursor_pos[1] + header_height]);
+}
+
+/// Events starting within this many seconds count as "soon" on category headers
+const CATEGORY_SOON_SECONDS: i64 = 15 * 60;
+
+/// Category title with its running and upcoming counts (when enabled, refreshed once per
+/// second), marked when the category is collapsed
+fn category_title(config: &crate::config::RuntimeConfig, category: &str) -> std::rc::Rc<str> {
+    let collapsed = config.is_category_collapsed(category);
+    let with_counts = config.category_header_counts;
+    label_cache::label(("category_title", category, collapsed, with_counts), || {
+        let mut title = category.to_string();
+        if with_counts {
+            let (active, soon) = category_counts(config, category, get_display_time());
+            let counts: Vec<String> = [(active, "active"), (soon, "soon")]
+                .iter()
+                .filter(|(count, _)| *count > 0)
+                .map(|(count, label)| format!("{} {}", count, label))
+                .collect();
+            if !counts.is_empty() {
+                title = format!("{} ({})", title, counts.join(", "));
+            }
+        }
+        if collapsed {
+            title.push_str(" [+]");
+        }
+        title
+    })
+}
+
+/// Occurrences on the category's shown tracks running at `now`, and starting within CATEGORY_SOON_SECONDS
+fn category_counts(config: &crate::config::RuntimeConfig, category: &str, now: i64) -> (usize, usize) {
+    let visible = |track: &EventTrack, event: &TimelineEvent| {
+        event.show_on_timeline && unowned_content_mode(track, event) != Some(UnownedContentMode::Hide)
+    };
+    config.tracks.iter()
+        .filter(|t| t.category == category && config.is_track_shown(t))
+        .fold((0, 0), |(active, soon), track| {
+            let running = track.occurrences_active_at(now).into_iter().filter(|(e, _)| visible(track, e)).count();
+            let upcoming = track.occurrences_between(now + 1, now + CATEGORY_SOON_SECONDS)
+                .into_iter()
+                .filter(|(e, _)| visible(track, e))
+                .count();
+            (active + running, soon + upcoming)
+        })
+}
+
+/// Enabled events of the category's shown tracks
+fn category_event_ids(config: &crate::config::RuntimeConfig, category: &str) -> Vec<TrackedEventId> {
+    config.tracks.iter()
+        .filter(|t| t.category == category && config.is_track_shown(t))
+        .flat_map(|t| t.events.iter().filter(|e| e.enabled).map(|e| TrackedEventId::new(&t.name, &e.name)))
+        .collect()
+}
+
+/// Quick-action buttons over a hovered category header: track all, collapse and hide
+fn category_header_actions(ui: &Ui, config: &crate::config::RuntimeConfig, category: &str, min: [f32; 2], max: [f32; 2]) {
+    if !ui.is_window_hovered() || !ui.is_mouse_hovering_rect(min, max) {
+        return;
+    }
+
+    let all_tracked = category_event_ids(config, category).iter().all(|id| config.tracked_events.contains(id));
+    let collapsed = config.is_category_collapsed(category);
+    let mut buttons = vec![(
+        if all_tracked { "Untrack All" } else { "Track All" },
+        CategoryAction::SetTracked(!all_tracked),
+    )];
+    if config.show_category_headers && !category.is_empty() {
+        buttons.push((if collapsed { "Expand" } else { "Collapse" }, CategoryAction::ToggleCollapsed));
+    }
+    buttons.push(("Hide", CategoryAction::Hide));
+
+    // Right-aligned, vertically centered in the header
+    let spacing = 4.0;
+    let padding = ui.clone_style().frame_padding[0];
+    let width: f32 = buttons.iter().map(|(label, _)| ui.calc_text_size(label)[0] + padding * 2.0 + spacing).sum();
+    let after = ui.cursor_screen_pos();
+    ui.set_cursor_screen_pos([(max[0] - width).max(min[0]), min[1] + ((max[1] - min[1]) - ui.frame_height()).max(0.0) / 2.0]);
+
+    let _id = ui.push_id(category);
+    for (index, (label, action)) in buttons.into_iter().enumerate() {
+        if index > 0 {
+            ui.same_line_with_spacing(0.0, spacing);
+        }
+        if ui.small_button(label) {
+            PENDING_CATEGORY_ACTION.with(|p| *p.borrow_mut() = Some((category.to_string(), action)));
+        }
+    }
+    ui.set_cursor_screen_pos(after);
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -900,29 +1872,44 @@ fn render_timeline_track(
         .build();
 
     // Pre-calculate common values
-    let elapsed_since_base = current_time - track.base_time;
     let pixels_per_second = available_width / view_range;
+    let hovered = hovered_event();
+    let occurrence_style = CACHED_OCCURRENCE_STYLE.with(|c| c.get());
+    let language = CACHED_EVENT_LANGUAGE.with(|c| c.get());
+    let last_chance_accent = CACHED_LAST_CHANCE.with(|c| c.get());
 
     for event in &track.events {
-        if !event.enabled {
+        if !event.enabled || !event.show_on_timeline {
             continue;
         }
 
-        let time_in_cycle = elapsed_since_base.rem_euclid(event.cycle_duration);
-        let event_start_in_cycle = event.start_offset;
-        let time_to_event_start = event_start_in_cycle - time_in_cycle;
-
-        // Static array instead of Vec allocation
-        let offsets = [
-            time_to_event_start,
-            time_to_event_start + event.cycle_duration,
-            time_to_event_start - event.cycle_duration,
-        ];
-
-        for &time_offset in &offsets {
-            // Early exit optimization
-            if time_offset < -time_before_current as i64 - event.duration 
-                || time_offset > time_after_current as i64 {
+        // Events needing an unowned expansion are hidden or drawn faded
+        let mut content_alpha = match unowned_content_mode(track, event) {
+            Some(UnownedContentMode::Hide) => continue,
+            Some(UnownedContentMode::Dim) => 0.3,
+            None => 1.0,
+        };
+        if occurrence_style.finished_today != FinishedTodayMode::Show && finished_for_today(track, event, current_time) {
+            if occurrence_style.finished_today == FinishedTodayMode::Hide {
+                continue;
+            }
+            content_alpha *= 0.3;
+        }
+        let mut event_color = event.color.to_array();
+        event_color[3] *= content_alpha;
+
+        let is_hovered_event = hovered.as_ref()
+            .is_some_and(|h| h.track_name == track.name && h.event_name == event.name);
+        let travel = travel_seconds(&track.name, &event.name);
+
+        // Every occurrence whose bar (or pre-event segment, or leave marker) reaches into the view
+        let (from, until) = visible_start_range(event, current_time, time_before_current, time_after_current);
+        let until = until.max(current_time + time_after_current as i64 + travel + 1);
+        for start in timeline::next_occurrences(event, track.base_time, from, until) {
+            let time_offset = start - current_time;
+            let is_this_occurrence_active = time_offset <= 0 && time_offset + event.duration > 0;
+            let is_past = time_offset + event.duration <= 0;
+            if is_past && occurrence_style.hide_past {
                 continue;
             }
 
@@ -932,22 +1919,44 @@ fn render_timeline_track(
             let event_start_x = cursor_pos[0] + x_offset;
             let event_end_x = event_start_x + event_width;
 
+            // Pre-event segment: dimmed and hatched, directly before the main bar
+            if event.pre_duration > 0 {
+                let pre_start_x = event_start_x - event.pre_duration as f32 * pixels_per_second;
+                let pre_min = [pre_start_x.max(cursor_pos[0]), cursor_pos[1]];
+                let pre_max = [
+                    event_start_x.min(cursor_pos[0] + available_width),
+                    cursor_pos[1] + track_height,
+                ];
+                if pre_max[0] > pre_min[0] {
+                    draw_pre_event_segment(&draw_list, pre_min, pre_max, event_color);
+                }
+            }
+
+            // "Leave now" marker, until the occurrence starts
+            if travel > 0 && time_offset > 0 {
+                let leave_x = event_start_x - travel as f32 * pixels_per_second;
+                if leave_x >= cursor_pos[0] && leave_x <= cursor_pos[0] + available_width {
+                    draw_leave_marker(&draw_list, leave_x, cursor_pos[1], track_height, event_color);
+                }
+            }
+
             if event_start_x >= cursor_pos[0] + available_width || event_end_x <= cursor_pos[0] {
                 continue;
             }
 
-            let is_active = time_in_cycle >= event.start_offset 
-                && time_in_cycle < event.start_offset + event.duration;
-            let is_this_occurrence_active = is_active && time_offset == time_to_event_start;
-            
             let bar_color = if is_this_occurrence_active {
-                event.color.to_array()
+                event_color
             } else {
+                let (brightness, alpha) = if is_past {
+                    (occurrence_style.past_brightness, occurrence_style.past_alpha)
+                } else {
+                    (occurrence_style.future_brightness, occurrence_style.future_alpha)
+                };
                 [
-                    event.color.r * 0.5,
-                    event.color.g * 0.5,
-                    event.color.b * 0.5,
-                    event.color.a,
+                    event_color[0] * brightness,
+                    event_color[1] * brightness,
+                    event_color[2] * brightness,
+                    event_color[3] * alpha,
                 ]
             };
             
@@ -964,6 +1973,22 @@ fn render_timeline_track(
                     .thickness(event_border_thickness)
                     .build();
             }
+
+            // Last chance before daily reset
+            if let Some(mut accent) = last_chance_accent.filter(|_| !is_past) {
+                if timeline::is_last_before_reset(event, track.base_time, start) {
+                    accent[3] *= content_alpha;
+                    draw_list.add_rect(bar_min, bar_max, accent).thickness(2.0).build();
+                }
+            }
+
+            // Trace every occurrence of the hovered event
+            if is_hovered_event {
+                draw_list.add_rect(bar_min, bar_max, [1.0, 1.0, 1.0, 0.12]).filled(true).build();
+                draw_list.add_rect(bar_min, bar_max, [1.0, 1.0, 1.0, 0.7])
+                    .thickness(1.5)
+                    .build();
+            }
             
             // Use window bounds in screen space for clipping (accounts for scroll automatically)
             let window_pos = ui.window_pos();
@@ -977,34 +2002,213 @@ fn render_timeline_track(
             let text_clip_max = [bar_max[0].min(window_clip_max[0]), bar_max[1].min(window_clip_max[1])];
             
             draw_list.with_clip_rect(text_clip_min, text_clip_max, || {
-                let text_color = get_text_color_for_bg(bar_color);
-                let text_size = ui.calc_text_size(&event.name);
+                let mut text_color = get_text_color_for_bg(bar_color);
+                text_color[3] *= content_alpha;
+                let event_label = event.display_name(language);
+                let text_size = ui.calc_text_size(event_label);
                 let text_pos = [
                     event_start_x + 5.0,
                     cursor_pos[1] + (track_height - text_size[1]) / 2.0,
                 ];
-                draw_list.add_text(text_pos, text_color, &event.name);
+                draw_list.add_text(text_pos, text_color, event_label);
             });
         }
     }
 
-    // Current time line
-    let current_time_x = cursor_pos[0] + (time_position * available_width);
-    draw_list.add_line(
-        [current_time_x, cursor_pos[1]],
-        [current_time_x, cursor_pos[1] + track_height],
-        [1.0, 0.0, 0.0, 1.0],
-    )
-    .thickness(2.0)
-    .build();
+    // Time markers (resets, chosen times), labelled on the ruler
+    CACHED_TIME_MARKERS.with(|markers| {
+        let view_from = current_time - time_before_current as i64;
+        let view_until = current_time + time_after_current as i64 + 1;
+        for marker in markers.borrow().iter() {
+            for time in marker.times_between(view_from, view_until) {
+                let x = cursor_pos[0] + ((time - current_time) as f32 + time_before_current) * pixels_per_second;
+                draw_list.add_line([x, cursor_pos[1]], [x, cursor_pos[1] + track_height], marker.color)
+                    .thickness(1.5)
+                    .build();
+            }
+        }
+    });
+
+    // Current time line (out of view when panned away)
+    if (0.0..=1.0).contains(&time_position) {
+        let current_time_x = cursor_pos[0] + (time_position * available_width);
+        draw_list.add_line(
+            [current_time_x, cursor_pos[1]],
+            [current_time_x, cursor_pos[1] + track_height],
+            [1.0, 0.0, 0.0, 1.0],
+        )
+        .thickness(2.0)
+        .build();
+    }
 
     ui.dummy([available_width, track_height]);
 
-    // Tooltip handling
-    if ui.is_item_hovered() {
+    // Tooltip handling; the current-time line takes precedence over the bars under it
+    let track_hovered = ui.is_item_hovered();
+    let now_x = cursor_pos[0] + time_position * available_width;
+    let on_now_line = track_hovered
+        && (0.0..=1.0).contains(&time_position)
+        && (ui.io().mouse_pos[0] - now_x).abs() <= NOW_LINE_GRAB;
+    if on_now_line {
+        now_line_hovered(ui, current_time);
+    } else if track_hovered {
         handle_track_tooltip(ui, track, current_time, time_before_current, time_after_current, 
                            view_range, cursor_pos, available_width, pixels_per_second);
     }
+
+    let dragging = BAR_DRAG.with(|d| d.borrow().is_some());
+    if (!on_now_line || dragging) && CACHED_EDITABLE_TRACKS.with(|c| c.borrow().contains(&track.name)) {
+        let after_track = ui.cursor_screen_pos();
+        handle_bar_drag(ui, track, current_time, time_before_current, cursor_pos, [available_width, track_height], pixels_per_second);
+        ui.set_cursor_screen_pos(after_track);
+    }
+}
+
+/// Part of an event bar grabbed for dragging
+#[derive(Clone, Copy, PartialEq)]
+enum DragHandle {
+    Start,
+    End,
+    Bar,
+}
+
+/// Drag in progress on an editable track, with the event's timing when it was grabbed
+struct BarDrag {
+    track_name: String,
+    event_index: usize,
+    handle: DragHandle,
+    grab_x: f32,
+    start_offset: i64,
+    duration: i64,
+}
+
+impl BarDrag {
+    /// New (start_offset, duration) for the mouse at `mouse_x`, snapped to `step` seconds
+    fn apply(&self, mouse_x: f32, pixels_per_second: f32, cycle: i64, step: i64) -> (i64, i64) {
+        let delta = ((mouse_x - self.grab_x) / pixels_per_second) as i64;
+        let snap = |seconds: i64| (seconds as f64 / step as f64).round() as i64 * step;
+        let wrap = |offset: i64| if cycle > 0 { offset.rem_euclid(cycle) } else { offset.max(0) };
+        let max_duration = if cycle > 0 { cycle } else { i64::MAX };
+        let end = self.start_offset + self.duration;
+
+        match self.handle {
+            DragHandle::Bar => (wrap(snap(self.start_offset + delta)), self.duration),
+            DragHandle::Start => {
+                let start = snap(self.start_offset + delta).min(end - step);
+                let duration = (end - start).min(max_duration);
+                (wrap(end - duration), duration)
+            }
+            DragHandle::End => {
+                let duration = (snap(end + delta) - self.start_offset).clamp(step, max_duration);
+                (self.start_offset, duration)
+            }
+        }
+    }
+}
+
+/// Bars within this many pixels of an edge resize instead of moving
+const BAR_EDGE_GRAB: f32 = 5.0;
+
+/// Drag editing of event bars on custom tracks: edges change the duration, the bar moves the
+/// start. Snaps to 5 minutes (1 with Ctrl held); events using a recurrence aren't draggable.
+fn handle_bar_drag(
+    ui: &Ui,
+    track: &EventTrack,
+    current_time: i64,
+    time_before_current: f32,
+    cursor_pos: [f32; 2],
+    size: [f32; 2],
+    pixels_per_second: f32,
+) {
+    let mouse = ui.io().mouse_pos;
+    let button_id = format!("##bar_drag_{}", track.name);
+    let dragging_here = BAR_DRAG.with(|d| d.borrow().as_ref().is_some_and(|drag| drag.track_name == track.name));
+
+    if dragging_here {
+        // Keep the handle alive over the whole track so the drag survives leaving the bar
+        ui.set_cursor_screen_pos(cursor_pos);
+        ui.invisible_button(&button_id, size);
+        if !ui.is_item_active() {
+            BAR_DRAG.with(|d| *d.borrow_mut() = None);
+            return;
+        }
+
+        BAR_DRAG.with(|d| {
+            let drag = d.borrow();
+            let Some(drag) = drag.as_ref() else { return };
+            let Some(event) = track.events.get(drag.event_index) else { return };
+            ui.set_mouse_cursor(Some(if drag.handle == DragHandle::Bar { MouseCursor::ResizeAll } else { MouseCursor::ResizeEW }));
+
+            let step = if ui.io().key_ctrl { 60 } else { 300 };
+            let (start_offset, duration) = drag.apply(mouse[0], pixels_per_second, event.cycle_duration, step);
+            if (start_offset, duration) != (event.start_offset, event.duration) {
+                PENDING_BAR_EDIT.with(|p| {
+                    *p.borrow_mut() = Some((track.name.clone(), drag.event_index, start_offset, duration));
+                });
+            }
+            ui.tooltip_text(format!(
+                "{}\nOffset: {}  Duration: {}",
+                event.name,
+                format_duration(start_offset),
+                format_duration(duration)
+            ));
+        });
+        return;
+    }
+
+    if BAR_DRAG.with(|d| d.borrow().is_some())
+        || !ui.is_window_hovered()
+        || !ui.is_mouse_hovering_rect(cursor_pos, [cursor_pos[0] + size[0], cursor_pos[1] + size[1]])
+    {
+        return;
+    }
+
+    // Bar under the cursor: (event index, start x, end x)
+    let view_from = current_time - time_before_current as i64;
+    let view_until = view_from + (size[0] / pixels_per_second) as i64;
+    let hit = track.events.iter().enumerate()
+        .filter(|(_, e)| e.enabled && e.show_on_timeline && e.recurrence.trim().is_empty() && e.spawn_offsets.is_empty())
+        .find_map(|(index, event)| {
+            timeline::next_occurrences(event, track.base_time, view_from - event.duration, view_until)
+                .map(|start| {
+                    let start_x = cursor_pos[0] + ((start - current_time) as f32 + time_before_current) * pixels_per_second;
+                    (start_x, start_x + event.duration as f32 * pixels_per_second)
+                })
+                .find(|&(start_x, end_x)| mouse[0] >= start_x && mouse[0] <= end_x)
+                .map(|(start_x, end_x)| (index, start_x, end_x))
+        });
+    let Some((event_index, start_x, end_x)) = hit else {
+        return;
+    };
+
+    let resizable = end_x - start_x > BAR_EDGE_GRAB * 3.0;
+    let handle = if resizable && mouse[0] - start_x <= BAR_EDGE_GRAB {
+        DragHandle::Start
+    } else if resizable && end_x - mouse[0] <= BAR_EDGE_GRAB {
+        DragHandle::End
+    } else {
+        DragHandle::Bar
+    };
+    ui.set_mouse_cursor(Some(if handle == DragHandle::Bar { MouseCursor::ResizeAll } else { MouseCursor::ResizeEW }));
+
+    // Cover the grabbed bar so pressing on it drags the event instead of the window
+    let bar_min = [start_x.max(cursor_pos[0]), cursor_pos[1]];
+    let bar_max = [end_x.min(cursor_pos[0] + size[0]), cursor_pos[1] + size[1]];
+    ui.set_cursor_screen_pos(bar_min);
+    ui.invisible_button(&button_id, [(bar_max[0] - bar_min[0]).max(1.0), size[1]]);
+    if ui.is_item_active() {
+        let event = &track.events[event_index];
+        BAR_DRAG.with(|d| {
+            *d.borrow_mut() = Some(BarDrag {
+                track_name: track.name.clone(),
+                event_index,
+                handle,
+                grab_x: mouse[0],
+                start_offset: event.start_offset,
+                duration: event.duration,
+            });
+        });
+    }
 }
 
 // Extract tooltip logic to separate function
@@ -1022,25 +2226,30 @@ fn handle_track_tooltip(
 ) {
     let mouse_pos = ui.io().mouse_pos;
     let mouse_x = mouse_pos[0];
-    let elapsed_since_base = current_time - track.base_time;
 
+    let (tooltips_enabled, tooltip_delay, tooltip_mode) = CACHED_TOOLTIP_SETTINGS.with(|c| c.get());
+    let show_tooltip = tooltips_enabled && hover_delay_elapsed(ui, &track.name, tooltip_delay);
+
+    let (hide_past, hide_finished_today) = CACHED_OCCURRENCE_STYLE.with(|c| {
+        let style = c.get();
+        (style.hide_past, style.finished_today == FinishedTodayMode::Hide)
+    });
+    let language = CACHED_EVENT_LANGUAGE.with(|c| c.get());
+
+    // Collect every event under the cursor (bars can overlap), with the hovered occurrence's start
+    let mut hits: Vec<(&TimelineEvent, i64)> = Vec::new();
     for event in &track.events {
-        if !event.enabled {
+        if !event.enabled || !event.show_on_timeline || unowned_content_mode(track, event) == Some(UnownedContentMode::Hide) {
+            continue;
+        }
+        if hide_finished_today && finished_for_today(track, event, current_time) {
             continue;
         }
 
-        let time_in_cycle = elapsed_since_base.rem_euclid(event.cycle_duration);
-        let time_to_event_start = event.start_offset - time_in_cycle;
-
-        let offsets = [
-            time_to_event_start,
-            time_to_event_start + event.cycle_duration,
-            time_to_event_start - event.cycle_duration,
-        ];
-
-        for &time_offset in &offsets {
-            if time_offset < -time_before_current as i64 - event.duration 
-                || time_offset > time_after_current as i64 {
+        let (from, until) = visible_start_range(event, current_time, time_before_current, time_after_current);
+        for start in timeline::next_occurrences(event, track.base_time, from, until) {
+            let time_offset = start - current_time;
+            if hide_past && time_offset + event.duration <= 0 {
                 continue;
             }
 
@@ -1049,75 +2258,417 @@ fn handle_track_tooltip(
 
             let event_start_x = cursor_pos[0] + x_offset;
             let event_end_x = event_start_x + event_width;
+            let pre_start_x = event_start_x - event.pre_duration as f32 * pixels_per_second;
 
-            if mouse_x >= event_start_x && mouse_x <= event_end_x {
-                // Calculate time info for THIS specific occurrence bar
-                let this_occurrence_start = current_time + time_offset;
-                let this_occurrence_end = this_occurrence_start + event.duration;
-                
-                // Determine display text based on timing
-                let (timing_text, _is_active_now) = if current_time >= this_occurrence_start && current_time < this_occurrence_end {
-                    // Currently active
-                    let seconds_remaining = this_occurrence_end - current_time;
-                    let minutes_remaining = (seconds_remaining / 60) as i32;
-                    (format!("Active now ({}m remaining)", minutes_remaining), true)
-                } else if this_occurrence_start > current_time {
-                    // Future occurrence
-                    let seconds_until = this_occurrence_start - current_time;
-                    let minutes_until = (seconds_until / 60) as i32;
-                    (format!("Starts: {} (in {}m)", format_time_only(this_occurrence_start), minutes_until), false)
-                } else {
-                    // Past occurrence
-                    (format!("Ended: {}", format_time_only(this_occurrence_end)), false)
-                };
+            if mouse_x >= pre_start_x && mouse_x <= event_end_x {
+                hits.push((event, current_time + time_offset));
+                break;
+            }
+        }
+    }
 
-                ui.tooltip(|| {
-                    ui.text(format!("Track: {}", track.name));
-                    ui.text(format!("Event: {}", event.name));
-                    ui.separator();
-                    ui.text(&timing_text);
-                    if !event.copy_text.is_empty() {
+    if hits.is_empty() {
+        // No event found, show track name
+        if show_tooltip {
+            ui.tooltip_text(track.display_name(language));
+        }
+        return;
+    }
+
+    // Pick the event that clicks act on; scroll or Shift+click cycles through overlapping ones
+    let mut selected = TOOLTIP_TARGET.with(|t| {
+        t.borrow().as_ref().and_then(|target| {
+            hits.iter().position(|(e, _)| target.track_name == track.name && target.event_name == e.name)
+        })
+    }).unwrap_or(0);
+    let shift_click = ui.io().key_shift && ui.is_mouse_clicked(MouseButton::Left);
+    if hits.len() > 1 {
+        let wheel = ui.io().mouse_wheel;
+        if shift_click || wheel < 0.0 {
+            selected = (selected + 1) % hits.len();
+        } else if wheel > 0.0 {
+            selected = (selected + hits.len() - 1) % hits.len();
+        }
+    }
+    let (event, this_occurrence_start) = hits[selected];
+    TOOLTIP_TARGET.with(|t| *t.borrow_mut() = Some(TrackedEventId::new(&track.name, &event.name)));
+
+    let copy_options = event.copy_options();
+    set_hovered_event(TrackedEventId::new(&track.name, &event.name));
+
+    if show_tooltip {
+        ui.tooltip(|| {
+            ui.text(format!("Track: {}", track.display_name(language)));
+            if tooltip_mode == TooltipMode::Detailed && !track.category.is_empty() {
+                ui.text_disabled(format!("Category: {}", track.category));
+            }
+            for (index, &(hit, hit_start)) in hits.iter().enumerate() {
+                if index != selected {
+                    ui.text_disabled(format!("  {}: {}", hit.display_name(language), occurrence_timing_text(current_time, hit_start, hit.duration)));
+                    continue;
+                }
+
+                ui.text(format!("Event: {}", event.display_name(language)));
+                if !event.chain.is_empty() {
+                    // List the chain's phases in order, marking the hovered one
+                    ui.text_disabled(format!("Chain: {}", event.chain));
+                    let mut seen_phases = HashSet::new();
+                    for phase in track.events.iter().filter(|e| e.chain == event.chain) {
+                        if seen_phases.insert(phase.name.as_str()) {
+                            let marker = if phase.name == event.name { ">" } else { " " };
+                            ui.text_disabled(format!(" {} {}", marker, phase.display_name(language)));
+                        }
+                    }
+                }
+                if !event.map_name.is_empty() {
+                    ui.text_disabled(format!("Map: {}", event.map_name));
+                }
+                if !event.description.is_empty() {
+                    ui.text(&event.description);
+                }
+                if !event.rewards.is_empty() {
+                    ui.text_disabled(format!("Rewards: {}", event.rewards));
+                }
+                ui.separator();
+                ui.text(occurrence_timing_text(current_time, this_occurrence_start, event.duration));
+                if let Some(accent) = CACHED_LAST_CHANCE.with(|c| c.get()) {
+                    if timeline::is_last_before_reset(event, track.base_time, this_occurrence_start) {
+                        ui.same_line();
+                        ui.text_colored(accent, "(last today)");
+                    }
+                }
+                let event_id = TrackedEventId::new(&track.name, &event.name);
+                let (checked_in, attended_count) = CACHED_ATTENDANCE.with(|c| {
+                    let records = c.borrow();
+                    let since = current_time - ATTENDANCE_DAYS * 24 * 3600;
+                    let records = records.iter().filter(|r| r.event_id == event_id && r.start_time > since);
+                    (records.clone().any(|r| r.start_time == this_occurrence_start), records.count())
+                });
+                if checked_in {
+                    ui.text_colored([0.5, 0.9, 0.5, 1.0], "Checked in");
+                }
+                if attended_count > 0 {
+                    ui.text_disabled(format!("Attended {} times in the last {} days", attended_count, ATTENDANCE_DAYS));
+                }
+                if event.pre_duration > 0 {
+                    let pre_start = this_occurrence_start - event.pre_duration;
+                    ui.text_disabled(format!(
+                        "Pre-event: {} ({} before)",
+                        format_time_precise(pre_start),
+                        format_duration(event.pre_duration)
+                    ));
+                }
+                let travel = travel_seconds(&track.name, &event.name);
+                if travel > 0 && this_occurrence_start > current_time {
+                    let leave_at = this_occurrence_start - travel;
+                    if leave_at > current_time {
+                        ui.text_colored(LEAVE_MARKER_COLOR, format!("Leave by {} (in {})", format_time_precise(leave_at), format_duration(leave_at - current_time)));
+                    } else {
+                        ui.text_colored(LEAVE_MARKER_COLOR, "Leave now");
+                    }
+                }
+                if tooltip_mode == TooltipMode::Detailed {
+                    let (tyria_hours, tyria_minutes) = calculate_tyria_time(this_occurrence_start);
+                    ui.text_disabled(format!("Duration: {}", format_duration(event.duration)));
+                    ui.text_disabled(format!("Tyria time at start: {:02}:{:02}", tyria_hours, tyria_minutes));
+                    if let Some(next_start) = timeline::next_occurrence_after(event, track.base_time, this_occurrence_start) {
+                        ui.text_disabled(format!(
+                            "Next after this: {} (in {})",
+                            format_time_precise(next_start),
+                            format_duration(next_start - current_time)
+                        ));
+                    }
+                }
+                match copy_options.as_slice() {
+                    [] => {}
+                    [option] => {
+                        ui.separator();
+                        ui.text(format!("Click to copy: {}", option.text));
+                    }
+                    options => {
                         ui.separator();
-                        ui.text(format!("Click to copy: {}", event.copy_text));
+                        ui.text(format!("Click for copy options ({})", options.len()));
+                    }
+                }
+                if index + 1 < hits.len() {
+                    ui.separator();
+                }
+            }
+            if hits.len() > 1 {
+                ui.separator();
+                ui.text_disabled(format!("{} overlapping events - scroll or Shift+click to switch", hits.len()));
+            }
+        });
+    }
+
+    // Double-click: open this event in the track editor
+    if ui.is_mouse_double_clicked(MouseButton::Left) {
+        PENDING_EDITOR_OPEN.with(|p| {
+            *p.borrow_mut() = Some((track.name.clone(), event.name.clone()));
+        });
+    } else if ui.is_mouse_clicked(MouseButton::Left) && !shift_click {
+        match copy_options.as_slice() {
+            [] => {}
+            [option] => {
+                let copy_text = CACHED_COPY_WITH_EVENT_NAME.with(|c| {
+                    if c.get() {
+                        format!("{}: {}", event.name, option.text)
+                    } else {
+                        option.text.clone()
                     }
                 });
+                ui.set_clipboard_text(&copy_text);
+            }
+            _ => {
+                COPY_MENU_EVENT.with(|e| {
+                    *e.borrow_mut() = Some((event.name.clone(), copy_options.clone()));
+                });
+                OPEN_COPY_MENU.with(|f| f.set(true));
+            }
+        }
+    }
 
-                if ui.is_mouse_clicked(MouseButton::Left) && !event.copy_text.is_empty() {
-                    let copy_text = CACHED_COPY_WITH_EVENT_NAME.with(|c| {
-                        if c.get() {
-                            format!("{}: {}", event.name, event.copy_text)
-                        } else {
-                            event.copy_text.clone()
-                        }
-                    });
-                    ui.set_clipboard_text(&copy_text);
-                }
+    // Right-click to track/untrack event
+    if ui.is_mouse_clicked(MouseButton::Right) {
+        // Check tracked status from cached value (avoids deadlock)
+        let event_id = TrackedEventId::new(&track.name, &event.name);
+        let is_tracked = CACHED_TRACKED_EVENTS.with(|c| {
+            c.borrow().contains(&event_id)
+        });
+        let is_oneshot = CACHED_ONESHOT_EVENTS.with(|c| {
+            c.borrow().contains(&event_id)
+        });
+        CONTEXT_EVENT.with(|e| {
+            *e.borrow_mut() = Some((track.name.clone(), event.name.clone(), is_tracked, is_oneshot));
+        });
+        // A finished occurrence can't notify any more, so muting targets the next one
+        let mute_start = if this_occurrence_start + event.duration <= current_time {
+            timeline::next_occurrence_after(event, track.base_time, current_time)
+        } else {
+            Some(this_occurrence_start)
+        };
+        CONTEXT_OCCURRENCE.with(|o| {
+            *o.borrow_mut() = mute_start
+                .map(|start| (start, NOTIFICATION_STATE.lock().is_occurrence_muted(&event_id, start)));
+        });
+        OPEN_EVENT_MENU.with(|f| {
+            *f.borrow_mut() = true;
+        });
+    }
+}
 
-                // Right-click to track/untrack event
-                if ui.is_mouse_clicked(MouseButton::Right) {
-                    // Check tracked status from cached value (avoids deadlock)
-                    let event_id = TrackedEventId::new(&track.name, &event.name);
-                    let is_tracked = CACHED_TRACKED_EVENTS.with(|c| {
-                        c.borrow().contains(&event_id)
-                    });
-                    let is_oneshot = CACHED_ONESHOT_EVENTS.with(|c| {
-                        c.borrow().contains(&event_id)
-                    });
-                    CONTEXT_EVENT.with(|e| {
-                        *e.borrow_mut() = Some((track.name.clone(), event.name.clone(), is_tracked, is_oneshot));
-                    });
-                    OPEN_EVENT_MENU.with(|f| {
-                        *f.borrow_mut() = true;
-                    });
-                }
+/// Window size, and position when it has to move
+type WindowGeometry = ([f32; 2], Option<[f32; 2]>);
+
+/// Main window sizes offered in the context menu
+#[derive(Clone, Copy)]
+enum WindowSizePreset {
+    /// One short row, for docking the timeline as a thin strip
+    CompactBar,
+    HalfHeight,
+    /// Full screen height, moved to the top
+    FullColumn,
+}
+
+impl WindowSizePreset {
+    const ALL: [Self; 3] = [Self::CompactBar, Self::HalfHeight, Self::FullColumn];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::CompactBar => "Compact Bar",
+            Self::HalfHeight => "Half Height",
+            Self::FullColumn => "Full Column",
+        }
+    }
+
+    /// Geometry for a window currently at `position` with `size`; the width is kept
+    fn geometry(self, display_size: [f32; 2], size: [f32; 2], position: [f32; 2]) -> WindowGeometry {
+        match self {
+            Self::CompactBar => ([size[0], 90.0], None),
+            Self::HalfHeight => ([size[0], display_size[1] / 2.0], None),
+            Self::FullColumn => ([size[0], display_size[1]], Some([position[0], 0.0])),
+        }
+    }
+}
+
+/// Quick action picked from a category header
+enum CategoryAction {
+    Hide,
+    ToggleCollapsed,
+    SetTracked(bool),
+}
+
+/// How occurrences other than the running one are drawn
+#[derive(Clone, Copy)]
+struct OccurrenceStyle {
+    past_brightness: f32,
+    past_alpha: f32,
+    future_brightness: f32,
+    future_alpha: f32,
+    hide_past: bool,
+    finished_today: FinishedTodayMode,
+}
+
+impl OccurrenceStyle {
+    const DEFAULT: Self = Self {
+        past_brightness: 0.5,
+        past_alpha: 1.0,
+        future_brightness: 0.5,
+        future_alpha: 1.0,
+        hide_past: false,
+        finished_today: FinishedTodayMode::Show,
+    };
+}
+
+/// Start of the overview cycle containing `time`, aligned to its base time calculator
+fn current_cycle_start(time: i64, cycle: i64, calculator: &str) -> i64 {
+    time - (time - get_base_time_from_calculator(calculator)).rem_euclid(cycle)
+}
+
+/// Whether an event on a whole-day cycle ran earlier today and has nothing left before local midnight
+fn finished_for_today(track: &EventTrack, event: &TimelineEvent, current_time: i64) -> bool {
+    const DAY: i64 = 24 * 3600;
+    if event.cycle_duration <= 0 || event.cycle_duration % DAY != 0 {
+        return false;
+    }
+    let day_start = local_day_start(current_time);
+    timeline::active_occurrence(event, track.base_time, current_time).is_none()
+        && timeline::next_occurrences(event, track.base_time, day_start, current_time).next().is_some()
+        && timeline::next_occurrence_after(event, track.base_time, current_time).is_none_or(|start| start >= day_start + DAY)
+}
 
-                return; // Found event, exit early
+/// How to show an event that needs an expansion the player doesn't own (None if owned)
+fn unowned_content_mode(track: &EventTrack, event: &TimelineEvent) -> Option<UnownedContentMode> {
+    let expansion = event.required_expansion(track);
+    if expansion.is_empty() {
+        return None;
+    }
+    CACHED_UNOWNED_CONTENT.with(|c| {
+        let snapshot = c.borrow();
+        let snapshot = snapshot.as_ref()?;
+        (!snapshot.owns_expansion(expansion)).then_some(snapshot.unowned_content_mode)
+    })
+}
+
+/// Whether the track has been hovered continuously for at least `delay` seconds
+fn hover_delay_elapsed(ui: &Ui, track_name: &str, delay: f32) -> bool {
+    let now = ui.time();
+    TOOLTIP_HOVER.with(|h| {
+        let mut hover = h.borrow_mut();
+        match hover.as_mut() {
+            // Still hovering the same track (seen within the last few frames)
+            Some((name, start, last_seen)) if name == track_name && now - *last_seen < 0.1 => {
+                *last_seen = now;
+                now - *start >= delay as f64
+            }
+            _ => {
+                *hover = Some((track_name.to_string(), now, now));
+                delay <= 0.0
             }
         }
+    })
+}
+
+const FOCUS_COLOR: [f32; 4] = [0.5, 0.8, 1.0, 1.0];
+
+/// Focus the view on an event, or go back to following the current time
+fn set_focus(event_id: Option<TrackedEventId>) {
+    FOCUS_EVENT.with(|f| *f.borrow_mut() = event_id);
+    VIEW_PAN.with(|p| p.set(0.0));
+}
+
+/// Pan that keeps the focused event's running or next occurrence in view: none while it
+/// already fits, otherwise just enough to show its end (its start at most a quarter in).
+/// Drops the focus when the event is gone or disabled.
+fn focus_pan(config: &crate::config::RuntimeConfig, current_time: i64, view_range: f32, time_position: f32) -> Option<f32> {
+    let focus = FOCUS_EVENT.with(|f| f.borrow().clone())?;
+    let occurrence = config.tracks.iter()
+        .filter(|t| t.name == focus.track_name)
+        .flat_map(|t| {
+            t.events.iter()
+                .filter(|e| e.enabled && e.name == focus.event_name)
+                .filter_map(move |e| timeline::current_or_next(e, t.base_time, current_time).map(|start| (start, e.duration)))
+        })
+        .min_by_key(|&(start, _)| start);
+    let Some((start, duration)) = occurrence else {
+        set_focus(None);
+        return None;
+    };
+
+    let view_end = current_time as f32 + view_range * (1.0 - time_position);
+    let target_end = ((start + duration) as f32).min(start as f32 + view_range * 0.75);
+    Some((target_end - view_end).max(0.0))
+}
+
+/// Travel time choices offered in the event context menu, in minutes
+const TRAVEL_TIME_CHOICES: [u32; 6] = [0, 2, 5, 10, 15, 20];
+
+const LEAVE_MARKER_COLOR: [f32; 4] = [1.0, 0.85, 0.3, 1.0];
+
+/// Travel buffer of an event in seconds, from this frame's cache
+fn travel_seconds(track_name: &str, event_name: &str) -> i64 {
+    CACHED_TRAVEL_BUFFERS.with(|c| {
+        c.borrow()
+            .iter()
+            .find(|entry| entry.event_id.track_name == track_name && entry.event_id.event_name == event_name)
+            .map_or(0, |entry| entry.minutes as i64 * 60)
+    })
+}
+
+/// Dashed vertical line with a flag at the top, where the player has to set off
+fn draw_leave_marker(draw_list: &DrawListMut, x: f32, top: f32, height: f32, event_color: [f32; 4]) {
+    let dash = 4.0;
+    let mut y = top;
+    while y < top + height {
+        draw_list.add_line([x, y], [x, (y + dash).min(top + height)], LEAVE_MARKER_COLOR).thickness(2.0).build();
+        y += dash * 2.0;
     }
+    let flag = (height * 0.3).clamp(4.0, 8.0);
+    draw_list
+        .add_triangle([x, top], [x + flag, top + flag / 2.0], [x, top + flag], [event_color[0], event_color[1], event_color[2], 1.0])
+        .filled(true)
+        .build();
+}
 
-    // No event found, show track name
-    ui.tooltip_text(&track.name);
+/// Range of start times [from, until) whose occurrences are at least partly in view
+fn visible_start_range(event: &TimelineEvent, current_time: i64, time_before: f32, time_after: f32) -> (i64, i64) {
+    (
+        current_time - time_before as i64 - event.duration,
+        current_time + time_after as i64 + event.pre_duration + 1,
+    )
+}
+
+/// "Active now (...)", "Starts: ..." or "Ended: ..." for one occurrence
+fn occurrence_timing_text(current_time: i64, start: i64, duration: i64) -> String {
+    let end = start + duration;
+    if current_time >= start && current_time < end {
+        format!("Active now ({} remaining)", format_duration(end - current_time))
+    } else if start > current_time {
+        format!("Starts: {} (in {})", format_time_precise(start), format_duration(start - current_time))
+    } else {
+        format!("Ended: {}", format_time_precise(end))
+    }
+}
+
+/// Draw a pre-event (warmup) segment as a faint fill with diagonal hatching
+fn draw_pre_event_segment(draw_list: &DrawListMut, min: [f32; 2], max: [f32; 2], color: [f32; 4]) {
+    const HATCH_SPACING: f32 = 8.0;
+
+    let fill_color = [color[0] * 0.5, color[1] * 0.5, color[2] * 0.5, color[3] * 0.35];
+    let hatch_color = [color[0], color[1], color[2], color[3] * 0.6];
+    draw_list.add_rect(min, max, fill_color).filled(true).build();
+
+    let height = max[1] - min[1];
+    draw_list.with_clip_rect_intersect(min, max, || {
+        let mut x = min[0] - height;
+        while x < max[0] {
+            draw_list
+                .add_line([x, max[1]], [x + height, min[1]], hatch_color)
+                .thickness(1.0)
+                .build();
+            x += HATCH_SPACING;
+        }
+    });
 }
 
 fn get_text_color_for_bg(bg_color: [f32; 4]) -> [f32; 4] {
@@ -1,8 +1,21 @@
+pub mod agenda;
+pub mod diagnostics;
+pub mod json_editor;
+pub mod label_cache;
+pub mod log_viewer;
 pub mod main_window;
 pub mod notifications;
 pub mod settings;
+pub mod snapping;
 pub mod time_ruler;
+pub mod track_wizard;
+pub mod whats_new;
 
+pub use agenda::render_agenda_window;
+pub use diagnostics::render_diagnostics_overlay;
+pub use log_viewer::render_log_window;
 pub use main_window::render_main_window;
-pub use notifications::{render_toast_notifications, render_upcoming_panel};
-pub use settings::{render_settings, check_for_event_tracks_update};
\ No newline at end of file
+pub use notifications::{render_center_alert, render_toast_notifications, render_upcoming_panel};
+pub use settings::{render_settings, render_track_editor_window, check_for_event_tracks_update};
+pub use track_wizard::render_track_wizard;
+pub use whats_new::render_whats_new_window;
\ No newline at end of file
@@ -1,8 +1,21 @@
-use nexus::imgui::{Condition, MenuItem, MouseButton, StyleColor, StyleVar, Ui, Window, WindowFlags};
+use nexus::imgui::{
+    Condition, MenuItem, MouseButton, Selectable, StyleColor, StyleVar, TableBgTarget, TableColumnFlags, TableColumnSetup,
+    TableFlags, TableSortDirection, Ui, Window, WindowFlags,
+};
 
-use crate::config::{NotificationConfig, ToastPosition, RUNTIME_CONFIG};
-use crate::notifications::{ToastNotification, NOTIFICATION_STATE};
-use crate::time_utils::format_time_only;
+use crate::config::{EventPriority, NotificationConfig, ToastPosition, UpcomingColumn, UpcomingGrouping, RUNTIME_CONFIG};
+use crate::notifications::{NotificationState, ToastNotification, UpcomingEvent, NOTIFICATION_STATE};
+use crate::time_utils::{format_duration, format_mmss, format_time_precise, get_current_unix_time};
+use crate::ui::{label_cache, snapping};
+
+/// Warning color for tracked events whose active windows overlap
+pub(crate) const CONFLICT_COLOR: [f32; 4] = [1.0, 0.6, 0.2, 1.0];
+
+/// Color of the user's event notes
+const NOTE_COLOR: [f32; 4] = [1.0, 0.9, 0.5, 1.0];
+
+/// Opacity of Upcoming rows for events needing an unowned expansion
+const UNOWNED_ROW_ALPHA: f32 = 0.4;
 
 /// Calculate toast position based on config
 fn calculate_toast_position(
@@ -45,12 +58,14 @@ fn calculate_toast_position(
 struct ToastAction {
     copy_clicked: bool,
     dismissed: bool,
+    muted: bool,
 }
 
 /// Render a single toast notification
 fn render_single_toast(
     ui: &Ui,
     toast: &ToastNotification,
+    (event_label, track_label): (&str, &str),
     position: [f32; 2],
     size: [f32; 2],
     config: &NotificationConfig,
@@ -58,9 +73,15 @@ fn render_single_toast(
     let mut action = ToastAction {
         copy_clicked: false,
         dismissed: false,
+        muted: false,
     };
     let _alpha = ui.push_style_var(StyleVar::Alpha(toast.opacity));
-    let _bg = ui.push_style_color(StyleColor::WindowBg, config.toast_bg_color);
+    let background = if toast.priority == EventPriority::High {
+        config.high_priority_toast_color
+    } else {
+        config.toast_bg_color
+    };
+    let _bg = ui.push_style_color(StyleColor::WindowBg, background);
 
     let window_flags = WindowFlags::NO_DECORATION
         | WindowFlags::NO_MOVE
@@ -69,7 +90,7 @@ fn render_single_toast(
         | WindowFlags::NO_FOCUS_ON_APPEARING
         | WindowFlags::NO_NAV;
 
-    Window::new(format!("##toast_{}", toast.id))
+    Window::new(&*label_cache::label(("toast_window", toast.id), || format!("##toast_{}", toast.id)))
         .position(position, Condition::Always)
         .size(size, Condition::Always)
         .flags(window_flags)
@@ -114,30 +135,44 @@ fn render_single_toast(
 
             // Event name (title)
             ui.set_window_font_scale(scale);
-            ui.text_colored(config.toast_title_color, &toast.event_id.event_name);
+            ui.text_colored(config.toast_title_color, event_label);
 
             // Track name
             ui.set_window_font_scale(scale * 0.85);
-            ui.text_colored(config.toast_track_color, &toast.event_id.track_name);
+            ui.text_colored(config.toast_track_color, track_label);
 
             // Reminder message and time info
             ui.set_window_font_scale(scale);
-            let time_text = if toast.minutes_until > 0 {
-                // Upcoming event: show minutes until
-                format!("{} ({} min)", toast.reminder_name, toast.minutes_until)
-            } else if toast.minutes_until < 0 {
-                // Ongoing event: negative value means minutes ago
-                format!("{} ({}m ago)", toast.reminder_name, -toast.minutes_until)
+            let time_text = label_cache::label(("toast_time", toast.id), || if toast.until_end {
+                // End reminder: time left in the running event
+                format!("{} ({} left)", toast.reminder_name, format_duration(toast.seconds_until.max(0)))
+            } else if toast.seconds_until > 0 {
+                // Upcoming event: show time until
+                format!("{} ({})", toast.reminder_name, format_duration(toast.seconds_until))
+            } else if toast.seconds_until < 0 {
+                // Ongoing event: negative value means time ago
+                format!("{} ({} ago)", toast.reminder_name, format_duration(-toast.seconds_until))
             } else {
-                // Just started (minutes_until == 0)
+                // Just started (seconds_until == 0)
                 format!("{} (now!)", toast.reminder_name)
-            };
-            ui.text_colored(toast.reminder_color, &time_text);
+            });
+            ui.text_colored(toast.reminder_color, &*time_text);
+            if toast.last_chance {
+                ui.same_line();
+                ui.text_disabled("(last today)");
+            }
 
-            // Click hint if copy_text available
-            if !toast.copy_text.is_empty() {
-                ui.set_window_font_scale(scale * 0.7);
-                ui.text_colored([0.5, 0.5, 0.5, 1.0], "Click to copy waypoint");
+            // The event's note takes the place of the click hints
+            ui.set_window_font_scale(scale * 0.7);
+            if let Some(note) = config.event_note(&toast.event_id).filter(|_| !toast.is_summary) {
+                ui.set_window_font_scale(scale * 0.85);
+                ui.text_colored(NOTE_COLOR, note);
+            } else if toast.is_summary {
+                ui.text_colored([0.5, 0.5, 0.5, 1.0], "Click to dismiss");
+            } else if !toast.copy_text.is_empty() {
+                ui.text_colored([0.5, 0.5, 0.5, 1.0], "Click to copy waypoint, right-click to mute until next spawn");
+            } else {
+                ui.text_colored([0.5, 0.5, 0.5, 1.0], "Right-click to mute until next spawn");
             }
 
             ui.set_window_font_scale(1.0);
@@ -148,19 +183,59 @@ fn render_single_toast(
             }
             // Check for click anywhere else to copy waypoint
             else if ui.is_window_hovered() && ui.is_mouse_clicked(MouseButton::Left) {
-                action.copy_clicked = true;
+                if toast.is_summary {
+                    action.dismissed = true;
+                } else {
+                    action.copy_clicked = true;
+                }
+            }
+            // Right-click: silence the rest of this occurrence
+            else if !toast.is_summary && ui.is_window_hovered() && ui.is_mouse_clicked(MouseButton::Right) {
+                action.muted = true;
             }
         });
 
     action
 }
 
+/// Snooze countdown where toasts appear; true when clicked to resume
+fn render_snooze_indicator(
+    ui: &Ui,
+    seconds_left: i64,
+    position: ToastPosition,
+    display_size: [f32; 2],
+    offset_x: f32,
+    offset_y: f32,
+) -> bool {
+    let size = [170.0, ui.text_line_height() + 12.0];
+    let pos = calculate_toast_position(0, position, size, display_size, offset_x, offset_y);
+    let mut clicked = false;
+    Window::new("##snooze_indicator")
+        .position(pos, Condition::Always)
+        .size(size, Condition::Always)
+        .bg_alpha(0.7)
+        .flags(
+            WindowFlags::NO_DECORATION
+                | WindowFlags::NO_MOVE
+                | WindowFlags::NO_SAVED_SETTINGS
+                | WindowFlags::NO_FOCUS_ON_APPEARING
+                | WindowFlags::NO_NAV,
+        )
+        .build(ui, || {
+            ui.text_colored([0.7, 0.7, 0.7, 1.0], format!("Snoozed {}", format_mmss(seconds_left)));
+            if ui.is_window_hovered() {
+                ui.tooltip_text("Reminders are paused. Click to resume now.");
+                clicked = ui.is_mouse_clicked(MouseButton::Left);
+            }
+        });
+    clicked
+}
+
 /// Render toast notifications (call from main render loop)
 pub fn render_toast_notifications(ui: &Ui) {
-    let (notification_config, copy_with_event_name) = {
-        let config = RUNTIME_CONFIG.lock();
-        (config.notification_config.clone(), config.copy_with_event_name)
-    };
+    let snapshot = RUNTIME_CONFIG.snapshot();
+    let notification_config = &snapshot.notification_config;
+    let copy_with_event_name = snapshot.copy_with_event_name;
 
     let toast_position = notification_config.toast_position;
     let toast_size = notification_config.toast_size;
@@ -176,10 +251,11 @@ pub fn render_toast_notifications(ui: &Ui) {
         if let Some(preview) = &state.preview_toast {
             let display_size = ui.io().display_size;
             let pos = calculate_toast_position(0, toast_position, toast_size, display_size, offset_x, offset_y);
-            let action = render_single_toast(ui, preview, pos, toast_size, &notification_config);
+            let labels = state.labels(&preview.event_id);
+            let action = render_single_toast(ui, preview, labels, pos, toast_size, notification_config);
             if action.copy_clicked && !preview.copy_text.is_empty() {
                 let copy_text = if copy_with_event_name {
-                    format!("{}: {}", preview.event_id.event_name, preview.copy_text)
+                    format!("{}: {}", labels.0, preview.copy_text)
                 } else {
                     preview.copy_text.clone()
                 };
@@ -191,6 +267,13 @@ pub fn render_toast_notifications(ui: &Ui) {
         }
     }
 
+    if let Some(left) = notification_config.snooze_remaining(get_current_unix_time()) {
+        let display_size = ui.io().display_size;
+        if render_snooze_indicator(ui, left, toast_position, display_size, offset_x, offset_y) {
+            RUNTIME_CONFIG.lock().notification_config.snoozed_until = None;
+        }
+    }
+
     if !notification_config.toast_enabled {
         return;
     }
@@ -198,6 +281,7 @@ pub fn render_toast_notifications(ui: &Ui) {
     // Collect actions from clicked toasts
     let mut copy_text_to_set: Option<String> = None;
     let mut toasts_to_dismiss: Vec<u64> = Vec::new();
+    let mut occurrences_to_mute: Vec<(crate::config::TrackedEventId, i64)> = Vec::new();
 
     {
         let state = NOTIFICATION_STATE.lock();
@@ -215,10 +299,11 @@ pub fn render_toast_notifications(ui: &Ui) {
                 offset_x,
                 offset_y,
             );
-            let action = render_single_toast(ui, toast, pos, toast_size, &notification_config);
+            let labels = state.labels(&toast.event_id);
+            let action = render_single_toast(ui, toast, labels, pos, toast_size, notification_config);
             if action.copy_clicked && !toast.copy_text.is_empty() {
                 let copy_text = if copy_with_event_name {
-                    format!("{}: {}", toast.event_id.event_name, toast.copy_text)
+                    format!("{}: {}", labels.0, toast.copy_text)
                 } else {
                     toast.copy_text.clone()
                 };
@@ -227,6 +312,9 @@ pub fn render_toast_notifications(ui: &Ui) {
             if action.dismissed {
                 toasts_to_dismiss.push(toast.id);
             }
+            if action.muted {
+                occurrences_to_mute.push((toast.event_id.clone(), toast.event_start_time));
+            }
         }
     }
 
@@ -235,14 +323,99 @@ pub fn render_toast_notifications(ui: &Ui) {
         ui.set_clipboard_text(&text);
     }
 
-    // Dismiss toasts outside of render lock
-    if !toasts_to_dismiss.is_empty() {
+    // Dismiss and mute toasts outside of render lock
+    if !toasts_to_dismiss.is_empty() || !occurrences_to_mute.is_empty() {
         let mut state = NOTIFICATION_STATE.lock();
         for id in toasts_to_dismiss {
             if let Some(toast) = state.toast_queue.iter_mut().find(|t| t.id == id) {
                 toast.dismissed = true;
             }
         }
+        for (event_id, start_time) in occurrences_to_mute {
+            state.set_occurrence_muted(&event_id, start_time, true);
+        }
+    }
+}
+
+/// Render the center-screen countdown alert (call from main render loop)
+pub fn render_center_alert(ui: &Ui) {
+    let (duration, text_scale, offset_y, color) = {
+        let config = RUNTIME_CONFIG.lock();
+        let notifications = &config.notification_config;
+        (
+            notifications.center_alert_duration,
+            notifications.center_alert_text_scale,
+            notifications.center_alert_offset_y,
+            notifications.toast_title_color,
+        )
+    };
+
+    let (alert, event_label) = {
+        let mut state = NOTIFICATION_STATE.lock();
+        match &state.center_alert {
+            Some(alert) if alert.shown_at.elapsed().as_secs_f32() < duration => {
+                (alert.clone(), state.labels(&alert.event_id).0.to_string())
+            }
+            Some(_) => {
+                state.center_alert = None;
+                return;
+            }
+            None => return,
+        }
+    };
+
+    let seconds = alert.start_time - get_current_unix_time();
+    let text = if seconds > 0 {
+        format!("{} in {}", event_label, format_mmss(seconds))
+    } else {
+        format!("{} now!", event_label)
+    };
+    // Fade out over the last half second
+    let opacity = ((duration - alert.shown_at.elapsed().as_secs_f32()) / 0.5).clamp(0.0, 1.0);
+
+    let display_size = ui.io().display_size;
+    Window::new("##center_alert")
+        .position([display_size[0] * 0.5, display_size[1] * offset_y], Condition::Always)
+        .position_pivot([0.5, 0.5])
+        .flags(
+            WindowFlags::NO_DECORATION
+                | WindowFlags::NO_INPUTS
+                | WindowFlags::NO_BACKGROUND
+                | WindowFlags::ALWAYS_AUTO_RESIZE
+                | WindowFlags::NO_SAVED_SETTINGS
+                | WindowFlags::NO_FOCUS_ON_APPEARING,
+        )
+        .build(ui, || {
+            ui.set_window_font_scale(text_scale);
+            // Drop shadow keeps the text readable over bright scenery
+            let pos = ui.cursor_screen_pos();
+            ui.get_window_draw_list()
+                .add_text([pos[0] + 2.0, pos[1] + 2.0], [0.0, 0.0, 0.0, 0.8 * opacity], &text);
+            ui.text_colored([color[0], color[1], color[2], color[3] * opacity], &text);
+            ui.set_window_font_scale(1.0);
+        });
+}
+
+/// Window chrome options of the Upcoming panel
+struct PanelChrome {
+    locked: bool,
+    size_locked: bool,
+    click_through: bool,
+    hide_background: bool,
+    show_title_bar: bool,
+    opacity: f32,
+}
+
+impl PanelChrome {
+    fn from_config(config: &NotificationConfig) -> Self {
+        Self {
+            locked: config.upcoming_panel_locked,
+            size_locked: config.upcoming_panel_size_locked,
+            click_through: config.upcoming_panel_click_through,
+            hide_background: config.upcoming_panel_hide_background,
+            show_title_bar: config.upcoming_panel_show_title_bar,
+            opacity: config.upcoming_panel_opacity,
+        }
     }
 }
 
@@ -252,14 +425,126 @@ thread_local! {
     static UPCOMING_OPEN_MENU: std::cell::Cell<bool> = std::cell::Cell::new(false);
 }
 
+/// Columns and sort order of the Upcoming panel table
+struct PanelLayout {
+    columns: Vec<UpcomingColumn>,
+    sort_column: UpcomingColumn,
+    sort_descending: bool,
+    group_by: UpcomingGrouping,
+    collapsed_groups: Vec<String>,
+    /// Rows pulse this many seconds before their event starts (0 = off)
+    pulse_seconds: i64,
+    colors: TimeColors,
+}
+
+/// Colors of the panel's time column
+struct TimeColors {
+    upcoming: [f32; 4],
+    active: [f32; 4],
+    imminent: [f32; 4],
+    imminent_seconds: i64,
+}
+
+impl PanelLayout {
+    fn from_config(config: &NotificationConfig) -> Self {
+        let mut columns = vec![UpcomingColumn::Time, UpcomingColumn::Event];
+        if config.upcoming_show_track_column {
+            columns.push(UpcomingColumn::Track);
+        }
+        if config.upcoming_show_waypoint_column {
+            columns.push(UpcomingColumn::Waypoint);
+        }
+        // A hidden column can't hold the sort
+        let sort_column = if columns.contains(&config.upcoming_sort_column) {
+            config.upcoming_sort_column
+        } else {
+            UpcomingColumn::Time
+        };
+        Self {
+            columns,
+            sort_column,
+            sort_descending: config.upcoming_sort_descending,
+            group_by: config.upcoming_group_by,
+            collapsed_groups: config.upcoming_collapsed_groups.clone(),
+            pulse_seconds: if config.upcoming_countdown_pulse { config.upcoming_countdown_seconds as i64 } else { 0 },
+            colors: TimeColors {
+                upcoming: config.upcoming_time_color,
+                active: config.active_time_color,
+                imminent: config.imminent_time_color,
+                imminent_seconds: config.imminent_seconds as i64,
+            },
+        }
+    }
+}
+
+/// Sort panel rows by a column, ties (and the Time column) by start time
+fn sort_upcoming(events: &mut [&UpcomingEvent], column: UpcomingColumn, descending: bool, state: &NotificationState) {
+    events.sort_by(|a, b| {
+        let (a_labels, b_labels) = (state.labels(&a.event_id), state.labels(&b.event_id));
+        let order = match column {
+            UpcomingColumn::Time => a.start_time.cmp(&b.start_time),
+            UpcomingColumn::Event => a_labels.0.to_lowercase().cmp(&b_labels.0.to_lowercase()),
+            UpcomingColumn::Track => a_labels.1.to_lowercase().cmp(&b_labels.1.to_lowercase()),
+            UpcomingColumn::Waypoint => a.copy_text.cmp(&b.copy_text),
+        };
+        let order = if descending { order.reverse() } else { order };
+        order.then(a.start_time.cmp(&b.start_time))
+    });
+}
+
+/// Split sorted rows into groups, ordered by their first row. Ungrouped rows form a single
+/// group without a header.
+fn group_upcoming<'a>(
+    events: Vec<&'a UpcomingEvent>,
+    group_by: UpcomingGrouping,
+    state: &NotificationState,
+) -> Vec<(Option<String>, Vec<&'a UpcomingEvent>)> {
+    if group_by == UpcomingGrouping::None {
+        return vec![(None, events)];
+    }
+
+    let mut groups: Vec<(Option<String>, Vec<&UpcomingEvent>)> = Vec::new();
+    for event in events {
+        let name = match group_by {
+            UpcomingGrouping::Category if event.category.is_empty() => "Uncategorized",
+            UpcomingGrouping::Category => event.category.as_str(),
+            _ => state.labels(&event.event_id).1,
+        };
+        match groups.iter_mut().find(|(group, _)| group.as_deref() == Some(name)) {
+            Some((_, rows)) => rows.push(event),
+            None => groups.push((Some(name.to_string()), vec![event])),
+        }
+    }
+    groups
+}
+
 /// Render the upcoming events panel
 pub fn render_upcoming_panel(ui: &Ui) {
-    let (panel_enabled, panel_size, copy_with_event_name) = {
-        let config = RUNTIME_CONFIG.lock();
+    let display_size = ui.io().display_size;
+    let (panel_enabled, panel_size, copy_with_event_name, docked, dock_side, snap_windows, snap_threshold, main_window_shown, relative_pos, chrome, layout) = {
+        let mut config = RUNTIME_CONFIG.lock();
+        let relative_pos = if config.relative_window_positions && config.notification_config.upcoming_panel_enabled {
+            let mut stored = config.notification_config.upcoming_panel_position;
+            let pos = snapping::relative_position("Upcoming Events", &mut stored, display_size);
+            if stored != config.notification_config.upcoming_panel_position {
+                config.notification_config.upcoming_panel_position = stored;
+            }
+            pos
+        } else {
+            None
+        };
         (
             config.notification_config.upcoming_panel_enabled,
             config.notification_config.upcoming_panel_size,
             config.copy_with_event_name,
+            config.notification_config.upcoming_panel_docked,
+            config.notification_config.upcoming_panel_dock_side,
+            config.snap_windows,
+            config.snap_threshold,
+            config.show_main_window,
+            relative_pos,
+            PanelChrome::from_config(&config.notification_config),
+            PanelLayout::from_config(&config.notification_config),
         )
     };
 
@@ -271,91 +556,246 @@ pub fn render_upcoming_panel(ui: &Ui) {
     let mut copy_text_to_set: Option<String> = None;
     let mut event_to_untrack: Option<crate::config::TrackedEventId> = None;
     let mut wiki_to_open: Option<String> = None;
+    let mut sort_change: Option<(UpcomingColumn, bool)> = None;
+    let mut group_to_toggle: Option<String> = None;
 
     {
         let state = NOTIFICATION_STATE.lock();
 
         let mut opened = true;
-        Window::new("Upcoming Events")
+        let mut window = Window::new("Upcoming Events")
             .size(panel_size, Condition::FirstUseEver)
-            .collapsible(true)
-            .opened(&mut opened)
-            .build(ui, || {
+            .collapsible(chrome.show_title_bar)
+            .title_bar(chrome.show_title_bar)
+            .movable(!chrome.locked)
+            .resizable(!chrome.size_locked)
+            .draw_background(!chrome.hide_background)
+            .bg_alpha(chrome.opacity)
+            .opened(&mut opened);
+        if chrome.click_through {
+            window = window.flags(WindowFlags::NO_INPUTS);
+        }
+
+        // Docked: follow the main window (and don't allow moving it separately)
+        let dock_anchor = if docked && main_window_shown { snapping::window_rect("Event Timers") } else { None };
+        if let Some(anchor) = dock_anchor {
+            let own_size = snapping::window_rect("Upcoming Events").map(|(_, size)| size).unwrap_or(panel_size);
+            window = window
+                .position(snapping::docked_position(anchor, own_size, dock_side), Condition::Always)
+                .movable(false);
+        } else if let Some(pos) = snapping::take_pending_position("Upcoming Events").or(relative_pos) {
+            window = window.position(pos, Condition::Always);
+        }
+
+        window.build(ui, || {
+                snapping::track_window(ui, "Upcoming Events", snap_windows && dock_anchor.is_none(), snap_threshold);
+
                 if state.upcoming_events.is_empty() {
                     ui.text_disabled("No tracked events");
                     ui.text_disabled("Right-click events in timeline to track");
                     return;
                 }
 
-                for event in &state.upcoming_events {
-                    // Event row with color indicator
-                    let draw_list = ui.get_window_draw_list();
-                    let cursor_pos = ui.cursor_screen_pos();
-
-                    // Color indicator bar
-                    draw_list
-                        .add_rect(
-                            cursor_pos,
-                            [cursor_pos[0] + 4.0, cursor_pos[1] + 18.0],
-                            event.color,
-                        )
-                        .filled(true)
-                        .build();
-
-                    ui.set_cursor_pos([ui.cursor_pos()[0] + 8.0, ui.cursor_pos()[1]]);
+                let hovered = crate::ui::main_window::hovered_event();
+
+                let table_flags = TableFlags::SIZING_STRETCH_PROP
+                    | TableFlags::ROW_BG
+                    | TableFlags::BORDERS_INNER_H
+                    | TableFlags::RESIZABLE
+                    | TableFlags::SORTABLE
+                    | TableFlags::NO_SAVED_SETTINGS;
+                // The column set is part of the id so a toggled column starts from the saved sort again
+                let table_id = format!("##upcoming_table_{}", layout.columns.iter().map(|c| c.label()).collect::<String>());
+                if let Some(_t) = ui.begin_table_with_flags(&table_id, layout.columns.len(), table_flags) {
+                    for column in &layout.columns {
+                        let mut flags = match column {
+                            UpcomingColumn::Time => TableColumnFlags::WIDTH_FIXED,
+                            _ => TableColumnFlags::WIDTH_STRETCH,
+                        };
+                        if *column == layout.sort_column {
+                            flags |= TableColumnFlags::DEFAULT_SORT;
+                            if layout.sort_descending {
+                                flags |= TableColumnFlags::PREFER_SORT_DESCENDING;
+                            }
+                        }
+                        ui.table_setup_column_with(TableColumnSetup {
+                            name: column.label(),
+                            flags,
+                            init_width_or_weight: if *column == UpcomingColumn::Time { 70.0 } else { 0.0 },
+                            user_id: Default::default(),
+                        });
+                    }
+                    ui.table_headers_row();
+
+                    if let Some(specs) = ui.table_sort_specs_mut() {
+                        specs.conditional_sort(|specs| {
+                            if let Some(spec) = specs.iter().next() {
+                                if let Some(column) = layout.columns.get(spec.column_idx()) {
+                                    let descending = spec.sort_direction() == Some(TableSortDirection::Descending);
+                                    sort_change = Some((*column, descending));
+                                }
+                            }
+                        });
+                    }
+                    let (sort_column, sort_descending) = sort_change.unwrap_or((layout.sort_column, layout.sort_descending));
+
+                    let mut events: Vec<&UpcomingEvent> = state.upcoming_events.iter().collect();
+                    sort_upcoming(&mut events, sort_column, sort_descending, &state);
+
+                    // Overlap warnings go after the event name, or in the first column without one
+                    let conflict_column = layout.columns.iter()
+                        .find(|c| **c == UpcomingColumn::Event)
+                        .or(layout.columns.first())
+                        .copied();
+
+                    for (group, events) in group_upcoming(events, layout.group_by, &state) {
+                        if let Some(name) = group {
+                            let collapsed = layout.collapsed_groups.contains(&name);
+                            ui.table_next_row();
+                            ui.table_next_column();
+                            let label = format!(
+                                "{} {} ({})##upcoming_group_{}",
+                                if collapsed { "+" } else { "-" },
+                                name,
+                                events.len(),
+                                name
+                            );
+                            if Selectable::new(&label).span_all_columns(true).build(ui) {
+                                group_to_toggle = Some(name);
+                            }
+                            if collapsed {
+                                continue;
+                            }
+                        }
 
-                    // Time display - show time until or time since started
-                    let (time_text, time_color) = format_event_time(event.seconds_until, event.seconds_into);
-                    ui.text_colored(time_color, &time_text);
+                        for event in events {
+                            ui.table_next_row();
+                            // Faded like on the timeline when it needs an expansion the player doesn't own
+                            let dimmed = event.unowned.then(|| ui.push_style_var(StyleVar::Alpha(UNOWNED_ROW_ALPHA)));
 
-                    // Check for clicks on time text
-                    let time_hovered = ui.is_item_hovered();
+                            // Pulse the rows of events about to start, once per second
+                            if event.seconds_until > 0 && event.seconds_until <= layout.pulse_seconds {
+                                let pulse = 0.5 + 0.5 * (ui.time() * std::f64::consts::TAU).cos() as f32;
+                                let [r, g, b, _] = event.color;
+                                ui.table_set_bg_color(TableBgTarget::ROW_BG0, [r, g, b, 0.15 + 0.35 * pulse]);
+                            }
 
-                    ui.same_line();
+                            // Highlight the row of the event hovered on the timeline
+                            if hovered.as_ref() == Some(&event.event_id) {
+                                ui.table_set_bg_color(TableBgTarget::ROW_BG1, [1.0, 1.0, 1.0, 0.1]);
+                            }
 
-                    // Event name
-                    ui.text(&event.event_id.event_name);
+                            let conflicts: Vec<(&UpcomingEvent, i64)> = state.upcoming_events.iter()
+                                .filter(|other| other.event_id != event.event_id)
+                                .map(|other| (other, event.overlap_with(other)))
+                                .filter(|&(_, overlap)| overlap > 0)
+                                .collect();
+
+                            let mut row_hovered = false;
+                            for column in &layout.columns {
+                                ui.table_next_column();
+                                match column {
+                                    UpcomingColumn::Time => {
+                                        // Color indicator bar
+                                        let cursor_pos = ui.cursor_screen_pos();
+                                        ui.get_window_draw_list()
+                                            .add_rect(
+                                                cursor_pos,
+                                                [cursor_pos[0] + 4.0, cursor_pos[1] + ui.text_line_height()],
+                                                event.color,
+                                            )
+                                            .filled(true)
+                                            .build();
+                                        ui.set_cursor_pos([ui.cursor_pos()[0] + 8.0, ui.cursor_pos()[1]]);
+
+                                        // Time until the event, or since it started if active
+                                        let time_text = label_cache::label(("upcoming_time", &event.event_id), || {
+                                            format_event_time(event.seconds_until, event.seconds_into)
+                                        });
+                                        let time_color = event_time_color(event.seconds_until, event.seconds_into, &layout.colors);
+                                        ui.text_colored(time_color, &*time_text);
+                                    }
+                                    UpcomingColumn::Event => {
+                                        ui.text(state.labels(&event.event_id).0);
+                                        if !event.map_name.is_empty() {
+                                            row_hovered |= ui.is_item_hovered();
+                                            ui.same_line();
+                                            ui.text_disabled(&*label_cache::label(("upcoming_map", &event.map_name), || {
+                                                format!("({})", event.map_name)
+                                            }));
+                                        }
+                                    }
+                                    UpcomingColumn::Track => ui.text_disabled(state.labels(&event.event_id).1),
+                                    UpcomingColumn::Waypoint => ui.text_disabled(&event.copy_text),
+                                }
+                                if Some(*column) == conflict_column && !conflicts.is_empty() {
+                                    row_hovered |= ui.is_item_hovered();
+                                    ui.same_line();
+                                    ui.text_colored(CONFLICT_COLOR, "(!)");
+                                }
+                                row_hovered |= ui.is_item_hovered();
+                            }
 
-                    // Check for clicks on event name
-                    let name_hovered = ui.is_item_hovered();
+                            if row_hovered {
+                                crate::ui::main_window::set_hovered_event(event.event_id.clone());
+                            }
 
-                    let row_hovered = time_hovered || name_hovered;
+                            // Tooltip with full info
+                            drop(dimmed);
+                            if row_hovered {
+                                ui.tooltip(|| {
+                                    ui.text(&event.event_id.display_name());
+                                    ui.separator();
+                                    ui.text(format!("Starts: {}", format_time_precise(event.start_time)));
+                                    if !event.map_name.is_empty() {
+                                        ui.text(format!("Map: {}", event.map_name));
+                                    }
+                                    if !event.description.is_empty() {
+                                        ui.text(&event.description);
+                                    }
+                                    if !event.rewards.is_empty() {
+                                        ui.text_disabled(format!("Rewards: {}", event.rewards));
+                                    }
+                                    if !event.note.is_empty() {
+                                        ui.text_colored(NOTE_COLOR, format!("Note: {}", event.note));
+                                    }
+                                    for (other, overlap) in &conflicts {
+                                        ui.text_colored(CONFLICT_COLOR, format!(
+                                            "{} overlaps {} by {}",
+                                            state.labels(&event.event_id).0,
+                                            state.labels(&other.event_id).0,
+                                            format_duration(*overlap)
+                                        ));
+                                    }
+                                    if !event.copy_text.is_empty() {
+                                        ui.text(format!("Waypoint: {}", event.copy_text));
+                                        ui.separator();
+                                        ui.text_disabled("Left-click to copy");
+                                    }
+                                    ui.text_disabled("Right-click for options");
+                                });
+                            }
 
-                    // Tooltip with full info
-                    if row_hovered {
-                        ui.tooltip(|| {
-                            ui.text(&event.event_id.display_name());
-                            ui.separator();
-                            ui.text(format!("Starts: {}", format_time_only(event.start_time)));
-                            if !event.copy_text.is_empty() {
-                                ui.text(format!("Waypoint: {}", event.copy_text));
-                                ui.separator();
-                                ui.text_disabled("Left-click to copy");
+                            // Left-click: copy waypoint (respects copy_with_event_name setting)
+                            if row_hovered && ui.is_mouse_clicked(MouseButton::Left) {
+                                if !event.copy_text.is_empty() {
+                                    if copy_with_event_name {
+                                        copy_text_to_set = Some(format!("{}: {}", state.labels(&event.event_id).0, event.copy_text));
+                                    } else {
+                                        copy_text_to_set = Some(event.copy_text.clone());
+                                    }
+                                }
                             }
-                            ui.text_disabled("Right-click for options");
-                        });
-                    }
 
-                    // Left-click: copy waypoint (respects copy_with_event_name setting)
-                    if row_hovered && ui.is_mouse_clicked(MouseButton::Left) {
-                        if !event.copy_text.is_empty() {
-                            if copy_with_event_name {
-                                copy_text_to_set = Some(format!("{}: {}", event.event_id.event_name, event.copy_text));
-                            } else {
-                                copy_text_to_set = Some(event.copy_text.clone());
+                            // Right-click: open context menu
+                            if row_hovered && ui.is_mouse_clicked(MouseButton::Right) {
+                                UPCOMING_CONTEXT_EVENT.with(|e| {
+                                    *e.borrow_mut() = Some(event.event_id.clone());
+                                });
+                                UPCOMING_OPEN_MENU.with(|f| f.set(true));
                             }
                         }
                     }
-
-                    // Right-click: open context menu
-                    if row_hovered && ui.is_mouse_clicked(MouseButton::Right) {
-                        UPCOMING_CONTEXT_EVENT.with(|e| {
-                            *e.borrow_mut() = Some(event.event_id.clone());
-                        });
-                        UPCOMING_OPEN_MENU.with(|f| f.set(true));
-                    }
-
-                    ui.separator();
                 }
 
                 // Render context menu
@@ -374,9 +814,28 @@ pub fn render_upcoming_panel(ui: &Ui) {
                 ui.popup("##upcoming_context_menu", || {
                     let context_event = UPCOMING_CONTEXT_EVENT.with(|e| e.borrow().clone());
                     if let Some(event_id) = context_event {
-                        ui.text_disabled(&event_id.event_name);
+                        let event_label = state.labels(&event_id).0;
+                        ui.text_disabled(event_label);
                         ui.separator();
 
+                        // Extra copy actions (the main waypoint is copied with a left-click)
+                        let copy_actions = state.upcoming_events.iter()
+                            .find(|e| e.event_id == event_id)
+                            .map(|e| e.copy_actions.as_slice())
+                            .unwrap_or_default();
+                        for action in copy_actions {
+                            if MenuItem::new(format!("Copy {}", action.label)).build(ui) {
+                                copy_text_to_set = Some(if copy_with_event_name {
+                                    format!("{}: {}", event_label, action.text)
+                                } else {
+                                    action.text.clone()
+                                });
+                            }
+                        }
+                        if !copy_actions.is_empty() {
+                            ui.separator();
+                        }
+
                         if MenuItem::new("Untrack Event").build(ui) {
                             event_to_untrack = Some(event_id.clone());
                         }
@@ -396,6 +855,23 @@ pub fn render_upcoming_panel(ui: &Ui) {
         }
     }
 
+    // Remember a header click as the panel's sort order
+    if let Some((column, descending)) = sort_change {
+        let mut config = RUNTIME_CONFIG.lock();
+        config.notification_config.upcoming_sort_column = column;
+        config.notification_config.upcoming_sort_descending = descending;
+    }
+
+    if let Some(name) = group_to_toggle {
+        let mut config = RUNTIME_CONFIG.lock();
+        let collapsed = &mut config.notification_config.upcoming_collapsed_groups;
+        if let Some(index) = collapsed.iter().position(|group| *group == name) {
+            collapsed.remove(index);
+        } else {
+            collapsed.push(name);
+        }
+    }
+
     // Copy to clipboard outside of lock
     if let Some(text) = copy_text_to_set {
         ui.set_clipboard_text(&text);
@@ -416,12 +892,11 @@ pub fn render_upcoming_panel(ui: &Ui) {
     }
 }
 
-/// Format event time - returns (text, color)
-/// Shows time until event, or time since it started if active
-fn format_event_time(seconds_until: i64, seconds_into: i64) -> (String, [f32; 4]) {
+/// Format event time: time until the event, or time since it started if active
+fn format_event_time(seconds_until: i64, seconds_into: i64) -> String {
     if seconds_until <= 0 && seconds_into > 0 {
         // Event is active - show time since it started
-        let text = if seconds_into < 60 {
+        if seconds_into < 60 {
             format!("{}s ago", seconds_into)
         } else if seconds_into < 3600 {
             let mins = seconds_into / 60;
@@ -430,30 +905,34 @@ fn format_event_time(seconds_until: i64, seconds_into: i64) -> (String, [f32; 4]
             let hours = seconds_into / 3600;
             let mins = (seconds_into % 3600) / 60;
             format!("{}h {}m ago", hours, mins)
-        };
-        // Yellow/orange color for active events
-        (text, [1.0, 0.8, 0.2, 1.0])
+        }
     } else if seconds_until <= 0 {
         // Just started
-        ("NOW".to_string(), [0.5, 1.0, 0.5, 1.0])
-    } else {
-        // Event upcoming
-        let text = if seconds_until < 60 {
-            format!("{}s", seconds_until)
-        } else if seconds_until < 3600 {
-            let mins = seconds_until / 60;
-            let secs = seconds_until % 60;
-            if secs > 0 {
-                format!("{}m {}s", mins, secs)
-            } else {
-                format!("{}m", mins)
-            }
+        "NOW".to_string()
+    } else if seconds_until < 60 {
+        format!("{}s", seconds_until)
+    } else if seconds_until < 3600 {
+        let mins = seconds_until / 60;
+        let secs = seconds_until % 60;
+        if secs > 0 {
+            format!("{}m {}s", mins, secs)
         } else {
-            let hours = seconds_until / 3600;
-            let mins = (seconds_until % 3600) / 60;
-            format!("{}h {}m", hours, mins)
-        };
-        // Green color for upcoming events
-        (text, [0.5, 1.0, 0.5, 1.0])
+            format!("{}m", mins)
+        }
+    } else {
+        let hours = seconds_until / 3600;
+        let mins = (seconds_until % 3600) / 60;
+        format!("{}h {}m", hours, mins)
+    }
+}
+
+/// Color of the time text for an event's state
+fn event_time_color(seconds_until: i64, seconds_into: i64, colors: &TimeColors) -> [f32; 4] {
+    if seconds_until <= 0 && seconds_into > 0 {
+        colors.active
+    } else if seconds_until > 0 && seconds_until <= colors.imminent_seconds {
+        colors.imminent
+    } else {
+        colors.upcoming
     }
 }
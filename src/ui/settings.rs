@@ -1,15 +1,45 @@
 use nexus::imgui::{
-    ColorEdit, ColorEditFlags, InputFloat, InputText, Selectable, TableFlags, TreeNodeFlags, Ui, Window,
+    ColorEdit, ColorEditFlags, InputFloat, InputText, Selectable, TableFlags, TreeNode, TreeNodeFlags, Ui, Window,
 };
+use base64::Engine;
 use std::collections::HashSet;
+use std::time::Duration;
 use parking_lot::MutexGuard;
 
-use crate::config::{TimeRulerInterval, ToastPosition, TrackedEventId, RUNTIME_CONFIG, SELECTED_EVENT, SELECTED_TRACK, RuntimeConfig};
-use crate::json_loader::{load_tracks_from_json, EventColor, EventTrack, TimelineEvent};
+use crate::addon_log;
+use crate::backups::{self, BackupEntry, BackupKind};
+use crate::config::{CatchUpPolicy, ATTENDANCE_DAYS, OverviewCycle, DockSide, EventLanguage, EventPriority, FinishedTodayMode, QaIconStyle, TimeMarker, TimeMarkerKind, TimeRulerInterval, ToastPosition, TooltipMode, UnownedContentMode, UpcomingGrouping, TrackedEventId, RUNTIME_CONFIG, SELECTED_EVENT, SELECTED_TRACK, RuntimeConfig};
+use crate::json_loader::{
+    cycle_minutes_for_calculator, export_pack_json, is_known_calculator, load_error, load_tracks_from_json, rejected_update,
+    pack_info, required_addon_version, set_rejected_update, verify_pack_hash, SCHEMA_VERSION, validate_tracks_json, CopyAction, EventColor, EventTrack, TimelineEvent, TimelineType,
+    BASE_TIME_CALCULATORS, EXPANSIONS,
+};
 use crate::notifications::NOTIFICATION_STATE;
+use crate::timeline;
+use crate::time_utils::{
+    calculate_tyria_time, format_duration, format_mmss, format_time_only, format_weekday_time, get_current_unix_time, parse_mmss,
+};
+use crate::ui::{json_editor, track_wizard, whats_new};
+use crate::watchlist;
 
 const GITHUB_EVENT_TRACKS_URL: &str = "https://raw.githubusercontent.com/qjv/event-timers/main/event_tracks.json";
 
+/// Shortest cycle a custom event can be given in the editor
+const MIN_CYCLE_SECONDS: i64 = 60;
+
+/// Starting points for new custom events: (label, recurrence, duration in seconds).
+/// "{reset}" and "{weekly_reset}" become the local time of the daily (00:00 UTC)
+/// and weekly (Monday 07:30 UTC) server resets.
+const EVENT_TEMPLATES: [(&str, &str, i64); 7] = [
+    ("Every 2 hours at :00", "every 2h at :00", 15 * 60),
+    ("Every 2 hours at :30", "every 2h at :30", 15 * 60),
+    ("Every hour at :00", "every 1h at :00", 10 * 60),
+    ("Every 3 hours at :00", "every 3h at :00", 15 * 60),
+    ("Every 15 minutes", "every 15m", 5 * 60),
+    ("Daily at server reset", "daily {reset}", 10 * 60),
+    ("Weekly at server reset", "{weekly_reset}", 10 * 60),
+];
+
 pub fn check_for_event_tracks_update() {
     use std::thread;
 
@@ -21,99 +51,324 @@ pub fn check_for_event_tracks_update() {
         let runtime = match runtime_result {
             Ok(rt) => rt,
             Err(e) => {
-                nexus::log::log(
-                    nexus::log::LogLevel::Critical,
-                    "Event Timers",
-                    &format!("Failed to create Tokio runtime: {}", e)
-                );
+                addon_log::error(format!("Failed to create Tokio runtime: {}", e));
                 return;
             }
         };
 
         runtime.block_on(async {
-            nexus::log::log(
-                nexus::log::LogLevel::Info,
-                "Event Timers",
-                "Checking for event_tracks.json updates from GitHub..."
-            );
+            addon_log::info("Checking for event_tracks.json updates from GitHub...");
 
-            match reqwest::get(GITHUB_EVENT_TRACKS_URL).await {
-                Ok(response) => {
-                    match response.text().await {
-                        Ok(github_content) => {
-                            let local_path = nexus::paths::get_addon_dir("event_timers")
-                                .map(|p| p.join("event_tracks.json"));
-
-                            if let Some(path) = local_path {
-                                let needs_update = if path.exists() {
-                                    match std::fs::read_to_string(&path) {
-                                        Ok(local_content) => local_content != github_content,
-                                        Err(_) => true,
-                                    }
-                                } else {
-                                    true
-                                };
-
-                                if needs_update {
-                                    if path.exists() {
-                                        let backup_path = path.with_extension("json.backup");
-                                        let _ = std::fs::copy(&path, backup_path);
-                                    }
-
-                                    match std::fs::write(&path, github_content) {
-                                        Ok(_) => {
-                                            nexus::log::log(
-                                                nexus::log::LogLevel::Info,
-                                                "Event Timers",
-                                                "event_tracks.json updated! Reload addon (Ctrl+Shift+L) to apply."
-                                            );
-                                        }
-                                        Err(e) => {
-                                            nexus::log::log(
-                                                nexus::log::LogLevel::Critical,
-                                                "Event Timers",
-                                                &format!("Failed to write file: {}", e)
-                                            );
-                                        }
-                                    }
-                                } else {
-                                    nexus::log::log(
-                                        nexus::log::LogLevel::Info,
-                                        "Event Timers",
-                                        "event_tracks.json is already up to date!"
-                                    );
+            let config = RUNTIME_CONFIG.snapshot();
+            let client = match update_client(config.update_timeout_seconds, &config.update_proxy) {
+                Ok(client) => client,
+                Err(e) => {
+                    addon_log::error(format!("Can't set up the download: {}", e));
+                    return;
+                }
+            };
+
+            match fetch_event_tracks(&client, config.update_retries).await {
+                Ok(github_content) => {
+                    let local_path = nexus::paths::get_addon_dir("event_timers")
+                        .map(|p| p.join("event_tracks.json"));
+
+                    if let Some(path) = local_path {
+                        let needs_update = if path.exists() {
+                            match std::fs::read_to_string(&path) {
+                                Ok(local_content) => local_content != github_content,
+                                Err(_) => true,
+                            }
+                        } else {
+                            true
+                        };
+
+                        if needs_update {
+                            // Never replace a working file with one that doesn't parse
+                            if let Err(e) = validate_tracks_json(&github_content) {
+                                addon_log::error(format!("Downloaded event_tracks.json is invalid, keeping the current file: {}", e));
+                                set_rejected_update(Some(e.to_string()));
+                                return;
+                            }
+                            // Installing it would only swap the tracks for the built-in ones
+                            if let Some(required) = required_addon_version(&github_content) {
+                                addon_log::warn(format!("Downloaded event_tracks.json needs addon version {}, keeping the current file", required));
+                                set_rejected_update(Some(format!("It needs Event Timers {} or newer; update the addon to get it", required)));
+                                return;
+                            }
+                            match verify_pack_hash(&github_content) {
+                                Ok(true) => {}
+                                Ok(false) => addon_log::warn("Downloaded event_tracks.json declares no hash, installing it unverified"),
+                                Err(reason) => {
+                                    addon_log::error(format!("Downloaded event_tracks.json failed verification, keeping the current file: {}", reason));
+                                    set_rejected_update(Some(reason));
+                                    return;
                                 }
                             }
-                        }
-                        Err(e) => {
-                            nexus::log::log(
-                                nexus::log::LogLevel::Critical,
-                                "Event Timers",
-                                &format!("Failed to read response: {}", e)
-                            );
+                            set_rejected_update(None);
+
+                            // Back up the current file only if it's a good copy worth falling back to
+                            let current_is_valid = std::fs::read_to_string(&path)
+                                .is_ok_and(|content| validate_tracks_json(&content).is_ok());
+                            if current_is_valid {
+                                if let Err(e) = backups::create_backup(BackupKind::TrackData) {
+                                    addon_log::warn(format!("Could not back up event_tracks.json: {}", e));
+                                }
+                            }
+
+                            // Written next to it first, so an interrupted write can't leave half a file
+                            let partial = path.with_extension("json.part");
+                            match std::fs::write(&partial, github_content).and_then(|_| std::fs::rename(&partial, &path)) {
+                                Ok(_) => {
+                                    addon_log::info("event_tracks.json updated! Reload addon (Ctrl+Shift+L) to apply.");
+                                }
+                                Err(e) => {
+                                    addon_log::error(format!("Failed to write file: {}", e));
+                                }
+                            }
+                        } else {
+                            addon_log::info("event_tracks.json is already up to date!");
                         }
                     }
                 }
                 Err(e) => {
-                    nexus::log::log(
-                        nexus::log::LogLevel::Critical,
-                        "Event Timers",
-                        &format!("Failed to fetch from GitHub: {}", e)
-                    );
+                    addon_log::error(format!("Failed to fetch from GitHub: {}", e));
                 }
             }
         });
     });
 }
 
+/// HTTP client for the event data download (and the webhook). reqwest reads HTTP_PROXY,
+/// HTTPS_PROXY and NO_PROXY by itself; a proxy set in the options takes their place.
+pub(crate) fn update_client(timeout_seconds: u32, proxy: &str) -> Result<reqwest::Client, String> {
+    let timeout = Duration::from_secs(timeout_seconds.max(1) as u64);
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(timeout.min(Duration::from_secs(10)))
+        .timeout(timeout);
+    let proxy = proxy.trim();
+    if !proxy.is_empty() {
+        let proxy = reqwest::Proxy::all(proxy).map_err(|e| format!("invalid proxy '{}': {}", proxy, e))?;
+        builder = builder.proxy(proxy);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Download event_tracks.json, retrying failed attempts after 2s, 4s, 8s, ...
+async fn fetch_event_tracks(client: &reqwest::Client, retries: u32) -> Result<String, reqwest::Error> {
+    let mut delay = Duration::from_secs(2);
+    let mut attempt = 0;
+    loop {
+        let result = async {
+            let response = client.get(GITHUB_EVENT_TRACKS_URL).send().await?.error_for_status()?;
+            response.text().await
+        }
+        .await;
+
+        match result {
+            Err(e) if attempt < retries => {
+                attempt += 1;
+                addon_log::warn(format!(
+                    "Fetching event_tracks.json failed ({}), retry {} of {} in {}s",
+                    e, attempt, retries, delay.as_secs()
+                ));
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Backup operation chosen in the Backups section. Restores reload the config, so they run
+/// at the start of the next frame, before the config is locked.
+enum BackupAction {
+    Create(BackupKind),
+    Restore(BackupEntry),
+    Delete(BackupEntry),
+}
+
+thread_local! {
+    static PENDING_BACKUP_ACTION: std::cell::RefCell<Option<BackupAction>> = const { std::cell::RefCell::new(None) };
+    // Result of the last pack export: (message, is_error)
+    static PACK_EXPORT_STATUS: std::cell::RefCell<Option<(String, bool)>> = const { std::cell::RefCell::new(None) };
+    // Result of the last backup operation: (message, is_error)
+    static BACKUP_STATUS: std::cell::RefCell<Option<(String, bool)>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Top-level settings sections other windows can point the user to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingsSection {
+    MainWindow,
+    Notifications,
+    TrackManagement,
+    Backups,
+}
+
+thread_local! {
+    static REVEAL_SECTION: std::cell::Cell<Option<SettingsSection>> = const { std::cell::Cell::new(None) };
+}
+
+/// Expand and scroll to a section the next time the settings are drawn
+pub fn reveal_section(section: SettingsSection) {
+    REVEAL_SECTION.with(|r| r.set(Some(section)));
+}
+
+/// Collapsing header, expanded regardless of its stored state when `force_open` is set
+fn collapsing_header_opened(ui: &Ui, label: &str, flags: TreeNodeFlags, force_open: bool) -> bool {
+    // A framed tree node that doesn't push onto the tree stack is what collapsing_header draws
+    let mut header = TreeNode::new(label).flags(flags).framed(true).tree_push_on_open(false);
+    if force_open {
+        header = header.opened(true, nexus::imgui::Condition::Always);
+    }
+    header.push(ui).is_some()
+}
+
+fn section_header(ui: &Ui, label: &str, section: SettingsSection, flags: TreeNodeFlags) -> bool {
+    let reveal = REVEAL_SECTION.with(|r| r.get()) == Some(section);
+    let open = collapsing_header_opened(ui, label, flags, reveal);
+    if reveal {
+        ui.set_scroll_here_y_with_ratio(0.0);
+        REVEAL_SECTION.with(|r| r.set(None));
+    }
+    open
+}
+
+fn run_pending_backup_action() {
+    let Some(action) = PENDING_BACKUP_ACTION.with(|p| p.borrow_mut().take()) else {
+        return;
+    };
+
+    let result = match &action {
+        BackupAction::Create(kind) => backups::create_backup(*kind)
+            .map(|path| format!("Created {}", path.file_name().unwrap_or_default().to_string_lossy())),
+        BackupAction::Restore(backup) => backups::restore_backup(backup).map(|_| format!("Restored {}", backup.file_name())),
+        BackupAction::Delete(backup) => backups::delete_backup(backup).map(|_| format!("Deleted {}", backup.file_name())),
+    };
+
+    let status = match result {
+        Ok(message) => {
+            addon_log::info(&message);
+            (message, false)
+        }
+        Err(e) => {
+            addon_log::error(&e);
+            (e, true)
+        }
+    };
+    BACKUP_STATUS.with(|s| *s.borrow_mut() = Some(status));
+}
+
+/// Write every track (default and custom) as a pack into the addon's exports folder
+fn export_pack_file(config: &RuntimeConfig) -> Result<std::path::PathBuf, String> {
+    let json = export_pack_json(&config.tracks, &config.category_order)?;
+    let dir = nexus::paths::get_addon_dir("event_timers")
+        .ok_or("Addon directory not available")?
+        .join("exports");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(chrono::Local::now().format("event_tracks_%Y%m%d_%H%M%S.json").to_string());
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+fn render_backups_section(ui: &Ui) {
+    ui.indent();
+
+    if ui.button("Back Up Event Data") {
+        PENDING_BACKUP_ACTION.with(|p| *p.borrow_mut() = Some(BackupAction::Create(BackupKind::TrackData)));
+    }
+    ui.same_line();
+    if ui.button("Back Up Settings") {
+        PENDING_BACKUP_ACTION.with(|p| *p.borrow_mut() = Some(BackupAction::Create(BackupKind::UserConfig)));
+    }
+    ui.text_disabled("Event data is backed up before each update, settings once a day. Restoring backs up the current file first.");
+
+    BACKUP_STATUS.with(|s| {
+        if let Some((message, is_error)) = s.borrow().as_ref() {
+            let color = if *is_error { [1.0, 0.4, 0.4, 1.0] } else { [0.5, 0.9, 0.5, 1.0] };
+            ui.text_colored(color, message);
+        }
+    });
+
+    let backups = backups::list_backups();
+    if backups.is_empty() {
+        ui.text_disabled("No backups yet");
+        ui.unindent();
+        return;
+    }
+
+    let table_flags = TableFlags::SIZING_STRETCH_PROP | TableFlags::ROW_BG | TableFlags::PAD_OUTER_X;
+    if let Some(_t) = ui.begin_table_with_flags("##backups", 4, table_flags) {
+        ui.table_setup_column("Type");
+        ui.table_setup_column("Created");
+        ui.table_setup_column("Size");
+        ui.table_setup_column("");
+        ui.table_headers_row();
+
+        for (i, backup) in backups.iter().enumerate() {
+            ui.table_next_row();
+            ui.table_next_column();
+            ui.text(backup.kind.label());
+            if ui.is_item_hovered() {
+                ui.tooltip_text(backup.file_name());
+            }
+            ui.table_next_column();
+            ui.text(backups::format_created(backup));
+            ui.table_next_column();
+            ui.text(format!("{:.1} KB", backup.size as f32 / 1024.0));
+            ui.table_next_column();
+            if ui.small_button(format!("Restore##backup_{}", i)) {
+                PENDING_BACKUP_ACTION.with(|p| *p.borrow_mut() = Some(BackupAction::Restore(backup.clone())));
+            }
+            ui.same_line();
+            if ui.small_button(format!("Delete##backup_{}", i)) && ui.io().key_ctrl {
+                PENDING_BACKUP_ACTION.with(|p| *p.borrow_mut() = Some(BackupAction::Delete(backup.clone())));
+            }
+            if ui.is_item_hovered() && !ui.io().key_ctrl {
+                ui.tooltip_text("Hold Ctrl to delete");
+            }
+        }
+    }
+
+    ui.unindent();
+}
+
 pub fn render_settings(ui: &Ui) {
+    run_pending_backup_action();
+    json_editor::apply_pending_save();
+
     let mut config = RUNTIME_CONFIG.lock();
 
     ui.text("Event Timers Settings");
+    ui.same_line();
+    if ui.small_button("What's New") {
+        whats_new::open();
+    }
     ui.separator();
 
+    if let Some(error) = load_error() {
+        if error.requires_version.is_some() {
+            ui.text_colored([1.0, 0.8, 0.3, 1.0], "event_tracks.json is for a newer addon version, using the built-in tracks:");
+        } else if error.using_backup {
+            ui.text_colored([1.0, 0.8, 0.3, 1.0], "event_tracks.json could not be loaded, using the previous working copy:");
+        } else {
+            ui.text_colored([1.0, 0.4, 0.4, 1.0], "event_tracks.json could not be loaded, default tracks are unavailable:");
+        }
+        ui.text_wrapped(&error.message);
+        if ui.small_button("Show Log") {
+            config.show_log_window = true;
+        }
+        ui.separator();
+    }
+
+    if let Some(reason) = rejected_update() {
+        ui.text_colored([1.0, 0.8, 0.3, 1.0], "A downloaded event_tracks.json update was rejected:");
+        ui.text_wrapped(&reason);
+        ui.separator();
+    }
+
     // ==================== MAIN WINDOW ====================
-    if ui.collapsing_header("Main Window", TreeNodeFlags::DEFAULT_OPEN) {
+    if section_header(ui, "Main Window", SettingsSection::MainWindow, TreeNodeFlags::DEFAULT_OPEN) {
         ui.indent();
 
         // --- Timeline ---
@@ -137,6 +392,19 @@ pub fn render_settings(ui: &Ui) {
             ui.checkbox("Show Current Time on Ruler", &mut config.time_ruler_show_current_time);
         }
 
+        render_time_markers(ui, &mut config.time_markers);
+
+        ui.text("Cycle Minimap:");
+        ui.same_line();
+        ui.radio_button("Off##minimap", &mut config.minimap_cycle, OverviewCycle::Off);
+        ui.same_line();
+        ui.radio_button("Tyria Cycle (2h)##minimap", &mut config.minimap_cycle, OverviewCycle::Tyria);
+        ui.same_line();
+        ui.radio_button("Day (24h)##minimap", &mut config.minimap_cycle, OverviewCycle::Day);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Thin strip under the ruler showing the whole cycle; click or drag it to pan the timeline");
+        }
+
         let mut view_range_minutes = config.view_range_seconds / 60.0;
         if nexus::imgui::Slider::new("View Range (minutes)", 15.0, 120.0)
             .build(ui, &mut view_range_minutes)
@@ -149,6 +417,17 @@ pub fn render_settings(ui: &Ui) {
             .build(ui, &mut config.current_time_position);
         ui.text_disabled("0.0 = Left edge, 0.5 = Center");
 
+        ui.text("Hold ALT to show:");
+        ui.same_line();
+        ui.radio_button("Nothing##alt_zoom", &mut config.alt_zoom_cycle, OverviewCycle::Off);
+        ui.same_line();
+        ui.radio_button("Tyria Cycle (2h)##alt_zoom", &mut config.alt_zoom_cycle, OverviewCycle::Tyria);
+        ui.same_line();
+        ui.radio_button("Day (24h)##alt_zoom", &mut config.alt_zoom_cycle, OverviewCycle::Day);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Temporarily zoom the timeline out to the whole current cycle while ALT is held");
+        }
+
         ui.spacing();
 
         // --- Categories ---
@@ -163,8 +442,25 @@ pub fn render_settings(ui: &Ui) {
             ui.same_line();
             if ui.radio_button("Right##hdr", &mut config.category_header_alignment, crate::config::TextAlignment::Right) {}
 
+            ui.checkbox("Show Event Counts", &mut config.category_header_counts);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Append how many events are running and starting within 15 minutes,\ne.g. \"World Bosses (3 active, 2 soon)\"");
+            }
+
             nexus::imgui::Slider::new("Header Padding", 0.0, 50.0)
                 .build(ui, &mut config.category_header_padding);
+            nexus::imgui::Slider::new("Header Height", 0.0, 60.0)
+                .display_format("%.0f")
+                .build(ui, &mut config.category_header_height);
+            ui.text_disabled("0 = fit to text");
+            nexus::imgui::Slider::new("Header Text Size", 0.5, 2.0)
+                .build(ui, &mut config.category_header_text_scale);
+            ColorEdit::new("Header Background", &mut config.category_header_bg_color)
+                .flags(ColorEditFlags::ALPHA_BAR)
+                .build(ui);
+            ColorEdit::new("Header Text", &mut config.category_header_text_color)
+                .flags(ColorEditFlags::ALPHA_BAR)
+                .build(ui);
         }
 
         nexus::imgui::Slider::new("Spacing (Same Category)", 0.0, 20.0)
@@ -173,6 +469,16 @@ pub fn render_settings(ui: &Ui) {
         nexus::imgui::Slider::new("Spacing (Between Categories)", 0.0, 50.0)
             .build(ui, &mut config.spacing_between_categories);
 
+        ui.checkbox("Category Background Panels", &mut config.category_panels_enabled);
+        if config.category_panels_enabled {
+            ColorEdit::new("Panel Color", &mut config.category_panel_color)
+                .flags(ColorEditFlags::ALPHA_BAR)
+                .build(ui);
+            nexus::imgui::Slider::new("Panel Rounding", 0.0, 12.0)
+                .build(ui, &mut config.category_panel_rounding);
+            ui.text_disabled("Per-category colors can be set under Track Management");
+        }
+
         ui.spacing();
 
         // --- Labels ---
@@ -225,6 +531,17 @@ pub fn render_settings(ui: &Ui) {
                 .build(ui, &mut config.global_track_height);
         }
 
+        ui.checkbox("Auto-Fit Track Heights", &mut config.auto_fit_track_heights);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Divide the window height among visible tracks so everything fits");
+        }
+        if config.auto_fit_track_heights {
+            nexus::imgui::Slider::new("Min Track Height", 10.0, 200.0)
+                .build(ui, &mut config.auto_fit_min_height);
+            nexus::imgui::Slider::new("Max Track Height", 10.0, 400.0)
+                .build(ui, &mut config.auto_fit_max_height);
+        }
+
         ui.checkbox("Draw Event Borders", &mut config.draw_event_borders);
         if config.draw_event_borders {
             ColorEdit::new("Border Color", &mut config.event_border_color)
@@ -235,25 +552,268 @@ pub fn render_settings(ui: &Ui) {
                 .build(ui, &mut config.event_border_thickness);
         }
 
+        ui.checkbox("Mark Last Chance Before Reset", &mut config.last_chance_highlight);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Outline an event's final occurrence before daily reset and add \"(last today)\"\nto its tooltip and reminders. Events that run once a day are left alone.");
+        }
+        if config.last_chance_highlight {
+            ui.same_line();
+            ColorEdit::new("##last_chance_color", &mut config.last_chance_color)
+                .flags(ColorEditFlags::NO_INPUTS | ColorEditFlags::ALPHA_BAR)
+                .build(ui);
+        }
+
+        ui.text_disabled("Occurrences other than the running one:");
+        ui.checkbox("Hide Past Occurrences", &mut config.hide_past_events);
+        if !config.hide_past_events {
+            nexus::imgui::Slider::new("Past Brightness", 0.0, 1.0)
+                .build(ui, &mut config.past_event_brightness);
+            nexus::imgui::Slider::new("Past Opacity", 0.0, 1.0)
+                .build(ui, &mut config.past_event_alpha);
+        }
+        nexus::imgui::Slider::new("Upcoming Brightness", 0.0, 1.0)
+            .build(ui, &mut config.future_event_brightness);
+        nexus::imgui::Slider::new("Upcoming Opacity", 0.0, 1.0)
+            .build(ui, &mut config.future_event_alpha);
+        ui.text("Daily events finished for today:");
+        ui.same_line();
+        ui.radio_button("Show##finished_today", &mut config.finished_today_mode, FinishedTodayMode::Show);
+        ui.same_line();
+        ui.radio_button("Dim##finished_today", &mut config.finished_today_mode, FinishedTodayMode::Dim);
+        ui.same_line();
+        ui.radio_button("Hide##finished_today", &mut config.finished_today_mode, FinishedTodayMode::Hide);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Events on a daily (or weekly) cycle that already ran today and won't start again before midnight");
+        }
+
+        ui.spacing();
+
+        // --- Window ---
+        ui.text("Window");
+        ui.checkbox("Lock Position", &mut config.is_window_locked);
+        ui.same_line();
+        ui.checkbox("Lock Size", &mut config.is_window_size_locked);
+        ui.same_line();
+        ui.checkbox("Disable Interaction", &mut config.disable_window_interaction);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Click-through: the timeline ignores the mouse (no tooltips or menus)");
+        }
+        ui.checkbox("Show Title Bar", &mut config.show_title_bar);
+        ui.checkbox("Fade When Inactive", &mut config.fade_when_inactive);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Turn the timeline nearly transparent during normal play; it comes back\nwhen the cursor gets close or a tracked event is about to start");
+        }
+        if config.fade_when_inactive {
+            ui.indent();
+            nexus::imgui::Slider::new("Faded Opacity", 0.05, 1.0)
+                .display_format("%.2f")
+                .build(ui, &mut config.fade_opacity);
+            nexus::imgui::Slider::new("Wake Distance (px)", 0.0, 400.0)
+                .display_format("%.0f")
+                .build(ui, &mut config.fade_distance);
+            nexus::imgui::Slider::new("Wake Before Events (min)", 0, 30)
+                .build(ui, &mut config.fade_event_minutes);
+            ui.unindent();
+        }
+        ui.checkbox("Pause While Loading", &mut config.pause_while_loading);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Hide the windows and stop checking reminders during loading screens and\ncharacter select; reminders that came due meanwhile follow Missed Reminders");
+        }
+        ui.checkbox("Show Agenda Window", &mut config.show_agenda_window);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Lists every occurrence in the next 24 hours, grouped by hour");
+        }
+        if config.show_agenda_window {
+            ui.same_line();
+            ui.checkbox("Tracked events only##agenda", &mut config.agenda_tracked_only);
+        }
+
+        ui.spacing();
+
+        // --- Tooltips ---
+        ui.text("Tooltips");
+        ui.same_line();
+        ui.radio_button("Compact##ttm", &mut config.tooltip_mode, TooltipMode::Compact);
+        ui.same_line();
+        ui.radio_button("Detailed##ttm", &mut config.tooltip_mode, TooltipMode::Detailed);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Adds category, duration, Tyria time and the following occurrence");
+        }
+        nexus::imgui::Slider::new("Tooltip Delay (s)", 0.0, 2.0)
+            .display_format("%.1f")
+            .build(ui, &mut config.tooltip_delay);
+        ui.checkbox("Show Tooltips While Locked", &mut config.tooltips_when_locked);
+
+        let language_labels: Vec<&str> = EventLanguage::ALL.iter().map(|l| l.label()).collect();
+        let mut language_index = EventLanguage::ALL.iter().position(|&l| l == config.event_language).unwrap_or(0);
+        if ui.combo_simple_string("Event Names", &mut language_index, &language_labels) {
+            config.event_language = EventLanguage::ALL[language_index];
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Language of event and track names, where the data pack has translations (English otherwise)");
+        }
+
+        ui.spacing();
+
+        // --- Expansions ---
+        ui.text("Owned Expansions");
+        for (id, name) in EXPANSIONS {
+            let mut owned = !config.unowned_expansions.contains(id);
+            if ui.checkbox(name, &mut owned) {
+                if owned {
+                    config.unowned_expansions.remove(id);
+                } else {
+                    config.unowned_expansions.insert(id.to_string());
+                }
+            }
+        }
+        ui.checkbox("Auto-Solo Current Map", &mut config.auto_solo_map);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Only show tracks for the map you're on (all tracks when none match)");
+        }
+        ui.text("Events needing unowned content:");
+        ui.same_line();
+        ui.radio_button("Dim##unowned", &mut config.unowned_content_mode, UnownedContentMode::Dim);
+        ui.same_line();
+        ui.radio_button("Hide##unowned", &mut config.unowned_content_mode, UnownedContentMode::Hide);
+
         ui.spacing();
 
         // --- Other ---
         ui.text("Other");
         ui.checkbox("Close window with ESC", &mut config.close_on_escape);
         ui.checkbox("Include event name when copying waypoint", &mut config.copy_with_event_name);
+        ui.checkbox("Snap Windows to Edges", &mut config.snap_windows);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Snap addon windows to screen edges and to each other after dragging");
+        }
+        if config.snap_windows {
+            nexus::imgui::Slider::new("Snap Distance", 2.0, 50.0)
+                .build(ui, &mut config.snap_threshold);
+        }
+        ui.checkbox("Keep Window Positions Relative to Screen", &mut config.relative_window_positions);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Re-position windows proportionally when the resolution or window mode changes");
+        }
+        ui.checkbox("Show Log", &mut config.show_log_window);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Recent update checks, data file warnings and errors");
+        }
+        ui.same_line();
+        ui.checkbox("Show Performance Overlay", &mut config.show_diagnostics);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Per-frame time spent in the addon, for performance reports");
+        }
+
+        ui.text("Quick Access Icon");
+        ui.same_line();
+        ui.radio_button("Classic##qaicon", &mut config.qa_icon_style, QaIconStyle::Classic);
+        ui.same_line();
+        ui.radio_button("Monochrome##qaicon", &mut config.qa_icon_style, QaIconStyle::Monochrome);
+        ui.same_line();
+        ui.radio_button("Custom##qaicon", &mut config.qa_icon_style, QaIconStyle::Custom);
+        if config.qa_icon_style == QaIconStyle::Custom {
+            thread_local! {
+                // Edited paths, applied on Enter so the icon isn't reloaded on every keystroke
+                static ICON_PATHS: std::cell::RefCell<Option<(String, String)>> = const { std::cell::RefCell::new(None) };
+            }
+
+            ICON_PATHS.with(|paths| {
+                let mut paths = paths.borrow_mut();
+                let (icon_path, hover_path) = paths
+                    .get_or_insert_with(|| (config.qa_icon_path.clone(), config.qa_icon_hover_path.clone()));
+                let icon_entered = InputText::new(ui, "Icon PNG", icon_path)
+                    .hint("C:\\path\\to\\icon.png")
+                    .enter_returns_true(true)
+                    .build();
+                let hover_entered = InputText::new(ui, "Hover Icon PNG", hover_path)
+                    .hint("optional, defaults to the icon")
+                    .enter_returns_true(true)
+                    .build();
+                ui.same_line();
+                if icon_entered || hover_entered || ui.button("Apply##qaicon") {
+                    config.qa_icon_path = icon_path.trim().to_string();
+                    config.qa_icon_hover_path = hover_path.trim().to_string();
+                }
+            });
+            ui.text_disabled("Square PNGs around 64x64 work best. Unreadable files fall back to the classic icon.");
+        }
 
         ui.unindent();
     }
 
     // ==================== NOTIFICATIONS & TRACKING ====================
-    if ui.collapsing_header("Notifications & Tracking", TreeNodeFlags::DEFAULT_OPEN) {
+    if section_header(ui, "Notifications & Tracking", SettingsSection::Notifications, TreeNodeFlags::DEFAULT_OPEN) {
         ui.indent();
 
         // --- Toast Notifications ---
         ui.text("Toast Notifications");
         ui.checkbox("Enable Toasts", &mut config.notification_config.toast_enabled);
+        ui.same_line();
+        ui.checkbox("Mute All", &mut config.notification_config.muted);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Temporarily silence every reminder.\nAlso available from the Quick Access icon's right-click menu and a keybind.");
+        }
+        ui.text("Snooze Keybind:");
+        for minutes in crate::config::SNOOZE_CHOICES {
+            ui.same_line();
+            ui.radio_button(format!("{} min##snooze_keybind", minutes), &mut config.notification_config.snooze_keybind_minutes, minutes);
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("How long the \"Snooze Notifications\" keybind silences reminders.\nPress it again to resume early. The Quick Access menu offers every length.");
+        }
+
+        ui.checkbox("Quick Access Badge", &mut config.notification_config.qa_badge_enabled);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Show on the Quick Access icon how many tracked events start soon");
+        }
+        if config.notification_config.qa_badge_enabled {
+            ui.same_line();
+            let mut badge_minutes = config.notification_config.qa_badge_minutes as i32;
+            ui.set_next_item_width(120.0);
+            if nexus::imgui::Slider::new("Within (min)##qa_badge", 1, 60).build(ui, &mut badge_minutes) {
+                config.notification_config.qa_badge_minutes = badge_minutes as u32;
+            }
+        }
+
+        ui.checkbox("Auto Check-In", &mut config.notification_config.auto_attendance);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Count a tracked event as attended when you're on one of its track's maps while it runs.\nOnly tracks with map ids can be checked in to.");
+        }
+        if config.notification_config.auto_attendance {
+            ui.same_line();
+            ui.checkbox("Mute Rest of Day", &mut config.notification_config.attendance_mutes_day);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("After checking in, skip the event's remaining reminders until daily reset");
+            }
+        }
+        let attended = config.notification_config.attendance.len();
+        if attended > 0 {
+            ui.text_disabled(format!("{} check-ins recorded (kept {} days)", attended, ATTENDANCE_DAYS));
+            ui.same_line();
+            if ui.small_button("Clear##attendance") {
+                config.notification_config.attendance.clear();
+            }
+        }
 
         if config.notification_config.toast_enabled {
+            ui.checkbox("Hold Toasts While Away", &mut config.notification_config.hold_while_away);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("While the game is unfocused or loading, keep reminders back and show them (or a summary) when you return");
+            }
+
+            let catch_up_labels: Vec<&str> = CatchUpPolicy::ALL.iter().map(|p| p.label()).collect();
+            let mut catch_up_index = CatchUpPolicy::ALL
+                .iter()
+                .position(|&p| p == config.notification_config.catch_up_policy)
+                .unwrap_or(0);
+            if ui.combo_simple_string("Missed Reminders", &mut catch_up_index, &catch_up_labels) {
+                config.notification_config.catch_up_policy = CatchUpPolicy::ALL[catch_up_index];
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Reminders that came due during a loading screen, or while the PC was asleep\nor the clock jumped ahead");
+            }
+
             nexus::imgui::Slider::new("Toast Duration (sec)", 3.0, 15.0)
                 .build(ui, &mut config.notification_config.toast_duration_seconds);
 
@@ -309,6 +869,42 @@ pub fn render_settings(ui: &Ui) {
                 .flags(ColorEditFlags::ALPHA_BAR)
                 .build(ui);
 
+            ColorEdit::new("High Priority Background", &mut config.notification_config.high_priority_toast_color)
+                .flags(ColorEditFlags::ALPHA_BAR)
+                .build(ui);
+            nexus::imgui::Slider::new("High Priority Duration", 1.0, 4.0)
+                .display_format("x%.1f")
+                .build(ui, &mut config.notification_config.high_priority_duration_factor);
+
+            ui.spacing();
+            ui.checkbox("Center-Screen Alert", &mut config.notification_config.center_alert_enabled);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Large countdown in the middle of the screen shortly before high priority events");
+            }
+            if config.notification_config.center_alert_enabled {
+                let notifications = &mut config.notification_config;
+                let mut lead = notifications.center_alert_lead_seconds as i32;
+                if nexus::imgui::Slider::new("Show Before Start (sec)", 5, 120).build(ui, &mut lead) {
+                    notifications.center_alert_lead_seconds = lead as u32;
+                }
+                nexus::imgui::Slider::new("Alert Duration (sec)", 1.0, 10.0)
+                    .display_format("%.1f")
+                    .build(ui, &mut notifications.center_alert_duration);
+                nexus::imgui::Slider::new("Alert Text Scale", 1.5, 6.0)
+                    .build(ui, &mut notifications.center_alert_text_scale);
+                let mut y_pct = notifications.center_alert_offset_y * 100.0;
+                if nexus::imgui::Slider::new("Alert Height", 5.0, 95.0)
+                    .display_format("%.0f%%")
+                    .build(ui, &mut y_pct)
+                {
+                    notifications.center_alert_offset_y = y_pct / 100.0;
+                }
+                let mut max_per_hour = notifications.center_alert_max_per_hour as i32;
+                if nexus::imgui::Slider::new("Max Alerts per Hour", 1, 20).build(ui, &mut max_per_hour) {
+                    notifications.center_alert_max_per_hour = max_per_hour as u32;
+                }
+            }
+
             if ui.button("Preview Toast") {
                 let (name, color) = config.notification_config.reminders.first()
                     .map(|r| (r.name.clone(), r.text_color))
@@ -347,6 +943,48 @@ pub fn render_settings(ui: &Ui) {
                 if nexus::imgui::Slider::new("Repeat Interval (min)", 1, 10).build(ui, &mut interval) {
                     config.notification_config.reminders[i].ongoing_interval_minutes = interval.max(1) as u32;
                 }
+            } else {
+                let reminder = &mut config.notification_config.reminders[i];
+                ui.text("Count To:");
+                ui.same_line();
+                if ui.radio_button_bool("Start", !reminder.before_pre_event && !reminder.before_end) {
+                    reminder.before_pre_event = false;
+                    reminder.before_end = false;
+                }
+                ui.same_line();
+                if ui.radio_button_bool("Pre-Event", reminder.before_pre_event && !reminder.before_end) {
+                    reminder.before_pre_event = true;
+                    reminder.before_end = false;
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Count down to the pre-event (warmup) start instead of the main start.\nOnly applies to events that have a pre-event.");
+                }
+                ui.same_line();
+                if ui.radio_button_bool("End", reminder.before_end) {
+                    reminder.before_pre_event = false;
+                    reminder.before_end = true;
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Fire this long before a running event ends, e.g. \"5 minutes left\"");
+                }
+
+                let reminder = &mut config.notification_config.reminders[i];
+                let mut repeats = reminder.repeat_count as i32;
+                if nexus::imgui::Slider::new("Repeat Times", 0, 5).build(ui, &mut repeats) {
+                    reminder.repeat_count = repeats as u32;
+                }
+                if reminder.repeat_count > 0 {
+                    let mut spacing = reminder.repeat_spacing_minutes as i32;
+                    if nexus::imgui::Slider::new("Repeat Every (min)", 1, 15).build(ui, &mut spacing) {
+                        reminder.repeat_spacing_minutes = spacing.max(1) as u32;
+                    }
+                    let times: Vec<String> = (0..=reminder.repeat_count as i64)
+                        .map(|k| reminder.minutes_before as i64 - k * reminder.repeat_spacing_minutes as i64)
+                        .take_while(|&minutes| minutes > 0)
+                        .map(|minutes| minutes.to_string())
+                        .collect();
+                    ui.text_disabled(format!("Fires at {} min before", times.join(", ")));
+                }
             }
 
             ColorEdit::new("Reminder Color", &mut config.notification_config.reminders[i].text_color)
@@ -366,6 +1004,18 @@ pub fn render_settings(ui: &Ui) {
         if ui.button("Add Reminder") {
             config.notification_config.reminders.push(crate::config::ReminderConfig::default());
         }
+        ui.checkbox("Use Track Lead Times", &mut config.notification_config.use_track_reminder_defaults);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Tracks that suggest their own lead times (e.g. 10 minutes for world bosses)\nuse those instead of the start countdowns above");
+        }
+        let mut fills_early_minutes = config.notification_config.fills_early_minutes as i32;
+        ui.set_next_item_width(120.0);
+        if nexus::imgui::Slider::new("Fills-Early Lead (min)", 0, 30).build(ui, &mut fills_early_minutes) {
+            config.notification_config.fills_early_minutes = fills_early_minutes as u32;
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Reminders for events that fill early (popular metas like Dragon's End) come\nthis much sooner, so you can join a squad before the map is full.\nTag events from their right-click menu on the timeline.");
+        }
 
         ui.spacing();
         ui.separator();
@@ -379,6 +1029,171 @@ pub fn render_settings(ui: &Ui) {
             if nexus::imgui::Slider::new("Max Events in Panel", 5, 20).build(ui, &mut max_upcoming) {
                 config.notification_config.max_upcoming_events = max_upcoming as usize;
             }
+
+            ui.checkbox("Dock to Main Window", &mut config.notification_config.upcoming_panel_docked);
+            if config.notification_config.upcoming_panel_docked {
+                ui.same_line();
+                ui.radio_button("Left##dock", &mut config.notification_config.upcoming_panel_dock_side, DockSide::Left);
+                ui.same_line();
+                ui.radio_button("Right##dock", &mut config.notification_config.upcoming_panel_dock_side, DockSide::Right);
+                ui.same_line();
+                ui.radio_button("Below##dock", &mut config.notification_config.upcoming_panel_dock_side, DockSide::Below);
+            }
+
+            let notifications = &mut config.notification_config;
+            ui.checkbox("Lock Position##upcoming", &mut notifications.upcoming_panel_locked);
+            ui.same_line();
+            ui.checkbox("Lock Size##upcoming", &mut notifications.upcoming_panel_size_locked);
+            ui.same_line();
+            ui.checkbox("Disable Interaction##upcoming", &mut notifications.upcoming_panel_click_through);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Click-through: the panel ignores the mouse (no copying or menus)");
+            }
+            ui.checkbox("Show Title Bar##upcoming", &mut notifications.upcoming_panel_show_title_bar);
+            ui.same_line();
+            ui.checkbox("Hide Background##upcoming", &mut notifications.upcoming_panel_hide_background);
+            if !notifications.upcoming_panel_hide_background {
+                nexus::imgui::Slider::new("Panel Opacity", 0.1, 1.0)
+                    .display_format("%.2f")
+                    .build(ui, &mut notifications.upcoming_panel_opacity);
+            }
+            ui.text("Extra Columns:");
+            ui.same_line();
+            ui.checkbox("Track##upcoming_column", &mut notifications.upcoming_show_track_column);
+            ui.same_line();
+            ui.checkbox("Waypoint##upcoming_column", &mut notifications.upcoming_show_waypoint_column);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Click a column header in the panel to sort by it; the order is remembered");
+            }
+            ui.text("Group By:");
+            for grouping in UpcomingGrouping::ALL {
+                ui.same_line();
+                ui.radio_button(
+                    format!("{}##upcoming_group", grouping.label()),
+                    &mut notifications.upcoming_group_by,
+                    grouping,
+                );
+            }
+
+            ColorEdit::new("Upcoming Time", &mut notifications.upcoming_time_color)
+                .flags(ColorEditFlags::NO_INPUTS)
+                .build(ui);
+            ui.same_line();
+            ColorEdit::new("Active Time", &mut notifications.active_time_color)
+                .flags(ColorEditFlags::NO_INPUTS)
+                .build(ui);
+            ui.same_line();
+            ColorEdit::new("Imminent Time", &mut notifications.imminent_time_color)
+                .flags(ColorEditFlags::NO_INPUTS)
+                .build(ui);
+            let mut imminent_seconds = notifications.imminent_seconds as i32;
+            if nexus::imgui::Slider::new("Imminent Within (seconds)", 0, 600).build(ui, &mut imminent_seconds) {
+                notifications.imminent_seconds = imminent_seconds as u32;
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Times of events starting this soon use the imminent color (0 = off)");
+            }
+
+            let mut countdown_seconds = notifications.upcoming_countdown_seconds as i32;
+            if nexus::imgui::Slider::new("Final Countdown (seconds)", 0, 120).build(ui, &mut countdown_seconds) {
+                notifications.upcoming_countdown_seconds = countdown_seconds as u32;
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Events starting within this many seconds are highlighted (0 = off)");
+            }
+            if notifications.upcoming_countdown_seconds > 0 {
+                ui.checkbox("Pulse Row##countdown", &mut notifications.upcoming_countdown_pulse);
+                ui.same_line();
+                ui.checkbox("Beep Each Second##countdown", &mut notifications.upcoming_countdown_beep);
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Ticks every second of the countdown and beeps at the spawn (not for low priority or muted events)");
+                }
+            }
+        }
+
+        ui.spacing();
+        ui.separator();
+
+        // --- Stream Overlay ---
+        ui.text("Stream Overlay");
+        ui.checkbox("Write Upcoming Events to File", &mut config.stream_export_enabled);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Keeps stream/upcoming.json and stream/upcoming.txt in the addon folder up to date,\nfor an OBS browser source or a text source reading from a file");
+        }
+        if config.stream_export_enabled {
+            ui.indent();
+            nexus::imgui::Slider::new("Events##stream_export", 1, 20).build(ui, &mut config.stream_export_count);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Limited by Max Upcoming Events as well");
+            }
+            nexus::imgui::Slider::new("Every (sec)##stream_export", 1, 60).build(ui, &mut config.stream_export_interval);
+            if ui.small_button("Open Folder##stream_export") {
+                if let Some(dir) = nexus::paths::get_addon_dir("event_timers").map(|dir| dir.join("stream")) {
+                    std::fs::create_dir_all(&dir).ok();
+                    if let Err(e) = open::that(&dir) {
+                        addon_log::warn(format!("Could not open {}: {}", dir.display(), e));
+                    }
+                }
+            }
+            ui.unindent();
+        }
+
+        ui.checkbox("Local HTTP Endpoint", &mut config.http_server_enabled);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Serves the upcoming events as JSON on this PC only (127.0.0.1),\nfor overlays, second-screen dashboards or home automation");
+        }
+        if config.http_server_enabled {
+            ui.indent();
+            let mut port = config.http_server_port as i32;
+            ui.set_next_item_width(120.0);
+            if nexus::imgui::InputInt::new(ui, "Port##http_server", &mut port).step(0).build() {
+                config.http_server_port = port.clamp(1024, u16::MAX as i32) as u32;
+            }
+            let url = format!("http://127.0.0.1:{}/", config.http_server_port);
+            ui.same_line();
+            if ui.small_button("Copy URL##http_server") {
+                ui.set_clipboard_text(&url);
+            }
+            ui.text_disabled(&url);
+            ui.unindent();
+        }
+
+        ui.checkbox("Webhook on Reminders", &mut config.webhook_enabled);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("POSTs the body below to a URL whenever a reminder fires, also when its toast is off.\nUses the timeout and proxy from Download Options; failures show up in the log.");
+        }
+        if config.webhook_enabled {
+            ui.indent();
+            InputText::new(ui, "URL##webhook", &mut config.webhook_url).hint("https://...").build();
+            nexus::imgui::InputTextMultiline::new(ui, "Headers##webhook", &mut config.webhook_headers, [0.0, 50.0]).build();
+            if ui.is_item_hovered() {
+                ui.tooltip_text("One \"Name: value\" per line, e.g. Authorization: Bearer ...");
+            }
+            if let Err(e) = crate::webhook::parse_headers(&config.webhook_headers) {
+                ui.text_colored([1.0, 0.4, 0.4, 1.0], format!("Invalid headers: {}", e));
+            }
+            nexus::imgui::InputTextMultiline::new(ui, "Body##webhook", &mut config.webhook_body, [0.0, 70.0]).build();
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Sent as JSON. Placeholders: {event} {track} {category} {reminder}\n{minutes} {start_time} {map} {copy_text}");
+            }
+            if ui.small_button("Send Test##webhook") {
+                let test = crate::webhook::WebhookFire {
+                    event: "Test Event".to_string(),
+                    track: "Event Timers".to_string(),
+                    category: "Test".to_string(),
+                    reminder: "Webhook test".to_string(),
+                    start_time: crate::time_utils::get_current_unix_time() + 300,
+                    seconds_until: 300,
+                    map: String::new(),
+                    copy_text: String::new(),
+                };
+                crate::webhook::send(vec![test], crate::webhook::WebhookTarget::from_config(&config));
+            }
+            ui.same_line();
+            if ui.small_button("Reset Body##webhook") {
+                config.webhook_body = crate::config::default_webhook_body();
+            }
+            ui.unindent();
         }
 
         ui.spacing();
@@ -551,7 +1366,9 @@ pub fn render_settings(ui: &Ui) {
                 | TableFlags::BORDERS_INNER_H
                 | TableFlags::PAD_OUTER_X;
 
-            if let Some(_t) = ui.begin_table_with_flags("##tracked", 4, table_flags) {
+            let mut priority_changes: Vec<(TrackedEventId, EventPriority)> = Vec::new();
+
+            if let Some(_t) = ui.begin_table_with_flags("##tracked", 5, table_flags) {
                 ui.table_setup_column_with(nexus::imgui::TableColumnSetup {
                     name: "##color",
                     flags: nexus::imgui::TableColumnFlags::WIDTH_FIXED,
@@ -560,6 +1377,12 @@ pub fn render_settings(ui: &Ui) {
                 });
                 ui.table_setup_column("Event");
                 ui.table_setup_column("Track");
+                ui.table_setup_column_with(nexus::imgui::TableColumnSetup {
+                    name: "Priority",
+                    flags: nexus::imgui::TableColumnFlags::WIDTH_FIXED,
+                    init_width_or_weight: 56.0,
+                    user_id: Default::default(),
+                });
                 ui.table_setup_column_with(nexus::imgui::TableColumnSetup {
                     name: "##x",
                     flags: nexus::imgui::TableColumnFlags::WIDTH_FIXED,
@@ -604,6 +1427,23 @@ pub fn render_settings(ui: &Ui) {
                         ui.text_colored([1.0, 0.6, 0.2, 0.8], "(next only)");
                     }
 
+                    // Priority column: click to cycle Low -> Normal -> High
+                    ui.table_next_column();
+                    let priority = config.notification_config.priority(event_id);
+                    let priority_color = match priority {
+                        EventPriority::Low => [0.6, 0.6, 0.6, 1.0],
+                        EventPriority::Normal => [1.0, 1.0, 1.0, 1.0],
+                        EventPriority::High => [1.0, 0.45, 0.35, 1.0],
+                    };
+                    let priority_text = ui.push_style_color(nexus::imgui::StyleColor::Text, priority_color);
+                    if ui.small_button(format!("{}##prio_{}{}{}", priority.label(), event_id.track_name, event_id.event_name, is_oneshot)) {
+                        priority_changes.push((event_id.clone(), priority.next()));
+                    }
+                    drop(priority_text);
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Low: Upcoming panel only\nHigh: highlighted, longer toast that skips the toast cooldown, plus the center-screen alert");
+                    }
+
                     // Remove button column
                     ui.table_next_column();
                     let _btn_color = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.6, 0.2, 0.2, 0.8]);
@@ -614,6 +1454,10 @@ pub fn render_settings(ui: &Ui) {
                 }
             }
 
+            for (id, priority) in priority_changes {
+                config.notification_config.set_priority(&id, priority);
+            }
+
             for (id, is_oneshot) in to_remove {
                 if is_oneshot {
                     config.oneshot_events.remove(&id);
@@ -626,11 +1470,66 @@ pub fn render_settings(ui: &Ui) {
             ui.text_disabled("No events tracked. Right-click events in the timeline to track them.");
         }
 
+        ui.spacing();
+        ui.separator();
+
+        // --- Watchlist ---
+        ui.text("Watchlist");
+        ui.text_disabled("Events whose name matches a pattern are notified as if tracked");
+
+        thread_local! {
+            static NEW_PATTERN: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+        }
+
+        let mut pattern_to_remove = None;
+        for (index, pattern) in config.notification_config.watchlist.iter().enumerate() {
+            if ui.small_button(format!("X##watch_{}", index)) {
+                pattern_to_remove = Some(index);
+            }
+            ui.same_line();
+            match watchlist::parse_pattern(pattern) {
+                Ok(compiled) => {
+                    let matches = config
+                        .tracks
+                        .iter()
+                        .flat_map(|t| &t.events)
+                        .filter(|e| compiled.is_match(&e.name))
+                        .map(|e| e.name.as_str())
+                        .collect::<HashSet<_>>()
+                        .len();
+                    ui.text(pattern);
+                    ui.same_line();
+                    ui.text_disabled(format!("({} events)", matches));
+                }
+                Err(e) => ui.text_colored([1.0, 0.4, 0.4, 1.0], format!("{}: {}", pattern, e)),
+            }
+        }
+        if let Some(index) = pattern_to_remove {
+            config.notification_config.watchlist.remove(index);
+        }
+
+        NEW_PATTERN.with(|new_pattern| {
+            let mut new_pattern = new_pattern.borrow_mut();
+            ui.set_next_item_width(ui.content_region_avail()[0] - 50.0);
+            let entered = InputText::new(ui, "##new_watch_pattern", &mut new_pattern)
+                .hint("e.g. Dragonstorm or ^Tequatl|Triple")
+                .enter_returns_true(true)
+                .build();
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Case-insensitive, matches anywhere in the name.\n. any character, * + ? repeat, ^ $ anchors, | alternatives, [a-z] classes");
+            }
+            ui.same_line();
+            if (ui.button("Add##watch") || entered) && !new_pattern.trim().is_empty() {
+                config.notification_config.watchlist.push(new_pattern.trim().to_string());
+                new_pattern.clear();
+            }
+        });
+
         ui.unindent();
     }
 
     // ==================== TRACK MANAGEMENT ====================
-    if ui.collapsing_header("Track Management", TreeNodeFlags::empty()) {
+    if section_header(ui, "Track Management", SettingsSection::TrackManagement, TreeNodeFlags::empty()) {
         ui.indent();
 
         // --- Database ---
@@ -641,6 +1540,66 @@ pub fn render_settings(ui: &Ui) {
         ui.same_line();
         ui.text_disabled("Downloads latest events from GitHub");
 
+        if ui.collapsing_header("Download Options", TreeNodeFlags::empty()) {
+            ui.indent();
+            nexus::imgui::Slider::new("Timeout (sec)", 5, 120).build(ui, &mut config.update_timeout_seconds);
+            nexus::imgui::Slider::new("Retries", 0, 6).build(ui, &mut config.update_retries);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Failed downloads are tried again after 2s, 4s, 8s, ...");
+            }
+            InputText::new(ui, "Proxy", &mut config.update_proxy)
+                .hint("http://host:port")
+                .build();
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Leave empty to use the HTTP_PROXY / HTTPS_PROXY environment variables, if set");
+            }
+            ui.unindent();
+        }
+
+        if let Some(pack) = pack_info() {
+            ui.text_disabled(format!("Data version {} (schema {})", pack.version, pack.schema_version));
+            if !pack.skipped_fields.is_empty() {
+                ui.text_colored([1.0, 0.8, 0.3, 1.0], "Pack uses newer schema features that were skipped:");
+                ui.text_wrapped(pack.skipped_fields.join(", "));
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Updating the addon may add support for them; a typo in a custom pack also shows up here");
+                }
+            } else if pack.schema_version > SCHEMA_VERSION {
+                ui.text_colored(
+                    [1.0, 0.8, 0.3, 1.0],
+                    format!("Pack uses schema {}, this version reads {}; update the addon for its newer features", pack.schema_version, SCHEMA_VERSION),
+                );
+            }
+        }
+
+        ui.checkbox("Pack Authoring Mode", &mut config.authoring_mode);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Edit default tracks like custom ones and export everything as an event_tracks.json pack");
+        }
+        if config.authoring_mode {
+            ui.same_line();
+            if ui.button("Export Pack") {
+                let status = match export_pack_file(&config) {
+                    Ok(path) => (format!("Exported {}", path.display()), false),
+                    Err(e) => {
+                        addon_log::error(format!("Pack export failed: {}", e));
+                        (e, true)
+                    }
+                };
+                PACK_EXPORT_STATUS.with(|s| *s.borrow_mut() = Some(status));
+            }
+            ui.text_colored(
+                [1.0, 0.8, 0.3, 1.0],
+                "Edits to default tracks are not saved with your settings, export the pack to keep them.",
+            );
+            PACK_EXPORT_STATUS.with(|s| {
+                if let Some((message, is_error)) = s.borrow().as_ref() {
+                    let color = if *is_error { [1.0, 0.4, 0.4, 1.0] } else { [0.5, 0.9, 0.5, 1.0] };
+                    ui.text_colored(color, message);
+                }
+            });
+        }
+
         ui.spacing();
         ui.separator();
 
@@ -688,6 +1647,7 @@ pub fn render_settings(ui: &Ui) {
 
         let mut category_to_move_up = None;
         let mut category_to_move_down = None;
+        let mut category_to_duplicate = None;
 
         for (cat_pos, category) in ordered_categories.iter().enumerate() {
             if show_vis {
@@ -708,6 +1668,59 @@ pub fn render_settings(ui: &Ui) {
             }
 
             if ui.collapsing_header(category, TreeNodeFlags::empty()) {
+                ui.indent();
+                let mut has_keybind = config.category_keybinds.contains(category);
+                if ui.checkbox(format!("Solo Keybind##ckb_{}", category), &mut has_keybind) {
+                    if has_keybind {
+                        config.category_keybinds.push(category.clone());
+                        crate::category_keybinds::register(category);
+                    } else {
+                        config.category_keybinds.retain(|c| c != category);
+                        if config.solo_category.as_ref() == Some(category) {
+                            config.solo_category = None;
+                        }
+                    }
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text(format!(
+                        "Adds an \"Event Timers: Only {}\" keybind in the Nexus keybind settings.\nPressing it shows only this category on the timeline; press again to show everything.",
+                        category
+                    ));
+                }
+                if let Some(enabled) = bulk_enable_buttons(ui, category) {
+                    for track in config.tracks.iter_mut().filter(|t| t.category == *category) {
+                        set_events_enabled(track, enabled);
+                    }
+                }
+                ui.same_line();
+                if ui.small_button(format!("Duplicate as Custom##dup_{}", category)) {
+                    category_to_duplicate = Some(category.clone());
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Copy every track of this category into a new custom category you can edit freely.\nThe copies don't receive pack updates; hide this category to avoid seeing both.");
+                }
+                ui.unindent();
+
+                if config.category_panels_enabled {
+                    ui.indent();
+                    let mut custom_panel = config.category_panel_colors.contains_key(category);
+                    if ui.checkbox(format!("Custom Panel Color##cpc_{}", category), &mut custom_panel) {
+                        if custom_panel {
+                            let color = config.category_panel_color;
+                            config.category_panel_colors.insert(category.clone(), color);
+                        } else {
+                            config.category_panel_colors.remove(category);
+                        }
+                    }
+                    if let Some(color) = config.category_panel_colors.get_mut(category) {
+                        ui.same_line();
+                        ColorEdit::new(&format!("##cpc_color_{}", category), color)
+                            .flags(ColorEditFlags::ALPHA_BAR | ColorEditFlags::NO_INPUTS)
+                            .build(ui);
+                    }
+                    ui.unindent();
+                }
+
                 let track_indices: Vec<usize> = config.tracks.iter().enumerate()
                     .filter(|(_, t)| t.category == *category)
                     .map(|(i, _)| i)
@@ -721,7 +1734,7 @@ pub fn render_settings(ui: &Ui) {
                 for (list_pos, &index) in track_indices.iter().enumerate() {
                     let track_name = config.tracks[index].name.clone();
                     let mut track_visible = config.tracks[index].visible;
-                    let is_default = default_names.contains(track_name.as_str());
+                    let is_default = default_names.contains(track_name.as_str()) && !config.authoring_mode;
 
                     ui.indent();
 
@@ -746,7 +1759,7 @@ pub fn render_settings(ui: &Ui) {
                         if ui.collapsing_header(&track_name, TreeNodeFlags::empty()) {
                             let mut tracked_events_clone = config.tracked_events.clone();
                             let track = &mut config.tracks[index];
-                            render_default_track_editor_inline(ui, track, &mut tracked_events_clone);
+                            render_default_track_editor_inline(ui, track, &mut tracked_events_clone, None);
                             config.tracked_events = tracked_events_clone;
                         }
                     } else {
@@ -783,24 +1796,68 @@ pub fn render_settings(ui: &Ui) {
         } else if let Some(pos) = category_to_move_down {
             config.category_order.swap(pos, pos + 1);
         }
+        if let Some(category) = category_to_duplicate {
+            duplicate_category_as_custom(&mut config, &category);
+        }
 
         ui.separator();
 
         if ui.button("Add Custom Track") {
-            let (default_tracks, _) = load_tracks_from_json();
-            let default_names: HashSet<&str> = default_tracks.iter().map(|t| t.name.as_str()).collect();
-            let custom_count = config.tracks.iter().filter(|t| !default_names.contains(t.name.as_str())).count();
-            let mut track = EventTrack::default();
-            track.name = format!("Custom Track {}", custom_count + 1);
-            track.category = "Custom".to_string();
-            let new_index = config.tracks.len();
-            config.tracks.push(track);
+            let new_index = add_custom_track(&mut config);
             *SELECTED_TRACK.lock() = Some(new_index);
         }
+        ui.same_line();
+        if ui.button("Track Wizard...") {
+            track_wizard::open();
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Step-by-step: name, schedule, spawn times (local or Tyrian) and color");
+        }
+        ui.same_line();
+        if ui.button("New Event from Clipboard") {
+            // Only read on click, the clipboard is an OS call
+            match ui.clipboard_text().as_deref().and_then(chat_link) {
+                Some(code) => CHAT_LINK_DRAFT.with(|draft| {
+                    CHAT_LINK_MISSING.with(|missing| missing.set(false));
+                    let (default_tracks, _) = load_tracks_from_json();
+                    *draft.borrow_mut() = Some(ChatLinkDraft {
+                        code: code.to_string(),
+                        default_names: default_tracks.into_iter().map(|t| t.name).collect(),
+                        name: String::new(),
+                        track: 0,
+                        starts_in: 10,
+                        duration: 15,
+                    });
+                }),
+                None => CHAT_LINK_MISSING.with(|missing| missing.set(true)),
+            }
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Copy a waypoint chat link ([&...]) first, e.g. from a commander's ping,\nthen add it as an event to a custom track");
+        }
+        if CHAT_LINK_MISSING.with(|missing| missing.get()) {
+            ui.text_disabled("No waypoint chat link on the clipboard");
+            if ui.is_item_clicked() {
+                CHAT_LINK_MISSING.with(|missing| missing.set(false));
+            }
+        }
+        render_chat_link_event(ui, &mut config);
 
         ui.unindent();
     }
 
+    // ==================== BACKUPS ====================
+    if section_header(ui, "Backups", SettingsSection::Backups, TreeNodeFlags::empty()) {
+        render_backups_section(ui);
+    }
+
+    // ==================== ADVANCED ====================
+    if ui.collapsing_header("Advanced: Raw JSON Editor", TreeNodeFlags::empty()) {
+        ui.indent();
+        json_editor::render_json_editor(ui);
+        ui.unindent();
+    }
+
     // ==================== RESET ====================
     ui.separator();
     ui.text_colored([1.0, 0.4, 0.4, 1.0], "Reset");
@@ -817,11 +1874,130 @@ pub fn render_settings(ui: &Ui) {
         ui.text_disabled("[Hold Ctrl] Reset All Settings");
     }
 
-    ui.separator();
+}
+
+/// Track editor window, opened from settings or by double-clicking an event on the timeline.
+/// Rendered every frame (not only while the options panel is open).
+pub fn render_track_editor_window(ui: &Ui) {
+    let mut config = RUNTIME_CONFIG.lock();
     render_custom_track_editor(ui, &mut config);
 }
 
-fn render_custom_track_editor(ui: &Ui, config: &mut MutexGuard<RuntimeConfig>) {
+/// Append an empty custom track named "Custom Track N" and return its index
+fn add_custom_track(config: &mut RuntimeConfig) -> usize {
+    let (default_tracks, _) = load_tracks_from_json();
+    let default_names: HashSet<&str> = default_tracks.iter().map(|t| t.name.as_str()).collect();
+    let custom_count = config.tracks.iter().filter(|t| !default_names.contains(t.name.as_str())).count();
+    let mut track = EventTrack::default();
+    track.name = format!("Custom Track {}", custom_count + 1);
+    track.category = "Custom".to_string();
+    config.tracks.push(track);
+    config.tracks.len() - 1
+}
+
+/// The first waypoint or point of interest chat link ("[&BDgAAAA=]") in `text`
+fn chat_link(text: &str) -> Option<&str> {
+    // A map link decodes to its type byte followed by the 4-byte point id
+    const MAP_LINK_TYPE: u8 = 0x04;
+    const MAP_LINK_LEN: usize = 5;
+
+    let mut from = 0;
+    while let Some(found) = text[from..].find("[&") {
+        let start = from + found;
+        let end = start + text[start..].find(']')?;
+        let payload = base64::engine::general_purpose::STANDARD.decode(&text[start + 2..end]);
+        if payload.is_ok_and(|bytes| bytes.len() == MAP_LINK_LEN && bytes[0] == MAP_LINK_TYPE) {
+            return Some(&text[start..=end]);
+        }
+        from = start + 2;
+    }
+    None
+}
+
+/// Event being created from a chat link, until it's added or cancelled
+struct ChatLinkDraft {
+    code: String,
+    /// Tracks of the event data, read once when the draft is made
+    default_names: HashSet<String>,
+    name: String,
+    /// Index into the custom tracks, one past the end for a new track
+    track: usize,
+    /// Minutes from now
+    starts_in: i32,
+    /// Minutes
+    duration: i32,
+}
+
+thread_local! {
+    static CHAT_LINK_DRAFT: std::cell::RefCell<Option<ChatLinkDraft>> = const { std::cell::RefCell::new(None) };
+    // The last "New Event from Clipboard" found nothing to use
+    static CHAT_LINK_MISSING: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Form for the chat link event: name, custom track and start. The event repeats daily like any
+/// other, but only its next occurrence is tracked.
+fn render_chat_link_event(ui: &Ui, config: &mut RuntimeConfig) {
+    CHAT_LINK_DRAFT.with(|draft_cell| {
+        let mut draft_slot = draft_cell.borrow_mut();
+        let Some(draft) = draft_slot.as_mut() else {
+            return;
+        };
+
+        let custom: Vec<usize> = (0..config.tracks.len())
+            .filter(|&i| !draft.default_names.contains(&config.tracks[i].name))
+            .collect();
+
+        ui.spacing();
+        ui.text(format!("New event for {}", draft.code));
+        ui.indent();
+        InputText::new(ui, "Name##chat_link", &mut draft.name).hint("e.g. Commander's pin").build();
+
+        let mut track_names: Vec<&str> = custom.iter().map(|&i| config.tracks[i].name.as_str()).collect();
+        track_names.push("(new custom track)");
+        draft.track = draft.track.min(custom.len());
+        ui.combo_simple_string("Track##chat_link", &mut draft.track, &track_names);
+
+        nexus::imgui::Slider::new("Starts in (min)##chat_link", 0, 180).build(ui, &mut draft.starts_in);
+        nexus::imgui::Slider::new("Duration (min)##chat_link", 1, 120).build(ui, &mut draft.duration);
+
+        let name = draft.name.trim().to_string();
+        let taken = custom.get(draft.track).is_some_and(|&i| config.tracks[i].events.iter().any(|e| e.name == name));
+        if name.is_empty() {
+            ui.text_disabled("Name the event to add it");
+        } else if taken {
+            ui.text_colored([1.0, 0.4, 0.4, 1.0], "That track already has an event with this name");
+        } else if ui.button("Add Event##chat_link") {
+            const DAY: i64 = 24 * 3600;
+            let track_index = match custom.get(draft.track) {
+                Some(&index) => index,
+                None => add_custom_track(config),
+            };
+            let track = &mut config.tracks[track_index];
+            let start = get_current_unix_time() + draft.starts_in as i64 * 60;
+            track.events.push(TimelineEvent {
+                name: name.clone(),
+                start_offset: (start - track.base_time).rem_euclid(DAY),
+                duration: draft.duration as i64 * 60,
+                cycle_duration: DAY,
+                copy_text: draft.code.clone(),
+                ..Default::default()
+            });
+            let event_id = TrackedEventId::new(&track.name, &name);
+            addon_log::info(format!("Added {} ({}) from a chat link", name, track.name));
+            config.oneshot_events.insert(event_id);
+            *draft_slot = None;
+        }
+        if draft_slot.is_some() {
+            ui.same_line();
+            if ui.button("Cancel##chat_link") {
+                *draft_slot = None;
+            }
+        }
+        ui.unindent();
+    });
+}
+
+fn render_custom_track_editor(ui: &Ui, config: &mut RuntimeConfig) {
     let mut selected_track = SELECTED_TRACK.lock();
     let mut selected_event = SELECTED_EVENT.lock();
 
@@ -830,7 +2006,7 @@ fn render_custom_track_editor(ui: &Ui, config: &mut MutexGuard<RuntimeConfig>) {
         let default_names: HashSet<&str> = default_tracks.iter().map(|t| t.name.as_str()).collect();
 
         if track_index < config.tracks.len() {
-            let is_custom = !default_names.contains(config.tracks[track_index].name.as_str());
+            let is_custom = !default_names.contains(config.tracks[track_index].name.as_str()) || config.authoring_mode;
 
             if is_custom {
                 let mut open = true;
@@ -846,7 +2022,28 @@ fn render_custom_track_editor(ui: &Ui, config: &mut MutexGuard<RuntimeConfig>) {
                     *selected_event = None;
                 }
             } else {
-                *selected_track = None;
+                // Default tracks: the inline editor in its own window, with the
+                // selected event (if any) expanded once
+                let focus_event = selected_event.take()
+                    .and_then(|i| config.tracks[track_index].events.get(i))
+                    .map(|e| e.name.clone());
+                let track_name = config.tracks[track_index].name.clone();
+
+                let mut open = true;
+                Window::new("Edit Track")
+                    .opened(&mut open)
+                    .size([450.0, 500.0], nexus::imgui::Condition::FirstUseEver)
+                    .build(ui, || {
+                        ui.text(&track_name);
+                        ui.separator();
+                        let mut tracked_events_clone = config.tracked_events.clone();
+                        render_default_track_editor_inline(ui, &mut config.tracks[track_index], &mut tracked_events_clone, focus_event.as_deref());
+                        config.tracked_events = tracked_events_clone;
+                    });
+
+                if !open {
+                    *selected_track = None;
+                }
             }
         } else {
             *selected_track = None;
@@ -854,16 +2051,25 @@ fn render_custom_track_editor(ui: &Ui, config: &mut MutexGuard<RuntimeConfig>) {
     }
 }
 
-fn render_default_track_editor_inline(ui: &Ui, track: &mut EventTrack, tracked_events: &mut HashSet<TrackedEventId>) {
+fn render_default_track_editor_inline(ui: &Ui, track: &mut EventTrack, tracked_events: &mut HashSet<TrackedEventId>, focus_event: Option<&str>) {
     if InputFloat::new(ui, "Track Height", &mut track.height).build() {
         track.height = track.height.max(20.0).min(200.0);
     }
+    ui.checkbox(format!("Auto-track new events##{}", track.name), &mut track.auto_track_new);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Events added to this track by a data update are tracked automatically");
+    }
+    track_icon_input(ui, &format!("Icon##{}", track.name), &mut track.icon);
 
     ui.separator();
     ui.text("Events");
+    if let Some(enabled) = bulk_enable_buttons(ui, &track.name) {
+        set_events_enabled(track, enabled);
+    }
 
     let mut changes: Vec<(String, Option<bool>, Option<[f32; 4]>, Option<i64>, Option<i64>)> = Vec::new();
     let mut tracking_changes: Vec<(String, bool)> = Vec::new();
+    let mut display_changes: Vec<(String, bool, bool)> = Vec::new();
     let mut seen_names = HashSet::new();
 
     for event in track.events.iter() {
@@ -876,11 +2082,21 @@ fn render_default_track_editor_inline(ui: &Ui, track: &mut EventTrack, tracked_e
             format!("- {}", event.name)
         };
 
-        if ui.collapsing_header(&label, TreeNodeFlags::empty()) {
+        let focused = focus_event == Some(event.name.as_str());
+        if collapsing_header_opened(ui, &label, TreeNodeFlags::empty(), focused) {
             ui.indent();
+            if focused {
+                ui.set_scroll_here_y();
+            }
 
             let mut current_enabled = event.enabled;
             let enabled_changed = ui.checkbox(&format!("Enabled##{}", event.name), &mut current_enabled);
+            ui.same_line();
+            let mut show_on_timeline = event.show_on_timeline;
+            let mut notify = event.notify;
+            if event_display_checkboxes(ui, &event.name, &mut show_on_timeline, &mut notify) {
+                display_changes.push((event.name.clone(), show_on_timeline, notify));
+            }
 
             let event_id = TrackedEventId::new(&track.name, &event.name);
             let mut tracked = tracked_events.contains(&event_id);
@@ -893,13 +2109,13 @@ fn render_default_track_editor_inline(ui: &Ui, track: &mut EventTrack, tracked_e
                 .flags(ColorEditFlags::ALPHA_BAR)
                 .build(ui);
 
-            let mut start_min = (event.start_offset / 60) as i32;
-            let offset_changed = nexus::imgui::InputInt::new(ui, &format!("Start (min)##{}", event.name), &mut start_min).build();
+            let mut start_offset = event.start_offset;
+            let offset_changed = input_mmss(ui, &format!("Start (mm:ss)##{}", event.name), &mut start_offset);
 
-            let mut duration_min = (event.duration / 60) as i32;
-            let duration_changed = nexus::imgui::InputInt::new(ui, &format!("Duration (min)##{}", event.name), &mut duration_min).build();
+            let mut duration = event.duration;
+            let duration_changed = input_mmss(ui, &format!("Duration (mm:ss)##{}", event.name), &mut duration);
 
-            ui.text_disabled(&format!("Cycle: {}m", event.cycle_duration / 60));
+            ui.text_disabled(format!("Cycle: {}", format_mmss(event.cycle_duration)));
 
             let reset_clicked = ui.button(&format!("Reset##{}", event.name));
 
@@ -909,6 +2125,7 @@ fn render_default_track_editor_inline(ui: &Ui, track: &mut EventTrack, tracked_e
                     if let Some(dt) = default_tracks.iter().find(|t| t.name == track.name) {
                         if let Some(de) = dt.events.iter().find(|e| e.name == event.name) {
                             changes.push((event.name.clone(), Some(de.enabled), Some(de.color.to_array()), Some(de.start_offset), Some(de.duration)));
+                            display_changes.push((event.name.clone(), true, true));
                         }
                     }
                 } else {
@@ -916,8 +2133,8 @@ fn render_default_track_editor_inline(ui: &Ui, track: &mut EventTrack, tracked_e
                         event.name.clone(),
                         if enabled_changed { Some(current_enabled) } else { None },
                         if color_changed { Some(color) } else { None },
-                        if offset_changed { Some((start_min as i64) * 60) } else { None },
-                        if duration_changed { Some((duration_min.max(1) as i64) * 60) } else { None },
+                        if offset_changed { Some(start_offset) } else { None },
+                        if duration_changed { Some(duration.max(1)) } else { None },
                     ));
                 }
             }
@@ -937,6 +2154,13 @@ fn render_default_track_editor_inline(ui: &Ui, track: &mut EventTrack, tracked_e
         }
     }
 
+    for (name, show_on_timeline, notify) in display_changes {
+        for e in track.events.iter_mut().filter(|e| e.name == name) {
+            e.show_on_timeline = show_on_timeline;
+            e.notify = notify;
+        }
+    }
+
     for (name, should_track) in tracking_changes {
         let event_id = TrackedEventId::new(&track.name, &name);
         if should_track { tracked_events.insert(event_id); }
@@ -944,7 +2168,8 @@ fn render_default_track_editor_inline(ui: &Ui, track: &mut EventTrack, tracked_e
     }
 }
 
-fn render_track_editor_modal(ui: &Ui, config: &mut MutexGuard<RuntimeConfig>, track_index: usize, selected_event: &mut MutexGuard<Option<usize>>) {
+fn render_track_editor_modal(ui: &Ui, config: &mut RuntimeConfig, track_index: usize, selected_event: &mut MutexGuard<Option<usize>>) {
+    let authoring = config.authoring_mode;
     let track = &mut config.tracks[track_index];
 
     let mut name = track.name.clone();
@@ -957,15 +2182,134 @@ fn render_track_editor_modal(ui: &Ui, config: &mut MutexGuard<RuntimeConfig>, tr
         track.category = category;
     }
 
+    if authoring {
+        stable_id_input(ui, "Track ID", &mut track.id);
+    }
+
     if InputFloat::new(ui, "Track Height", &mut track.height).build() {
         track.height = track.height.max(20.0).min(200.0);
     }
 
+    ui.separator();
+    ui.text("Timing");
+
+    ui.text("Timeline:");
+    ui.same_line();
+    ui.radio_button("Game Time##tl", &mut track.timeline_type, TimelineType::GameTime);
+    ui.same_line();
+    ui.radio_button("Real Time##tl", &mut track.timeline_type, TimelineType::RealTime);
+
+    let labels: Vec<&str> = BASE_TIME_CALCULATORS.iter().map(|(_, label)| *label).collect();
+    let mut calculator_idx = BASE_TIME_CALCULATORS
+        .iter()
+        .position(|(id, _)| *id == track.base_time_calculator)
+        .unwrap_or(BASE_TIME_CALCULATORS.len() - 1);
+    if ui.combo_simple_string("Base Time", &mut calculator_idx, &labels) {
+        let calculator = BASE_TIME_CALCULATORS[calculator_idx].0;
+        track.base_time_calculator = calculator.to_string();
+        track.refresh_base_time();
+
+        // Switching calculator resets plain events to its natural cycle length
+        let cycle_seconds = cycle_minutes_for_calculator(calculator) as i64 * 60;
+        for event in &mut track.events {
+            if event.recurrence.trim().is_empty() {
+                event.cycle_duration = cycle_seconds;
+            }
+        }
+        recompile_track_recurrences(track);
+    }
+
+    if !is_known_calculator(&track.base_time_calculator) {
+        let mut epoch = track.base_time.to_string();
+        if InputText::new(ui, "Epoch (unix seconds)", &mut epoch).build() {
+            if let Ok(value) = epoch.trim().parse::<i64>() {
+                track.base_time = value;
+                recompile_track_recurrences(track);
+            }
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Unix timestamp of any cycle start.\nEvent start offsets are measured from this point.");
+        }
+        ui.same_line();
+        if ui.small_button("Now") {
+            track.base_time = crate::time_utils::get_current_unix_time();
+            recompile_track_recurrences(track);
+        }
+    }
+
+    // Cycle length applies to every event that isn't driven by a recurrence expression
+    let plain_cycle = track.events.iter()
+        .find(|e| e.recurrence.trim().is_empty())
+        .map(|e| e.cycle_duration)
+        .unwrap_or_else(|| cycle_minutes_for_calculator(&track.base_time_calculator) as i64 * 60);
+    let mut cycle_seconds = plain_cycle;
+    if input_mmss(ui, "Cycle Length (mm:ss)", &mut cycle_seconds) {
+        let cycle_seconds = cycle_seconds.max(MIN_CYCLE_SECONDS);
+        for event in &mut track.events {
+            if event.recurrence.trim().is_empty() {
+                event.cycle_duration = cycle_seconds;
+            }
+        }
+    }
+
+    expansion_combo(ui, "Requires##track_expansion", &mut track.expansion);
+
+    let mut map_ids = track.map_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ");
+    if InputText::new(ui, "Map IDs", &mut map_ids).hint("e.g. 1052, 1043").build() {
+        track.map_ids = map_ids
+            .split(',')
+            .filter_map(|id| id.trim().parse().ok())
+            .collect();
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Maps this track belongs to, for Auto-Solo Current Map");
+    }
+
+    track_icon_input(ui, "Icon", &mut track.icon);
+
+    let mut lead_times = track.reminder_minutes.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(", ");
+    if InputText::new(ui, "Reminder Lead Times", &mut lead_times).hint("minutes, e.g. 10, 2").build() {
+        track.reminder_minutes = lead_times
+            .split(',')
+            .filter_map(|minutes| minutes.trim().parse().ok())
+            .collect();
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Default reminders for this track's events, in minutes before the start\n(empty uses the global reminders)");
+    }
+
     ui.separator();
     ui.text("Events");
+    if let Some(enabled) = bulk_enable_buttons(ui, "custom_track") {
+        set_events_enabled(track, enabled);
+    }
 
+    thread_local! {
+        static EVENT_TEMPLATE: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+    let mut template_index = EVENT_TEMPLATE.get();
+    let mut template_labels = vec!["Blank (track cycle)"];
+    template_labels.extend(EVENT_TEMPLATES.iter().map(|(label, _, _)| *label));
+    ui.set_next_item_width(200.0);
+    if ui.combo_simple_string("##event_template", &mut template_index, &template_labels) {
+        EVENT_TEMPLATE.set(template_index);
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Template for the next added event.\nTemplates fill in a recurrence, so they don't depend on the track's cycle length.");
+    }
+    ui.same_line();
     if ui.button("Add Event") {
-        track.events.push(TimelineEvent::default());
+        let mut event = TimelineEvent {
+            cycle_duration: plain_cycle,
+            ..Default::default()
+        };
+        if let Some((label, recurrence, duration)) = template_index.checked_sub(1).and_then(|i| EVENT_TEMPLATES.get(i)) {
+            event.name = label.to_string();
+            event.recurrence = template_recurrence(recurrence);
+            event.duration = *duration;
+            event.compile_recurrence(track.base_time).ok();
+        }
+        track.events.push(event);
     }
     ui.separator();
 
@@ -987,10 +2331,14 @@ fn render_track_editor_modal(ui: &Ui, config: &mut MutexGuard<RuntimeConfig>, tr
             }
 
             ui.table_next_column();
-            ui.text(format!("{}m", event.start_offset / 60));
+            if event.recurrence.is_empty() {
+                ui.text(format_mmss(event.start_offset));
+            } else {
+                ui.text(&event.recurrence);
+            }
 
             ui.table_next_column();
-            ui.text(format!("{}m", event.duration / 60));
+            ui.text(format_mmss(event.duration));
 
             ui.table_next_column();
             if ui.small_button(&format!("Edit##ev_{}", idx)) {
@@ -1011,14 +2359,181 @@ fn render_track_editor_modal(ui: &Ui, config: &mut MutexGuard<RuntimeConfig>, tr
     }
 
     if let Some(event_idx) = **selected_event {
+        let base_time = track.base_time;
         if let Some(event) = track.events.get_mut(event_idx) {
             ui.separator();
-            render_event_editor(ui, event);
+            render_event_editor(ui, event, base_time, authoring);
+        }
+    }
+}
+
+/// "Enable All" / "Disable All" buttons on the current line; Some(enabled) when one was clicked
+fn bulk_enable_buttons(ui: &Ui, id: &str) -> Option<bool> {
+    ui.same_line();
+    let enable = ui.small_button(format!("Enable All##bulk_{}", id));
+    ui.same_line();
+    let disable = ui.small_button(format!("Disable All##bulk_{}", id));
+    if enable {
+        Some(true)
+    } else if disable {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn set_events_enabled(track: &mut EventTrack, enabled: bool) {
+    for event in &mut track.events {
+        event.enabled = enabled;
+    }
+}
+
+/// First of `base`, `base 2`, `base 3`, ... that `taken` doesn't contain
+fn unique_name(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut name = base.to_string();
+    let mut number = 2;
+    while taken(&name) {
+        name = format!("{} {}", base, number);
+        number += 1;
+    }
+    name
+}
+
+/// Deep-copy a category's tracks into a new custom category listed right after it
+fn duplicate_category_as_custom(config: &mut RuntimeConfig, category: &str) {
+    let new_category = unique_name(&format!("{} (Custom)", category), |name| {
+        config.category_order.iter().any(|c| c == name) || config.tracks.iter().any(|t| t.category == name)
+    });
+
+    let originals: Vec<EventTrack> = config.tracks.iter().filter(|t| t.category == category).cloned().collect();
+    for mut track in originals {
+        track.name = unique_name(&format!("{} (Custom)", track.name), |name| config.tracks.iter().any(|t| t.name == name));
+        track.category = new_category.clone();
+        // Stable ids belong to the pack; the copies are tracked by name like any custom track
+        track.id.clear();
+        for event in &mut track.events {
+            event.id.clear();
+        }
+        config.tracks.push(track);
+    }
+
+    let position = config.category_order.iter().position(|c| c == category).map_or(config.category_order.len(), |p| p + 1);
+    config.category_order.insert(position, new_category.clone());
+    config.category_visibility.insert(new_category.clone(), true);
+    addon_log::info(format!("Duplicated category '{}' as '{}'", category, new_category));
+}
+
+/// Image path for a track's label column icon
+fn track_icon_input(ui: &Ui, label: &str, icon: &mut String) {
+    InputText::new(ui, label, icon).hint("e.g. icons/tequatl.png").build();
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Shown before the track name in the label column.\nAbsolute, or relative to the addon folder.");
+    }
+}
+
+/// Pack authoring input for a track or event's stable id
+fn stable_id_input(ui: &Ui, label: &str, id: &mut String) {
+    InputText::new(ui, label, id).hint("optional, e.g. tequatl").build();
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Never change an id once published: users' settings follow it when the name changes");
+    }
+}
+
+/// Combo over EXPANSIONS, with an empty id meaning core content (or "inherit" for events)
+fn expansion_combo(ui: &Ui, label: &str, expansion: &mut String) -> bool {
+    let mut labels = vec!["None"];
+    labels.extend(EXPANSIONS.iter().map(|(_, name)| *name));
+    let mut index = EXPANSIONS
+        .iter()
+        .position(|(id, _)| *id == expansion.as_str())
+        .map_or(0, |i| i + 1);
+    if ui.combo_simple_string(label, &mut index, &labels) {
+        *expansion = if index == 0 { String::new() } else { EXPANSIONS[index - 1].0.to_string() };
+        return true;
+    }
+    false
+}
+
+/// Text input for a duration in seconds, edited as "mm:ss" (plain minutes are accepted too)
+fn input_mmss(ui: &Ui, label: &str, seconds: &mut i64) -> bool {
+    let mut text = format_mmss(*seconds);
+    if InputText::new(ui, label, &mut text).build() {
+        if let Some(value) = parse_mmss(&text) {
+            *seconds = value;
+            return true;
+        }
+    }
+    false
+}
+
+/// Editor for the vertical time markers across the timeline
+fn render_time_markers(ui: &Ui, markers: &mut Vec<TimeMarker>) {
+    ui.text("Time Markers:");
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Vertical lines across every track, labelled on the time ruler");
+    }
+
+    let mut marker_to_remove = None;
+    for (i, marker) in markers.iter_mut().enumerate() {
+        ui.checkbox(format!("##tm_enabled_{}", i), &mut marker.enabled);
+        ui.same_line();
+        ColorEdit::new(format!("##tm_color_{}", i), &mut marker.color)
+            .flags(ColorEditFlags::ALPHA_BAR | ColorEditFlags::NO_INPUTS)
+            .build(ui);
+        ui.same_line();
+        ui.set_next_item_width(140.0);
+        InputText::new(ui, format!("##tm_label_{}", i), &mut marker.label).build();
+        ui.same_line();
+        if marker.kind == TimeMarkerKind::LocalDaily {
+            // hh:mm reads like mm:ss, counted in minutes
+            let mut minutes = marker.minute_of_day as i64;
+            ui.set_next_item_width(60.0);
+            if input_mmss(ui, &format!("##tm_time_{}", i), &mut minutes) {
+                marker.minute_of_day = minutes.clamp(0, 24 * 60 - 1) as u32;
+            }
+            ui.same_line();
+            ui.text_disabled("local");
+        } else {
+            ui.text_disabled(marker.kind.label());
         }
+        ui.same_line();
+        if ui.small_button(format!("Remove##tm_{}", i)) {
+            marker_to_remove = Some(i);
+        }
+    }
+    if let Some(i) = marker_to_remove {
+        markers.remove(i);
+    }
+
+    for kind in TimeMarkerKind::all() {
+        if ui.small_button(format!("Add {}##tm_add", kind.label())) {
+            markers.push(TimeMarker::new(*kind));
+        }
+        ui.same_line();
+    }
+    ui.new_line();
+}
+
+/// Fill in the reset placeholders of an EVENT_TEMPLATES recurrence with local times
+fn template_recurrence(template: &str) -> String {
+    const DAY: i64 = 24 * 3600;
+    let now = get_current_unix_time();
+    let daily_reset = now - now.rem_euclid(DAY);
+    // Mondays are 4 days after the unix epoch (a Thursday); weekly reset is at 07:30 UTC
+    let weekly_reset = now - (now - 4 * DAY - 7 * 3600 - 30 * 60).rem_euclid(7 * DAY);
+    template
+        .replace("{reset}", &format_time_only(daily_reset))
+        .replace("{weekly_reset}", &format_weekday_time(weekly_reset))
+}
+
+fn recompile_track_recurrences(track: &mut EventTrack) {
+    let base_time = track.base_time;
+    for event in &mut track.events {
+        event.compile_recurrence(base_time).ok();
     }
 }
 
-fn render_event_editor(ui: &Ui, event: &mut TimelineEvent) {
+fn render_event_editor(ui: &Ui, event: &mut TimelineEvent, base_time: i64, authoring: bool) {
     ui.text("Event Editor");
     ui.separator();
 
@@ -1027,19 +2542,95 @@ fn render_event_editor(ui: &Ui, event: &mut TimelineEvent) {
         event.name = name;
     }
 
-    let mut start_min = (event.start_offset / 60) as i32;
-    if nexus::imgui::InputInt::new(ui, "Start (minutes)", &mut start_min).build() {
-        event.start_offset = (start_min as i64) * 60;
+    if authoring {
+        stable_id_input(ui, "Event ID", &mut event.id);
+    }
+
+    let mut chain = event.chain.clone();
+    if InputText::new(ui, "Chain", &mut chain).hint("optional").build() {
+        event.chain = chain;
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Groups this event as a phase of a meta chain.\nPhases of the same chain are listed together in the timeline tooltip.");
+    }
+
+    let mut recurrence = event.recurrence.clone();
+    if InputText::new(ui, "Recurrence", &mut recurrence)
+        .hint("e.g. every 2h at :00 and :30; Sat,Sun 18:00")
+        .build()
+    {
+        event.recurrence = recurrence;
+        // Keep the last valid compilation while the expression is being typed
+        event.compile_recurrence(base_time).ok();
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Optional. Clauses separated by ';':\n  every 2h at :00 and :30\n  Sat,Sun 18:00\n  Mon-Fri 20:00, 22:30\nTimes are in your local time zone.");
+    }
+
+    let uses_recurrence = !event.recurrence.trim().is_empty();
+    if uses_recurrence {
+        match crate::recurrence::parse_recurrence(&event.recurrence) {
+            Ok(_) => ui.text_disabled(format!(
+                "{} spawn(s) per {} cycle",
+                event.cycle_offsets().len(),
+                format_duration(event.cycle_duration)
+            )),
+            Err(e) => ui.text_colored([1.0, 0.4, 0.4, 1.0], format!("Invalid recurrence: {}", e)),
+        }
+    } else {
+        input_mmss(ui, "Start (mm:ss)", &mut event.start_offset);
+    }
+
+    let mut condition = event.condition.clone();
+    if InputText::new(ui, "Condition", &mut condition)
+        .hint("e.g. day_of_week == \"Sat\" || tyria_night")
+        .build()
+    {
+        event.condition = condition;
+        event.compile_condition().ok();
     }
+    if ui.is_item_hovered() {
+        let variables: Vec<String> = crate::condition::VARIABLES
+            .iter()
+            .map(|(name, description)| format!("  {} - {}", name, description))
+            .collect();
+        ui.tooltip_text(format!(
+            "Optional. Occurrences are only shown and notified when this is true.\nOperators: || && ! == != < <= > >= ( )\n{}",
+            variables.join("\n")
+        ));
+    }
+    if !event.condition.trim().is_empty() {
+        if let Err(e) = crate::condition::parse_condition(&event.condition) {
+            ui.text_colored([1.0, 0.4, 0.4, 1.0], format!("Invalid condition: {}", e));
+        }
+    }
+
+    input_mmss(ui, "Duration (mm:ss)", &mut event.duration);
 
-    let mut duration_min = (event.duration / 60) as i32;
-    if nexus::imgui::InputInt::new(ui, "Duration (minutes)", &mut duration_min).build() {
-        event.duration = (duration_min as i64) * 60;
+    if input_mmss(ui, "Pre-Event (mm:ss)", &mut event.pre_duration) {
+        event.pre_duration = event.pre_duration.max(0);
     }
 
-    let mut cycle_min = (event.cycle_duration / 60) as i32;
-    if nexus::imgui::InputInt::new(ui, "Cycle (minutes)", &mut cycle_min).build() {
-        event.cycle_duration = (cycle_min as i64) * 60;
+    if !uses_recurrence && input_mmss(ui, "Cycle (mm:ss)", &mut event.cycle_duration) {
+        event.cycle_duration = event.cycle_duration.max(MIN_CYCLE_SECONDS);
+    }
+
+    // Live preview of the schedule, so offset/cycle math can be checked while typing
+    let now = get_current_unix_time();
+    let upcoming = timeline::upcoming_occurrences(event, base_time, now, 5);
+    ui.text_disabled("Next occurrences:");
+    if upcoming.is_empty() {
+        ui.text_disabled("  none in the near future");
+    }
+    for start in upcoming {
+        let (tyria_hours, tyria_minutes) = calculate_tyria_time(start);
+        ui.text_disabled(format!(
+            "  {}  (Tyria {:02}:{:02})  in {}",
+            format_weekday_time(start),
+            tyria_hours,
+            tyria_minutes,
+            format_duration(start - now)
+        ));
     }
 
     let mut color = event.color.to_array();
@@ -1052,5 +2643,103 @@ fn render_event_editor(ui: &Ui, event: &mut TimelineEvent) {
         event.copy_text = copy_text;
     }
 
+    InputText::new(ui, "Map", &mut event.map_name).hint("optional").build();
+    InputText::new(ui, "Description", &mut event.description).hint("e.g. bring CC").build();
+    InputText::new(ui, "Rewards", &mut event.rewards).hint("optional").build();
+    expansion_combo(ui, "Requires", &mut event.expansion);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("None = same as the track");
+    }
+    ui.checkbox("Fills Early", &mut event.fills_early);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Map instances fill up before the start; reminders get the fills-early lead time");
+    }
+
+    // Extra copy actions, offered in a menu when the event is clicked
+    let mut action_to_remove = None;
+    for (i, action) in event.copy_actions.iter_mut().enumerate() {
+        let _id = ui.push_id(format!("copy_action_{}", i));
+        ui.set_next_item_width(100.0);
+        InputText::new(ui, "##label", &mut action.label).hint("Label").build();
+        ui.same_line();
+        ui.set_next_item_width(200.0);
+        InputText::new(ui, "##text", &mut action.text).hint("Text to copy").build();
+        ui.same_line();
+        if ui.small_button("X") {
+            action_to_remove = Some(i);
+        }
+    }
+    if let Some(i) = action_to_remove {
+        event.copy_actions.remove(i);
+    }
+    if ui.small_button("Add Copy Action") {
+        event.copy_actions.push(CopyAction::default());
+    }
+
     ui.checkbox("Enabled", &mut event.enabled);
+    ui.same_line();
+    let (mut show_on_timeline, mut notify) = (event.show_on_timeline, event.notify);
+    if event_display_checkboxes(ui, "edit", &mut show_on_timeline, &mut notify) {
+        event.show_on_timeline = show_on_timeline;
+        event.notify = notify;
+    }
+}
+
+/// "On Timeline" / "Notify" checkboxes on the current line; true when either changed
+fn event_display_checkboxes(ui: &Ui, id: &str, show_on_timeline: &mut bool, notify: &mut bool) -> bool {
+    let mut changed = ui.checkbox(format!("On Timeline##{}", id), show_on_timeline);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Draw this event on the timeline. Off hides the bars but keeps notifications.");
+    }
+    ui.same_line();
+    changed |= ui.checkbox(format!("Notify##{}", id), notify);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Allow notifications when tracked or watchlisted. Off keeps the bars but never pings.");
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chat_link_finds_waypoints_and_points_of_interest() {
+        let cases = [
+            // Doomlore Shrine and Tequatl waypoints from the event data
+            ("[&BA4MAAA=]", Some("[&BA4MAAA=]")),
+            ("Meet at [&BNABAAA=] in 5", Some("[&BNABAAA=]")),
+            ("[&BDgAAAA=][&BAQAAAA=]", Some("[&BDgAAAA=]")),
+            // An item link comes first, the waypoint after it is used
+            ("[&AgH1WQAA] then [&BAQAAAA=]", Some("[&BAQAAAA=]")),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(chat_link(text), expected, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn chat_link_needs_five_bytes_of_type_four() {
+        let cases = [
+            // Item, skill and trait links
+            "[&AgH1WQAA]",
+            "[&BucCAAA=]",
+            "[&B/IAAAA=]",
+            // Type 4 but too short or too long
+            "[&BDgAAA==]",
+            "[&BDgAAAAA]",
+            "[&BA==]",
+        ];
+        for text in cases {
+            assert_eq!(chat_link(text), None, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn chat_link_rejects_malformed_input() {
+        let cases = ["", "BDgAAAA=", "[BDgAAAA=]", "[&]", "[&BDgAAAA=", "[&BDg*AAA=]", "[&BDg AAAA=]", "[&[&]"];
+        for text in cases {
+            assert_eq!(chat_link(text), None, "{:?}", text);
+        }
+    }
 }
@@ -0,0 +1,122 @@
+use crate::config::DockSide;
+use nexus::imgui::{MouseButton, Ui};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// (position, size) in screen space
+pub type Rect = ([f32; 2], [f32; 2]);
+
+// Screen-space rects of the addon windows from this/last frame, and positions to apply next frame
+thread_local! {
+    static WINDOW_RECTS: RefCell<HashMap<&'static str, Rect>> = RefCell::new(HashMap::new());
+    static DRAG_START: RefCell<HashMap<&'static str, [f32; 2]>> = RefCell::new(HashMap::new());
+    static PENDING_POSITION: RefCell<HashMap<&'static str, [f32; 2]>> = RefCell::new(HashMap::new());
+    static LAST_DISPLAY_SIZE: RefCell<HashMap<&'static str, [f32; 2]>> = RefCell::new(HashMap::new());
+}
+
+/// Position a window should be moved to this frame (after a snap), if any
+pub fn take_pending_position(window: &'static str) -> Option<[f32; 2]> {
+    PENDING_POSITION.with(|p| p.borrow_mut().remove(window))
+}
+
+/// Last known (position, size) of a window
+pub fn window_rect(window: &'static str) -> Option<Rect> {
+    WINDOW_RECTS.with(|r| r.borrow().get(window).copied())
+}
+
+/// Call inside a window's build closure. Records its rect and, when a drag ends,
+/// snaps it to the screen edges or other addon windows within `threshold` pixels.
+pub fn track_window(ui: &Ui, window: &'static str, snap_enabled: bool, threshold: f32) {
+    let pos = ui.window_pos();
+    let size = ui.window_size();
+    WINDOW_RECTS.with(|r| r.borrow_mut().insert(window, (pos, size)));
+
+    if !snap_enabled {
+        return;
+    }
+
+    if ui.is_mouse_clicked(MouseButton::Left) && ui.is_window_hovered() {
+        DRAG_START.with(|d| d.borrow_mut().insert(window, pos));
+    }
+
+    if ui.is_mouse_released(MouseButton::Left) {
+        let drag_start = DRAG_START.with(|d| d.borrow_mut().remove(window));
+        if drag_start.is_some_and(|start| start != pos) {
+            let display_size = ui.io().display_size;
+            let snapped = snap_position(window, pos, size, display_size, threshold);
+            if snapped != pos {
+                PENDING_POSITION.with(|p| p.borrow_mut().insert(window, snapped));
+            }
+        }
+    }
+}
+
+/// Resolution-relative placement. When the display size changed since the window was last
+/// placed (including the first frame), returns the pixel position derived from `stored`,
+/// clamped so the window stays on screen. Otherwise records the window's current position
+/// into `stored` as a fraction of the display.
+pub fn relative_position(window: &'static str, stored: &mut Option<[f32; 2]>, display_size: [f32; 2]) -> Option<[f32; 2]> {
+    if display_size[0] <= 0.0 || display_size[1] <= 0.0 {
+        return None;
+    }
+
+    let last_display = LAST_DISPLAY_SIZE.with(|d| d.borrow_mut().insert(window, display_size));
+    if last_display != Some(display_size) {
+        let fraction = (*stored)?;
+        let size = window_rect(window).map(|(_, size)| size).unwrap_or([0.0, 0.0]);
+        return Some([
+            (fraction[0] * display_size[0]).clamp(0.0, (display_size[0] - size[0]).max(0.0)),
+            (fraction[1] * display_size[1]).clamp(0.0, (display_size[1] - size[1]).max(0.0)),
+        ]);
+    }
+
+    if let Some((pos, _)) = window_rect(window) {
+        *stored = Some([pos[0] / display_size[0], pos[1] / display_size[1]]);
+    }
+    None
+}
+
+/// Position for a panel docked to a side of another window
+pub fn docked_position(anchor: Rect, size: [f32; 2], side: DockSide) -> [f32; 2] {
+    let (anchor_pos, anchor_size) = anchor;
+    match side {
+        DockSide::Right => [anchor_pos[0] + anchor_size[0], anchor_pos[1]],
+        DockSide::Left => [anchor_pos[0] - size[0], anchor_pos[1]],
+        DockSide::Below => [anchor_pos[0], anchor_pos[1] + anchor_size[1]],
+    }
+}
+
+fn snap_position(window: &str, pos: [f32; 2], size: [f32; 2], display_size: [f32; 2], threshold: f32) -> [f32; 2] {
+    // Candidate edges: screen borders, plus both sides of every other addon window
+    let mut x_targets = vec![0.0, display_size[0]];
+    let mut y_targets = vec![0.0, display_size[1]];
+    WINDOW_RECTS.with(|r| {
+        for (&name, &(other_pos, other_size)) in r.borrow().iter() {
+            if name != window {
+                x_targets.extend([other_pos[0], other_pos[0] + other_size[0]]);
+                y_targets.extend([other_pos[1], other_pos[1] + other_size[1]]);
+            }
+        }
+    });
+
+    [
+        snap_axis(pos[0], size[0], &x_targets, threshold),
+        snap_axis(pos[1], size[1], &y_targets, threshold),
+    ]
+}
+
+/// Snap either the near or far edge of a window to the closest target within threshold
+fn snap_axis(start: f32, length: f32, targets: &[f32], threshold: f32) -> f32 {
+    let mut best = start;
+    let mut best_distance = threshold;
+    for &target in targets {
+        for candidate in [target, target - length] {
+            let distance = (candidate - start).abs();
+            if distance <= best_distance {
+                best = candidate;
+                best_distance = distance;
+            }
+        }
+    }
+    best
+}
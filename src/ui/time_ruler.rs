@@ -1,11 +1,49 @@
-use nexus::imgui::Ui;
-use crate::config::TimeRulerInterval;
+use nexus::imgui::{MouseButton, MouseCursor, Ui};
+use std::cell::Cell;
+use crate::config::{TimeMarker, TimeRulerInterval};
 use crate::time_utils::{calculate_tyria_time, format_time_only};
+use crate::ui::label_cache;
+
+/// Pixels either side of the current-time line that count as pointing at it
+pub const NOW_LINE_GRAB: f32 = 4.0;
+
+thread_local! {
+    // ImGui time the now-line status was last copied
+    static NOW_COPIED_AT: Cell<f64> = const { Cell::new(f64::NEG_INFINITY) };
+}
+
+/// Tooltip for the hovered current-time line (on the ruler or a track); clicking copies
+/// a status line such as "Local 21:34 / Tyria 06:40"
+pub fn now_line_hovered(ui: &Ui, current_time: i64) {
+    let (tyria_hours, tyria_minutes) = calculate_tyria_time(current_time);
+    let status = format!("Local {} / Tyria {:02}:{:02}", format_time_only(current_time), tyria_hours, tyria_minutes);
+
+    ui.set_mouse_cursor(Some(MouseCursor::Hand));
+    if ui.is_mouse_clicked(MouseButton::Left) {
+        ui.set_clipboard_text(&status);
+        NOW_COPIED_AT.with(|t| t.set(ui.time()));
+    }
+
+    let copied = ui.time() - NOW_COPIED_AT.with(|t| t.get()) < 1.5;
+    ui.tooltip(|| {
+        ui.text(&status);
+        if copied {
+            ui.text_colored([0.5, 0.9, 0.5, 1.0], "Copied to clipboard");
+        } else {
+            ui.text_disabled("Click to copy");
+        }
+    });
+}
 
 /// Render the time ruler
 /// - `label_offset`: horizontal offset for the timeline portion (when labels are on the left)
 /// - `tick_interval`: interval between tick marks
 /// - `show_current_time`: whether to display the current time text on the ruler
+/// - `markers`: time markers to label (their lines across the tracks are drawn per track)
+///
+/// Returns the time under the mouse while the ruler is hovered; click-dragging along it
+/// scrubs a preview cursor.
+#[allow(clippy::too_many_arguments)]
 pub fn render_time_ruler(
     ui: &Ui,
     current_time: i64,
@@ -14,7 +52,8 @@ pub fn render_time_ruler(
     label_offset: f32,
     tick_interval: TimeRulerInterval,
     show_current_time: bool,
-) {
+    markers: &[TimeMarker],
+) -> Option<RulerProbe> {
     let draw_list = ui.get_window_draw_list();
     let cursor_pos = ui.cursor_screen_pos();
     let available_width = ui.content_region_avail()[0];
@@ -62,19 +101,36 @@ pub fn render_time_ruler(
         }
     }
 
-    // Current time red line - positioned within timeline area
+    // Time markers: a full-height tick with the label to its right
+    let view_from = current_time - time_before_current as i64;
+    let view_until = current_time + time_after_current as i64 + 1;
+    for marker in markers.iter().filter(|m| m.enabled) {
+        for time in marker.times_between(view_from, view_until) {
+            let x_pos = timeline_start_x + ((time - current_time) as f32 + time_before_current) * pixels_per_second;
+            draw_list.add_line([x_pos, cursor_pos[1]], [x_pos, cursor_pos[1] + ruler_height], marker.color)
+                .thickness(1.5)
+                .build();
+            let text_y = cursor_pos[1] + (ruler_height - ui.text_line_height()) / 2.0;
+            draw_list.add_text([x_pos + 3.0, text_y], marker.color, &marker.label);
+        }
+    }
+
+    // Current time red line - positioned within timeline area (out of view when panned away)
     let current_time_x = timeline_start_x + (time_position * timeline_width);
-    draw_list.add_line(
-        [current_time_x, cursor_pos[1]],
-        [current_time_x, cursor_pos[1] + ruler_height],
-        [1.0, 0.0, 0.0, 1.0],
-    )
-    .thickness(2.0)
-    .build();
+    let now_in_view = (0.0..=1.0).contains(&time_position);
+    if now_in_view {
+        draw_list.add_line(
+            [current_time_x, cursor_pos[1]],
+            [current_time_x, cursor_pos[1] + ruler_height],
+            [1.0, 0.0, 0.0, 1.0],
+        )
+        .thickness(2.0)
+        .build();
+    }
 
     // Display current time text on the ruler if enabled
-    if show_current_time {
-        let time_text = format_time_only(current_time);
+    if show_current_time && now_in_view {
+        let time_text = label_cache::label("ruler_now", || format_time_only(current_time));
         let text_size = ui.calc_text_size(&time_text);
 
         // Position the text to the left of the current time line, or right if not enough space
@@ -85,26 +141,131 @@ pub fn render_time_ruler(
         };
         let text_y = cursor_pos[1] + (ruler_height - text_size[1]) / 2.0;
 
-        draw_list.add_text([text_x, text_y], [1.0, 1.0, 1.0, 0.9], &time_text);
+        draw_list.add_text([text_x, text_y], [1.0, 1.0, 1.0, 0.9], &*time_text);
+    }
+
+    // Button rather than a dummy so dragging scrubs instead of moving the window
+    ui.invisible_button("##time_ruler", [available_width, ruler_height]);
+
+    // The now marker copies the current time instead of starting a scrub
+    let on_now_marker = now_in_view && (ui.io().mouse_pos[0] - current_time_x).abs() <= NOW_LINE_GRAB;
+    if on_now_marker && ui.is_item_hovered() && !ui.is_mouse_dragging(MouseButton::Left) {
+        now_line_hovered(ui, current_time);
+        return None;
     }
 
-    ui.dummy([available_width, ruler_height]);
+    if ui.is_item_active() {
+        let mouse_x = (ui.io().mouse_pos[0] - timeline_start_x).clamp(0.0, timeline_width);
+        let time_offset = (mouse_x * view_range / timeline_width) - time_before_current;
+        let scrub_time = current_time + time_offset as i64;
+        let scrub_x = timeline_start_x + mouse_x;
+
+        draw_list.add_line(
+            [scrub_x, cursor_pos[1]],
+            [scrub_x, cursor_pos[1] + ruler_height],
+            [1.0, 1.0, 1.0, 0.9],
+        )
+        .thickness(2.0)
+        .build();
+
+        return Some(RulerProbe { time: scrub_time, x: scrub_x, scrubbing: true });
+    }
 
     if ui.is_item_hovered() {
-        let mouse_pos = ui.io().mouse_pos;
-        let mouse_x = mouse_pos[0] - timeline_start_x;
+        let mouse_x = ui.io().mouse_pos[0] - timeline_start_x;
 
-        // Only show tooltip if mouse is over the timeline portion
+        // Only over the timeline portion
         if mouse_x >= 0.0 && mouse_x <= timeline_width {
             let time_offset = (mouse_x * view_range / timeline_width) - time_before_current;
             let hover_time = current_time + time_offset as i64;
+            return Some(RulerProbe { time: hover_time, x: timeline_start_x + mouse_x, scrubbing: false });
+        }
+    }
 
-            let tyria_time = calculate_tyria_time(hover_time);
+    None
+}
 
-            ui.tooltip(|| {
-                ui.text(format!("Local: {}", format_time_only(hover_time)));
-                ui.text(format!("Tyria: {:02}:{:02}", tyria_time.0, tyria_time.1));
-            });
-        }
+/// Moment pointed at on the time ruler
+pub struct RulerProbe {
+    pub time: i64,
+    /// Screen x position of `time`
+    pub x: f32,
+    /// Held down to scrub, rather than only hovered
+    pub scrubbing: bool,
+}
+
+/// What the cycle minimap asks the main view to do
+pub enum MinimapAction {
+    /// Pan so the view is centered on this time
+    CenterOn(i64),
+    /// Follow the current time again
+    Reset,
+}
+
+/// Render a thin strip for the whole cycle [cycle_start, cycle_start + cycle), with the visible
+/// window boxed. Clicking or dragging centers the view there; right-click returns to now.
+pub fn render_cycle_minimap(
+    ui: &Ui,
+    current_time: i64,
+    cycle_start: i64,
+    cycle: i64,
+    view_start: i64,
+    view_range: f32,
+    label_offset: f32,
+) -> Option<MinimapAction> {
+    let draw_list = ui.get_window_draw_list();
+    let cursor_pos = ui.cursor_screen_pos();
+    let available_width = ui.content_region_avail()[0];
+    let strip_height = 8.0;
+
+    let strip_start_x = cursor_pos[0] + label_offset;
+    let strip_width = available_width - label_offset;
+    let pixels_per_second = strip_width / cycle as f32;
+    let time_to_x = |time: i64| strip_start_x + ((time - cycle_start) as f32 * pixels_per_second).clamp(0.0, strip_width);
+
+    draw_list.add_rect(
+        [strip_start_x, cursor_pos[1]],
+        [strip_start_x + strip_width, cursor_pos[1] + strip_height],
+        [0.1, 0.1, 0.1, 1.0],
+    )
+    .filled(true)
+    .build();
+
+    // Visible window
+    let view_min_x = time_to_x(view_start);
+    let view_max_x = time_to_x(view_start + view_range as i64);
+    if view_max_x > view_min_x {
+        draw_list.add_rect(
+            [view_min_x, cursor_pos[1]],
+            [view_max_x, cursor_pos[1] + strip_height],
+            [1.0, 1.0, 1.0, 0.25],
+        )
+        .filled(true)
+        .build();
+        draw_list.add_rect(
+            [view_min_x, cursor_pos[1]],
+            [view_max_x, cursor_pos[1] + strip_height],
+            [1.0, 1.0, 1.0, 0.7],
+        )
+        .build();
+    }
+
+    let now_x = time_to_x(current_time);
+    draw_list.add_line([now_x, cursor_pos[1]], [now_x, cursor_pos[1] + strip_height], [1.0, 0.0, 0.0, 1.0])
+        .thickness(2.0)
+        .build();
+
+    ui.invisible_button("##cycle_minimap", [available_width, strip_height]);
+
+    if ui.is_item_clicked_with_button(MouseButton::Right) {
+        return Some(MinimapAction::Reset);
+    }
+    if ui.is_item_active() {
+        let mouse_x = (ui.io().mouse_pos[0] - strip_start_x).clamp(0.0, strip_width);
+        return Some(MinimapAction::CenterOn(cycle_start + (mouse_x / pixels_per_second) as i64));
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Click or drag to pan the timeline, right-click to return to now");
     }
-}
\ No newline at end of file
+    None
+}
@@ -0,0 +1,353 @@
+use nexus::imgui::{ColorEdit, ColorEditFlags, Condition, InputText, Ui, Window};
+use std::cell::RefCell;
+
+use crate::config::{RUNTIME_CONFIG, SELECTED_EVENT, SELECTED_TRACK};
+use crate::json_loader::{EventColor, EventTrack, TimelineEvent, TimelineType};
+use crate::recurrence::parse_recurrence;
+use crate::time_utils::{format_duration, format_mmss, format_weekday_time, get_current_unix_time, parse_mmss};
+
+const STEPS: [&str; 4] = ["Name", "Schedule", "Spawns", "Color"];
+const DAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+/// Interval lengths (hours) that divide a day evenly
+const INTERVAL_HOURS: [i64; 7] = [1, 2, 3, 4, 6, 8, 12];
+/// Real seconds per Tyrian minute (a Tyrian day lasts two real hours)
+const TYRIAN_MINUTE: i64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Clock {
+    Local,
+    Tyrian,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Repeat {
+    Interval,
+    Daily,
+    Weekly,
+}
+
+struct WizardState {
+    step: usize,
+    name: String,
+    category: String,
+    event_name: String,
+    clock: Clock,
+    repeat: Repeat,
+    interval_index: usize,
+    days: [bool; 7],
+    spawns: Vec<String>,
+    new_spawn: String,
+    duration: i64,
+    color: [f32; 4],
+    error: Option<String>,
+}
+
+impl Default for WizardState {
+    fn default() -> Self {
+        Self {
+            step: 0,
+            name: String::new(),
+            category: "Custom".to_string(),
+            event_name: String::new(),
+            clock: Clock::Local,
+            repeat: Repeat::Daily,
+            interval_index: 1,
+            days: [false, false, false, false, false, true, true],
+            spawns: Vec::new(),
+            new_spawn: String::new(),
+            duration: 15 * 60,
+            color: EventColor::default().to_array(),
+            error: None,
+        }
+    }
+}
+
+thread_local! {
+    static WIZARD: RefCell<Option<WizardState>> = const { RefCell::new(None) };
+}
+
+/// Open the wizard with a fresh state
+pub fn open() {
+    WIZARD.with(|w| *w.borrow_mut() = Some(WizardState::default()));
+}
+
+/// Render the custom track wizard (call from main render loop)
+pub fn render_track_wizard(ui: &Ui) {
+    WIZARD.with(|w| {
+        let mut wizard = w.borrow_mut();
+        let Some(state) = wizard.as_mut() else {
+            return;
+        };
+
+        let mut opened = true;
+        let mut finished = false;
+        Window::new("New Custom Track")
+            .size([420.0, 380.0], Condition::FirstUseEver)
+            .opened(&mut opened)
+            .build(ui, || {
+                finished = render_wizard(ui, state);
+            });
+
+        if !opened || finished {
+            *wizard = None;
+        }
+    });
+}
+
+/// Returns true once the track has been created
+fn render_wizard(ui: &Ui, state: &mut WizardState) -> bool {
+    ui.text_disabled(format!("Step {} of {}: {}", state.step + 1, STEPS.len(), STEPS[state.step]));
+    ui.separator();
+
+    match state.step {
+        0 => render_name_step(ui, state),
+        1 => render_schedule_step(ui, state),
+        2 => render_spawns_step(ui, state),
+        _ => render_color_step(ui, state),
+    }
+
+    ui.separator();
+    if let Some(error) = &state.error {
+        ui.text_colored([1.0, 0.4, 0.4, 1.0], error);
+    }
+
+    if state.step > 0 && ui.button("Back") {
+        state.step -= 1;
+        state.error = None;
+    }
+    if state.step > 0 {
+        ui.same_line();
+    }
+
+    if state.step + 1 < STEPS.len() {
+        if ui.button("Next") {
+            state.error = validate_step(state).err();
+            if state.error.is_none() {
+                state.step += 1;
+            }
+        }
+        return false;
+    }
+
+    if ui.button("Create Track") {
+        match build_track(state) {
+            Ok(track) => {
+                let mut config = RUNTIME_CONFIG.lock();
+                if config.tracks.iter().any(|t| t.name == track.name) {
+                    state.error = Some(format!("A track named '{}' already exists", track.name));
+                    return false;
+                }
+                config.tracks.push(track);
+                // Open the regular editor on the new track for any fine-tuning
+                *SELECTED_TRACK.lock() = Some(config.tracks.len() - 1);
+                *SELECTED_EVENT.lock() = None;
+                return true;
+            }
+            Err(e) => state.error = Some(e),
+        }
+    }
+    false
+}
+
+fn render_name_step(ui: &Ui, state: &mut WizardState) {
+    InputText::new(ui, "Track Name", &mut state.name).hint("e.g. Guild Missions").build();
+    InputText::new(ui, "Category", &mut state.category).build();
+    InputText::new(ui, "Event Name", &mut state.event_name).hint("defaults to the track name").build();
+    ui.text_disabled("The event name is what notifications and the timeline show.");
+}
+
+fn render_schedule_step(ui: &Ui, state: &mut WizardState) {
+    ui.text("Spawn times are given in:");
+    ui.radio_button("My local time", &mut state.clock, Clock::Local);
+    ui.radio_button("Tyrian time (repeats every 2 real hours)", &mut state.clock, Clock::Tyrian);
+
+    if state.clock == Clock::Tyrian {
+        ui.text_disabled("Tyrian spawns repeat every Tyrian day.");
+        return;
+    }
+
+    ui.spacing();
+    ui.text("Repeats:");
+    ui.radio_button("Every few hours", &mut state.repeat, Repeat::Interval);
+    if state.repeat == Repeat::Interval {
+        ui.same_line();
+        let labels: Vec<String> = INTERVAL_HOURS.iter().map(|h| format!("every {}h", h)).collect();
+        ui.set_next_item_width(120.0);
+        ui.combo_simple_string("##wizard_interval", &mut state.interval_index, &labels);
+    }
+    ui.radio_button("Daily", &mut state.repeat, Repeat::Daily);
+    ui.radio_button("On certain days", &mut state.repeat, Repeat::Weekly);
+    if state.repeat == Repeat::Weekly {
+        ui.indent();
+        for (index, day) in DAY_NAMES.iter().enumerate() {
+            if index > 0 {
+                ui.same_line();
+            }
+            ui.checkbox(day, &mut state.days[index]);
+        }
+        ui.unindent();
+    }
+}
+
+fn render_spawns_step(ui: &Ui, state: &mut WizardState) {
+    let hint = match (state.clock, state.repeat) {
+        (Clock::Tyrian, _) => "Tyrian time, e.g. 18:00",
+        (Clock::Local, Repeat::Interval) => "time into the interval, e.g. :30 or 1:15",
+        (Clock::Local, _) => "local time, e.g. 20:00",
+    };
+
+    let mut to_remove = None;
+    for (index, spawn) in state.spawns.iter().enumerate() {
+        ui.bullet_text(spawn);
+        ui.same_line();
+        if ui.small_button(format!("X##wizard_spawn_{}", index)) {
+            to_remove = Some(index);
+        }
+    }
+    if let Some(index) = to_remove {
+        state.spawns.remove(index);
+    }
+    if state.spawns.is_empty() {
+        ui.text_disabled("No spawn times yet");
+    }
+
+    ui.set_next_item_width(200.0);
+    let entered = InputText::new(ui, "##wizard_new_spawn", &mut state.new_spawn)
+        .hint(hint)
+        .enter_returns_true(true)
+        .build();
+    ui.same_line();
+    if (ui.button("Add Time") || entered) && !state.new_spawn.trim().is_empty() {
+        let spawn = state.new_spawn.trim().to_string();
+        match spawn_offset(state, &spawn) {
+            Ok(_) => {
+                state.spawns.push(spawn);
+                state.new_spawn.clear();
+                state.error = None;
+            }
+            Err(e) => state.error = Some(e),
+        }
+    }
+
+    let mut duration = format_mmss(state.duration);
+    if InputText::new(ui, "Duration (mm:ss)", &mut duration).build() {
+        if let Some(value) = parse_mmss(&duration) {
+            state.duration = value.max(1);
+        }
+    }
+}
+
+fn render_color_step(ui: &Ui, state: &mut WizardState) {
+    ColorEdit::new("Color", &mut state.color).flags(ColorEditFlags::ALPHA_BAR).build(ui);
+
+    ui.spacing();
+    match build_track(state) {
+        Ok(track) => {
+            ui.text(format!("{} ({})", track.name, track.category));
+            ui.text_disabled(format!("{} event(s), {} each", track.events.len(), format_duration(state.duration)));
+            let now = get_current_unix_time();
+            let upcoming = track.occurrences_between(now, now + 8 * 24 * 3600);
+            for (event, start) in upcoming.iter().take(3) {
+                ui.text_colored(state.color, format!("{}  {}", format_weekday_time(*start), event.name));
+            }
+        }
+        Err(e) => ui.text_colored([1.0, 0.4, 0.4, 1.0], e),
+    }
+}
+
+fn validate_step(state: &WizardState) -> Result<(), String> {
+    match state.step {
+        0 if state.name.trim().is_empty() => Err("Enter a track name".to_string()),
+        1 if state.clock == Clock::Local && state.repeat == Repeat::Weekly && !state.days.contains(&true) => {
+            Err("Pick at least one day".to_string())
+        }
+        2 if state.spawns.is_empty() => Err("Add at least one spawn time".to_string()),
+        2 => build_track(state).map(|_| ()),
+        _ => Ok(()),
+    }
+}
+
+/// Offset of a Tyrian spawn within the Tyria cycle, or a check that a local time parses
+fn spawn_offset(state: &WizardState, spawn: &str) -> Result<i64, String> {
+    match state.clock {
+        Clock::Tyrian => {
+            let (hours, minutes) = spawn.split_once(':').ok_or_else(|| format!("Invalid time '{}'", spawn))?;
+            let hours: i64 = hours.trim().parse().map_err(|_| format!("Invalid time '{}'", spawn))?;
+            let minutes: i64 = minutes.trim().parse().map_err(|_| format!("Invalid time '{}'", spawn))?;
+            if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+                return Err(format!("Invalid time '{}'", spawn));
+            }
+            Ok((hours * 60 + minutes) * TYRIAN_MINUTE)
+        }
+        Clock::Local => parse_recurrence(&local_recurrence(state, &[spawn.to_string()])).map(|_| 0),
+    }
+}
+
+/// Recurrence expression for local-time spawns
+fn local_recurrence(state: &WizardState, spawns: &[String]) -> String {
+    let times = spawns.join(", ");
+    match state.repeat {
+        Repeat::Interval => format!("every {}h at {}", INTERVAL_HOURS[state.interval_index], times),
+        Repeat::Daily => format!("daily {}", times),
+        Repeat::Weekly => {
+            let days: Vec<&str> = DAY_NAMES
+                .iter()
+                .zip(state.days)
+                .filter(|(_, selected)| *selected)
+                .map(|(day, _)| *day)
+                .collect();
+            format!("{} {}", days.join(","), times)
+        }
+    }
+}
+
+fn build_track(state: &WizardState) -> Result<EventTrack, String> {
+    let name = state.name.trim().to_string();
+    let event_name = if state.event_name.trim().is_empty() { name.clone() } else { state.event_name.trim().to_string() };
+    let category = if state.category.trim().is_empty() { "Custom".to_string() } else { state.category.trim().to_string() };
+    let color = EventColor::from_array(state.color);
+
+    let (timeline_type, calculator, events) = match state.clock {
+        Clock::Tyrian => {
+            // One event per spawn on the Tyria cycle; names must stay unique for tracking
+            let mut events = Vec::new();
+            for spawn in &state.spawns {
+                let name = if state.spawns.len() == 1 { event_name.clone() } else { format!("{} ({})", event_name, spawn) };
+                events.push(TimelineEvent {
+                    name,
+                    start_offset: spawn_offset(state, spawn)?,
+                    duration: state.duration,
+                    cycle_duration: 2 * 3600,
+                    color: color.clone(),
+                    ..Default::default()
+                });
+            }
+            (TimelineType::GameTime, "tyria_cycle", events)
+        }
+        Clock::Local => {
+            let event = TimelineEvent {
+                name: event_name,
+                duration: state.duration,
+                color: color.clone(),
+                recurrence: local_recurrence(state, &state.spawns),
+                ..Default::default()
+            };
+            (TimelineType::RealTime, "local_day_start", vec![event])
+        }
+    };
+
+    let mut track = EventTrack {
+        name,
+        timeline_type,
+        events,
+        category,
+        base_time_calculator: calculator.to_string(),
+        ..Default::default()
+    };
+    track.refresh_base_time();
+    let base_time = track.base_time;
+    for event in &mut track.events {
+        event.compile_recurrence(base_time)?;
+    }
+    Ok(track)
+}
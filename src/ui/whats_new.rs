@@ -0,0 +1,165 @@
+// "What's New" window, shown once after the addon is updated.
+//
+// Changes are listed per version. On load the window opens with the entries newer than the
+// version recorded in the user config, and each entry can point at the settings it added.
+
+use nexus::imgui::{Condition, Ui, Window};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::config::{get_user_config_path, RUNTIME_CONFIG};
+use crate::json_loader::parse_version;
+use crate::ui::settings::{reveal_section, SettingsSection};
+
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+struct Change {
+    version: &'static str,
+    title: &'static str,
+    description: &'static str,
+    section: Option<SettingsSection>,
+}
+
+/// Newest first
+const CHANGES: &[Change] = &[
+    Change {
+        version: "1.2.0",
+        title: "Quick Access icon",
+        description: "The icon can show a badge with the number of events starting soon, a muted look while \
+                      notifications are muted, and the next event in its tooltip. Right-click it for quick toggles, \
+                      or pick a monochrome or custom icon.",
+        section: Some(SettingsSection::Notifications),
+    },
+    Change {
+        version: "1.2.0",
+        title: "Muting",
+        description: "Mute all notifications with a keybind or checkbox, or right-click an event to mute it until \
+                      its next spawn.",
+        section: Some(SettingsSection::Notifications),
+    },
+    Change {
+        version: "1.2.0",
+        title: "Custom track wizard and templates",
+        description: "Create custom tracks step by step in local or Tyrian time, start events from schedule \
+                      templates and preview their next occurrences.",
+        section: Some(SettingsSection::TrackManagement),
+    },
+    Change {
+        version: "1.2.0",
+        title: "Recurrence and conditions",
+        description: "Custom events can repeat with expressions like \"Sat,Sun 20:00\" and be limited by \
+                      conditions like tyria_night or day_of_week == \"Fri\".",
+        section: Some(SettingsSection::TrackManagement),
+    },
+    Change {
+        version: "1.2.0",
+        title: "Backups",
+        description: "Event data and settings are backed up automatically and can be restored from the settings.",
+        section: Some(SettingsSection::Backups),
+    },
+    Change {
+        version: "1.2.0",
+        title: "Windows and tooltips",
+        description: "Edge snapping, separate position/size/interaction locks, an optional title bar, tooltip \
+                      delay and a detailed tooltip mode.",
+        section: Some(SettingsSection::MainWindow),
+    },
+    Change {
+        version: "1.2.0",
+        title: "Agenda, planning mode and image export",
+        description: "A 24-hour agenda window, previewing the timeline at a chosen time and exporting it as a PNG.",
+        section: Some(SettingsSection::MainWindow),
+    },
+    Change {
+        version: "1.2.0",
+        title: "Owned expansions",
+        description: "Events needing content you don't own can be dimmed or hidden, and the timeline can follow \
+                      the map you're on.",
+        section: Some(SettingsSection::MainWindow),
+    },
+];
+
+/// Entries the open window lists, None while it's closed
+static SHOWN_CHANGES: Lazy<Mutex<Option<Vec<&'static Change>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Record the running version, opening the window if it brings changes the user hasn't seen.
+/// Call once on load, after the user config is loaded.
+pub fn check_version() {
+    let since = {
+        let mut config = RUNTIME_CONFIG.lock();
+        if config.last_seen_version == CURRENT_VERSION {
+            return;
+        }
+        std::mem::replace(&mut config.last_seen_version, CURRENT_VERSION.to_string())
+    };
+
+    // Nothing is new on a fresh install (settings from versions before this window have no version)
+    if since.is_empty() && !get_user_config_path().is_some_and(|p| p.exists()) {
+        return;
+    }
+
+    let (since, current) = (parse_version(&since), parse_version(CURRENT_VERSION));
+    let changes: Vec<&Change> = CHANGES
+        .iter()
+        .filter(|c| {
+            let version = parse_version(c.version);
+            version > since && version <= current
+        })
+        .collect();
+    if !changes.is_empty() {
+        *SHOWN_CHANGES.lock() = Some(changes);
+    }
+}
+
+/// Open the window with the full change list
+pub fn open() {
+    *SHOWN_CHANGES.lock() = Some(CHANGES.iter().collect());
+}
+
+/// Render the What's New window (call from main render loop)
+pub fn render_whats_new_window(ui: &Ui) {
+    let mut shown = SHOWN_CHANGES.lock();
+    let Some(changes) = shown.as_ref() else {
+        return;
+    };
+
+    let mut opened = true;
+    let mut close = false;
+    Window::new("Event Timers - What's New")
+        .size([460.0, 420.0], Condition::FirstUseEver)
+        .opened(&mut opened)
+        .build(ui, || {
+            let mut version = "";
+            for (index, change) in changes.iter().enumerate() {
+                if change.version != version {
+                    version = change.version;
+                    if index > 0 {
+                        ui.spacing();
+                    }
+                    ui.text_colored([1.0, 0.85, 0.4, 1.0], format!("Version {}", version));
+                    ui.separator();
+                }
+
+                ui.bullet_text(change.title);
+                ui.indent();
+                ui.text_wrapped(change.description);
+                if let Some(section) = change.section {
+                    if ui.small_button(format!("Show in Settings##whats_new_{}", index)) {
+                        reveal_section(section);
+                    }
+                }
+                ui.unindent();
+                ui.spacing();
+            }
+
+            ui.separator();
+            ui.text_disabled("Settings are in the Nexus options under Event Timers.");
+            if ui.button("Close") {
+                close = true;
+            }
+        });
+
+    if !opened || close {
+        *shown = None;
+    }
+}
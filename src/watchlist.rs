@@ -0,0 +1,264 @@
+// Watchlist patterns: events whose name matches any pattern are notified as if tracked.
+//
+// Patterns are a small regex subset, matched case-insensitively anywhere in the event name:
+//   Dragonstorm          plain keyword
+//   .*Convergence.*      . any character; * + ? repeat the previous item
+//   ^Tequatl|Triple      ^ and $ anchor, | separates alternatives
+//   Wyvern [a-z]         [...] character classes (ranges, a leading ^ negates), \ escapes
+// Groups are not supported.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Repeat {
+    One,
+    ZeroOrOne,
+    ZeroOrMore,
+    OneOrMore,
+}
+
+impl Repeat {
+    fn bounds(self) -> (usize, usize) {
+        match self {
+            Repeat::One => (1, 1),
+            Repeat::ZeroOrOne => (0, 1),
+            Repeat::ZeroOrMore => (0, usize::MAX),
+            Repeat::OneOrMore => (1, usize::MAX),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Atom {
+    Any,
+    Char(char),
+    Class { negated: bool, ranges: Vec<(char, char)> },
+}
+
+impl Atom {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Atom::Any => true,
+            Atom::Char(expected) => *expected == c,
+            Atom::Class { negated, ranges } => ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c)) != *negated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Item {
+    atom: Atom,
+    repeat: Repeat,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Alternative {
+    anchored_start: bool,
+    anchored_end: bool,
+    items: Vec<Item>,
+}
+
+/// A compiled watchlist pattern
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    alternatives: Vec<Alternative>,
+}
+
+impl Pattern {
+    pub fn is_match(&self, name: &str) -> bool {
+        let text: Vec<char> = name.to_lowercase().chars().collect();
+        self.alternatives.iter().any(|alt| {
+            if alt.anchored_start {
+                match_here(&alt.items, &text, alt.anchored_end)
+            } else {
+                (0..=text.len()).any(|start| match_here(&alt.items, &text[start..], alt.anchored_end))
+            }
+        })
+    }
+}
+
+/// Parse a watchlist pattern
+pub fn parse_pattern(source: &str) -> Result<Pattern, String> {
+    let source = source.trim();
+    if source.is_empty() {
+        return Err("Empty pattern".to_string());
+    }
+
+    let alternatives = split_alternatives(source)
+        .into_iter()
+        .map(parse_alternative)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Pattern { alternatives })
+}
+
+/// Compile every valid pattern, skipping invalid ones (the settings show their errors)
+pub fn compile_all(patterns: &[String]) -> Vec<Pattern> {
+    patterns.iter().filter_map(|p| parse_pattern(p).ok()).collect()
+}
+
+/// Split on '|' outside of classes and escapes
+fn split_alternatives(source: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut in_class = false;
+    let mut escaped = false;
+    for c in source.chars() {
+        if !escaped && !in_class && c == '|' {
+            parts.push(String::new());
+            continue;
+        }
+        if !escaped {
+            match c {
+                '[' => in_class = true,
+                ']' => in_class = false,
+                _ => {}
+            }
+        }
+        escaped = !escaped && c == '\\';
+        if let Some(part) = parts.last_mut() {
+            part.push(c);
+        }
+    }
+    parts
+}
+
+fn parse_alternative(source: String) -> Result<Alternative, String> {
+    let lowered = source.to_lowercase();
+    let mut chars: Vec<char> = lowered.chars().collect();
+
+    let anchored_start = chars.first() == Some(&'^');
+    if anchored_start {
+        chars.remove(0);
+    }
+    let anchored_end = chars.last() == Some(&'$') && !ends_with_escape(&chars[..chars.len() - 1]);
+    if anchored_end {
+        chars.pop();
+    }
+
+    let mut items: Vec<Item> = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let c = chars[pos];
+        pos += 1;
+        let atom = match c {
+            '.' => Atom::Any,
+            '\\' => {
+                let escaped = *chars.get(pos).ok_or("Pattern ends with '\\'")?;
+                pos += 1;
+                Atom::Char(escaped)
+            }
+            '[' => {
+                let (atom, next) = parse_class(&chars, pos)?;
+                pos = next;
+                atom
+            }
+            '*' | '+' | '?' => {
+                let Some(last) = items.last_mut().filter(|item| item.repeat == Repeat::One) else {
+                    return Err(format!("'{}' has nothing to repeat", c));
+                };
+                last.repeat = match c {
+                    '*' => Repeat::ZeroOrMore,
+                    '+' => Repeat::OneOrMore,
+                    _ => Repeat::ZeroOrOne,
+                };
+                continue;
+            }
+            '(' | ')' => return Err("Groups aren't supported, use | between whole alternatives".to_string()),
+            '^' | '$' => return Err(format!("'{}' only works at the start or end", c)),
+            _ => Atom::Char(c),
+        };
+        items.push(Item { atom, repeat: Repeat::One });
+    }
+    // An empty alternative would match every event
+    if items.is_empty() {
+        return Err("Empty alternative, check for a stray '|', '^' or '$'".to_string());
+    }
+
+    Ok(Alternative { anchored_start, anchored_end, items })
+}
+
+fn ends_with_escape(chars: &[char]) -> bool {
+    chars.iter().rev().take_while(|c| **c == '\\').count() % 2 == 1
+}
+
+/// Parse a class body starting after '[', returning the atom and the position after ']'
+fn parse_class(chars: &[char], mut pos: usize) -> Result<(Atom, usize), String> {
+    let negated = chars.get(pos) == Some(&'^');
+    if negated {
+        pos += 1;
+    }
+
+    let mut ranges = Vec::new();
+    loop {
+        let mut c = *chars.get(pos).ok_or("Missing ']'")?;
+        pos += 1;
+        if c == ']' && !ranges.is_empty() {
+            return Ok((Atom::Class { negated, ranges }, pos));
+        }
+        if c == '\\' {
+            c = *chars.get(pos).ok_or("Missing ']'")?;
+            pos += 1;
+        }
+        if chars.get(pos) == Some(&'-') && chars.get(pos + 1).is_some_and(|next| *next != ']') {
+            let end = chars[pos + 1];
+            if end < c {
+                return Err(format!("Invalid range '{}-{}'", c, end));
+            }
+            ranges.push((c, end));
+            pos += 2;
+        } else {
+            ranges.push((c, c));
+        }
+    }
+}
+
+/// Backtracking match of `items` against the start of `text`
+fn match_here(items: &[Item], text: &[char], anchored_end: bool) -> bool {
+    let Some((item, rest)) = items.split_first() else {
+        return !anchored_end || text.is_empty();
+    };
+
+    let (min, max) = item.repeat.bounds();
+    let mut count = 0;
+    while count < max && count < text.len() && item.atom.matches(text[count]) {
+        count += 1;
+    }
+    if count < min {
+        return false;
+    }
+    (min..=count).rev().any(|taken| match_here(rest, &text[taken..], anchored_end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        parse_pattern(pattern).unwrap().is_match(name)
+    }
+
+    #[test]
+    fn rejects_empty_alternatives() {
+        for pattern in ["Dragonstorm|", "|foo", "a||b", "^", "$", "^$", "a|^"] {
+            assert!(parse_pattern(pattern).is_err(), "{} should be rejected", pattern);
+        }
+        assert_eq!(parse_pattern("   "), Err("Empty pattern".to_string()));
+    }
+
+    #[test]
+    fn alternatives_match_independently() {
+        assert!(matches("Dragonstorm|Tequatl", "Tequatl the Sunless"));
+        assert!(matches("^Tequatl|Triple", "Triple Trouble"));
+        assert!(!matches("^Tequatl|Triple", "Defeat Tequatl"));
+        assert!(!matches("Dragonstorm|Tequatl", "Shatterer"));
+        assert!(matches("a\\|b", "A|B"));
+        assert!(matches("[|]", "a | b"));
+    }
+
+    #[test]
+    fn matches_case_insensitively_anywhere() {
+        assert!(matches("convergence", "Mount Balrior Convergence"));
+        assert!(matches(".*Balrior.+", "Mount Balrior Convergence"));
+        assert!(matches("Wyvern [a-z]", "Wyvern Matriarch"));
+        assert!(!matches("Wyvern [^a-z]", "Wyvern Matriarch"));
+        assert!(matches("anomaly$", "Ley-Line Anomaly"));
+        assert!(!matches("^anomaly", "Ley-Line Anomaly"));
+    }
+}
@@ -0,0 +1,240 @@
+// Generic webhook: an HTTP POST for every fired reminder, to any URL with a user-written body.
+//
+// Requests go out on a background thread with its own runtime, so a slow or unreachable endpoint
+// never touches the frame. Each fired reminder is posted on its own, so one failing request
+// doesn't hold up the rest; failed requests are retried with a growing delay and end up in the log.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderName, HeaderValue};
+
+use crate::addon_log;
+use crate::config::RuntimeConfig;
+use crate::ui::settings::update_client;
+
+/// Attempts after the first failed one
+const RETRIES: u32 = 3;
+
+/// A reminder that fired, with what the body template can refer to
+pub struct WebhookFire {
+    pub event: String,
+    pub track: String,
+    pub category: String,
+    pub reminder: String,
+    pub start_time: i64,
+    /// Until the reminder's target, 0 once it's passed
+    pub seconds_until: i64,
+    pub map: String,
+    pub copy_text: String,
+}
+
+/// Settings for one batch of requests
+pub struct WebhookTarget {
+    pub url: String,
+    pub headers: String,
+    pub body: String,
+    pub timeout_seconds: u32,
+    pub proxy: String,
+}
+
+impl WebhookTarget {
+    /// The webhook settings, with the download options' timeout and proxy
+    pub fn from_config(config: &RuntimeConfig) -> Self {
+        Self {
+            url: config.webhook_url.clone(),
+            headers: config.webhook_headers.clone(),
+            body: config.webhook_body.clone(),
+            timeout_seconds: config.update_timeout_seconds,
+            proxy: config.update_proxy.clone(),
+        }
+    }
+}
+
+/// JSON string contents for `value`, without the surrounding quotes
+fn escape(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap_or_default();
+    quoted.get(1..quoted.len().saturating_sub(1)).unwrap_or_default().to_string()
+}
+
+impl WebhookFire {
+    /// The body template with this reminder's placeholders filled in
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{event}", &escape(&self.event))
+            .replace("{track}", &escape(&self.track))
+            .replace("{category}", &escape(&self.category))
+            .replace("{reminder}", &escape(&self.reminder))
+            .replace("{minutes}", &((self.seconds_until.max(0) + 59) / 60).to_string())
+            .replace("{start_time}", &self.start_time.to_string())
+            .replace("{map}", &escape(&self.map))
+            .replace("{copy_text}", &escape(&self.copy_text))
+    }
+}
+
+/// One "Name: value" header per line (blank lines are skipped), or what's wrong with the first
+/// line that isn't a valid header
+pub fn parse_headers(headers: &str) -> Result<Vec<(HeaderName, HeaderValue)>, String> {
+    headers
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("Expected \"Name: value\", got '{}'", line.trim()))?;
+            let name = HeaderName::from_bytes(name.trim().as_bytes())
+                .map_err(|_| format!("Invalid header name '{}'", name.trim()))?;
+            let value = HeaderValue::from_str(value.trim()).map_err(|_| format!("Invalid value for header '{}'", name))?;
+            Ok((name, value))
+        })
+        .collect()
+}
+
+/// Send one request per fired reminder, in the background
+pub fn send(fires: Vec<WebhookFire>, target: WebhookTarget) {
+    if fires.is_empty() || target.url.trim().is_empty() {
+        return;
+    }
+    let headers = match parse_headers(&target.headers) {
+        Ok(headers) => headers,
+        Err(e) => {
+            addon_log::error(format!("Webhook not sent, check its headers: {}", e));
+            return;
+        }
+    };
+
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                addon_log::error(format!("Failed to create Tokio runtime for the webhook: {}", e));
+                return;
+            }
+        };
+        let client = match update_client(target.timeout_seconds, &target.proxy) {
+            Ok(client) => client,
+            Err(e) => {
+                addon_log::error(format!("Can't set up the webhook request: {}", e));
+                return;
+            }
+        };
+
+        runtime.block_on(async {
+            let headers = Arc::new(headers);
+            let mut requests = tokio::task::JoinSet::new();
+            for fire in fires {
+                let (client, url, headers) = (client.clone(), target.url.clone(), Arc::clone(&headers));
+                let body = fire.render(&target.body);
+                requests.spawn(async move {
+                    match post(&client, &url, &headers, body).await {
+                        Ok(()) => addon_log::info(format!("Webhook sent for {} ({})", fire.event, fire.reminder)),
+                        Err(e) => addon_log::error(format!("Webhook for {} failed: {}", fire.event, e)),
+                    }
+                });
+            }
+            while requests.join_next().await.is_some() {}
+        });
+    });
+}
+
+/// POST the body, retrying failed attempts after 2s, 4s, 8s. A request that can't be built
+/// (such as a malformed URL) fails the same way every time and isn't retried.
+async fn post(
+    client: &reqwest::Client,
+    url: &str,
+    headers: &[(HeaderName, HeaderValue)],
+    body: String,
+) -> Result<(), reqwest::Error> {
+    let mut delay = Duration::from_secs(2);
+    let mut attempt = 0;
+    loop {
+        let mut request = client
+            .post(url.trim())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.clone());
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+
+        match request.send().await.and_then(|response| response.error_for_status()) {
+            Err(e) if attempt < RETRIES && !e.is_builder() => {
+                attempt += 1;
+                addon_log::warn(format!(
+                    "Webhook request failed ({}), retry {} of {} in {}s",
+                    e, attempt, RETRIES, delay.as_secs()
+                ));
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            result => return result.map(|_| ()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fire() -> WebhookFire {
+        WebhookFire {
+            event: "Tequatl \"the Sunless\"".to_string(),
+            track: "World Bosses".to_string(),
+            category: "Core".to_string(),
+            reminder: "Starting soon".to_string(),
+            start_time: 1_759_276_800,
+            seconds_until: 301,
+            map: "Sparkfly Fen".to_string(),
+            copy_text: "[&BNABAAA=]".to_string(),
+        }
+    }
+
+    #[test]
+    fn render_fills_every_placeholder() {
+        let body = fire().render(
+            r#"{"content": "{event} / {track} / {category} / {reminder} / {minutes} / {start_time} / {map} / {copy_text}"}"#,
+        );
+        assert_eq!(
+            body,
+            r#"{"content": "Tequatl \"the Sunless\" / World Bosses / Core / Starting soon / 6 / 1759276800 / Sparkfly Fen / [&BNABAAA=]"}"#
+        );
+        assert!(serde_json::from_str::<serde_json::Value>(&body).is_ok());
+    }
+
+    #[test]
+    fn render_escapes_for_json_strings() {
+        let mut fire = fire();
+        fire.event = "Line\nbreak\\".to_string();
+        assert_eq!(fire.render("{event}"), r"Line\nbreak\\");
+    }
+
+    #[test]
+    fn render_rounds_minutes_up_and_stops_at_zero() {
+        let mut fire = fire();
+        fire.seconds_until = 60;
+        assert_eq!(fire.render("{minutes}"), "1");
+        fire.seconds_until = 0;
+        assert_eq!(fire.render("{minutes}"), "0");
+        fire.seconds_until = -120;
+        assert_eq!(fire.render("{minutes}"), "0");
+        assert_eq!(fire.render("no placeholders {unknown}"), "no placeholders {unknown}");
+    }
+
+    #[test]
+    fn parse_headers_reads_one_per_line() {
+        let headers = parse_headers("Authorization: Bearer abc:def\n\n  X-Source :  event-timers  \n").unwrap();
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers[0].0, "authorization");
+        assert_eq!(headers[0].1, "Bearer abc:def");
+        assert_eq!(headers[1].0, "x-source");
+        assert_eq!(headers[1].1, "event-timers");
+        assert!(parse_headers("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_headers_reports_the_bad_line() {
+        assert_eq!(parse_headers("X-Ok: 1\nno colon").unwrap_err(), "Expected \"Name: value\", got 'no colon'");
+        assert_eq!(parse_headers("Bad Name: 1").unwrap_err(), "Invalid header name 'Bad Name'");
+        assert_eq!(parse_headers(": 1").unwrap_err(), "Invalid header name ''");
+        assert_eq!(parse_headers("X-Bad: a\u{7f}b").unwrap_err(), "Invalid value for header 'x-bad'");
+    }
+}